@@ -1,12 +1,15 @@
 use crate::{First, Main, MainSchedulePlugin, Plugin, Plugins, StateTransition};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    event::{EventOverflow, EventUpdates},
     prelude::*,
     schedule::{
-        apply_state_transition, common_conditions::run_once as run_once_condition,
-        run_enter_schedule, BoxedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs,
-        ScheduleLabel,
+        add_computed_state_transition_systems, add_state_transition_systems,
+        add_sub_state_transition_systems, BoxedScheduleLabel, IntoSystemConfigs,
+        IntoSystemSetConfigs, ScheduleExport, ScheduleLabel,
     },
+    system::SystemErrorHandler,
+    world::{apply_async_tasks, AsyncTasks},
 };
 use bevy_utils::{tracing::debug, HashMap, HashSet};
 use std::{
@@ -74,8 +77,9 @@ pub struct App {
     sub_apps: HashMap<AppLabelId, SubApp>,
     plugin_registry: Vec<Box<dyn Plugin>>,
     plugin_name_added: HashSet<String>,
-    /// A private counter to prevent incorrect calls to `App::run()` from `Plugin::build()`
-    building_plugin_depth: usize,
+    /// The names of the plugins currently being built, innermost last. Used to prevent incorrect
+    /// calls to `App::run()` from `Plugin::build()`, and to record which plugin added a system.
+    building_plugins: Vec<String>,
 }
 
 impl Debug for App {
@@ -91,6 +95,8 @@ impl Debug for App {
 /// A [`SubApp`] contains its own [`Schedule`] and [`World`] separate from the main [`App`].
 /// This is useful for situations where data and data processing should be kept completely separate
 /// from the main application. The primary use of this feature in bevy is to enable pipelined rendering.
+/// Sub apps can also run independent worlds, such as simulation shards, stepped at their own
+/// [`SubAppStepRate`] and exchanging data through [`world_channel`]s.
 ///
 /// # Example
 ///
@@ -138,6 +144,25 @@ pub struct SubApp {
     /// A function that allows access to both the main [`App`] [`World`] and the [`SubApp`]. This is
     /// useful for moving data between the sub app and the main app.
     extract: Box<dyn Fn(&mut World, &mut App) + Send>,
+
+    step_rate: SubAppStepRate,
+    updates_since_step: u32,
+}
+
+/// How often a [`SubApp`] is stepped by [`App::update`].
+///
+/// Each step extracts data from the main world and runs the default schedule of the sub app.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubAppStepRate {
+    /// The sub app is stepped once per update of the main app.
+    #[default]
+    EveryUpdate,
+    /// The sub app is stepped once every `n` updates of the main app, starting with the `n`th.
+    EveryNthUpdate(u32),
+    /// The sub app is stepped `n` times per update of the main app.
+    StepsPerUpdate(u32),
+    /// The sub app is only stepped by [`App::step_sub_app`].
+    Manual,
 }
 
 impl SubApp {
@@ -151,6 +176,48 @@ impl SubApp {
         Self {
             app,
             extract: Box::new(extract),
+            step_rate: SubAppStepRate::EveryUpdate,
+            updates_since_step: 0,
+        }
+    }
+
+    /// Sets how often the [`SubApp`] is stepped by [`App::update`].
+    pub fn set_step_rate(&mut self, step_rate: SubAppStepRate) -> &mut Self {
+        self.step_rate = step_rate;
+        self.updates_since_step = 0;
+        self
+    }
+
+    /// Returns how often the [`SubApp`] is stepped by [`App::update`].
+    pub fn step_rate(&self) -> SubAppStepRate {
+        self.step_rate
+    }
+
+    /// Extracts data from the main world and runs the default schedule once.
+    pub fn step(&mut self, main_world: &mut World) {
+        self.extract(main_world);
+        self.run();
+    }
+
+    /// Steps the [`SubApp`] as many times as its [`SubAppStepRate`] requires for one update of
+    /// the main app.
+    fn update(&mut self, main_world: &mut World) {
+        let steps = match self.step_rate {
+            SubAppStepRate::EveryUpdate => 1,
+            SubAppStepRate::EveryNthUpdate(n) => {
+                self.updates_since_step += 1;
+                if self.updates_since_step >= n {
+                    self.updates_since_step = 0;
+                    1
+                } else {
+                    0
+                }
+            }
+            SubAppStepRate::StepsPerUpdate(n) => n,
+            SubAppStepRate::Manual => 0,
+        };
+        for _ in 0..steps {
+            self.step(main_world);
         }
     }
 
@@ -184,6 +251,8 @@ impl Default for App {
 
         app.add_plugins(MainSchedulePlugin);
         app.add_event::<AppExit>();
+        app.init_resource::<AsyncTasks>()
+            .add_systems(First, apply_async_tasks);
 
         #[cfg(feature = "bevy_ci_testing")]
         {
@@ -220,7 +289,7 @@ impl App {
             plugin_registry: Vec::default(),
             plugin_name_added: Default::default(),
             main_schedule_label: Box::new(Main),
-            building_plugin_depth: 0,
+            building_plugins: Vec::new(),
         }
     }
 
@@ -246,8 +315,7 @@ impl App {
         for (_label, sub_app) in self.sub_apps.iter_mut() {
             #[cfg(feature = "trace")]
             let _sub_app_span = info_span!("sub app", name = ?_label).entered();
-            sub_app.extract(&mut self.world);
-            sub_app.run();
+            sub_app.update(&mut self.world);
         }
 
         self.world.clear_trackers();
@@ -284,7 +352,7 @@ impl App {
         let _bevy_app_run_span = info_span!("bevy_app").entered();
 
         let mut app = std::mem::replace(self, App::empty());
-        if app.building_plugin_depth > 0 {
+        if !app.building_plugins.is_empty() {
             panic!("App::run() was called from within Plugin::build(), which is not allowed.");
         }
 
@@ -326,12 +394,12 @@ impl App {
         self.plugin_registry = plugin_registry;
     }
 
-    /// Adds [`State<S>`] and [`NextState<S>`] resources, [`OnEnter`] and [`OnExit`] schedules
-    /// for each state variant (if they don't already exist), an instance of [`apply_state_transition::<S>`] in
-    /// [`StateTransition`] so that transitions happen before [`Update`](crate::Update) and
-    /// a instance of [`run_enter_schedule::<S>`] in [`StateTransition`] with a
-    /// [`run_once`](`run_once_condition`) condition to run the on enter schedule of the
-    /// initial state.
+    /// Adds [`State<S>`] and [`NextState<S>`] resources, and the systems applying the transitions
+    /// of `S` in [`StateTransition`] so that transitions happen before [`Update`](crate::Update).
+    /// The [`OnEnter`] schedule of the initial state runs the first time [`StateTransition`] runs.
+    ///
+    /// The [`OnEnter`], [`OnExit`] and [`OnTransition`] schedules are run in the order
+    /// described by [`StateTransitionSteps`](bevy_ecs::schedule::StateTransitionSteps).
     ///
     /// If you would like to control how other systems run based on the current state,
     /// you can emulate this behavior using the [`in_state`] [`Condition`](bevy_ecs::schedule::Condition).
@@ -341,14 +409,7 @@ impl App {
     pub fn add_state<S: States>(&mut self) -> &mut Self {
         self.init_resource::<State<S>>()
             .init_resource::<NextState<S>>()
-            .add_systems(
-                StateTransition,
-                (
-                    run_enter_schedule::<S>.run_if(run_once_condition()),
-                    apply_state_transition::<S>,
-                )
-                    .chain(),
-            );
+            .edit_schedule(StateTransition, add_state_transition_systems::<S>);
 
         // The OnEnter, OnExit, and OnTransition schedules are lazily initialized
         // (i.e. when the first system is added to them), and World::try_run_schedule is used to fail
@@ -357,6 +418,25 @@ impl App {
         self
     }
 
+    /// Adds the [`NextState<S>`] resource and the systems creating, removing and applying the
+    /// transitions of the [sub-state](SubStates) `S` in [`StateTransition`].
+    ///
+    /// The [`State<S>`] resource only exists while the [source states](SubStates::SourceStates)
+    /// of `S` have the expected values, which are checked after their own transitions were applied.
+    /// The source states must be added to the app too.
+    pub fn add_sub_state<S: SubStates>(&mut self) -> &mut Self {
+        self.init_resource::<NextState<S>>()
+            .edit_schedule(StateTransition, add_sub_state_transition_systems::<S>)
+    }
+
+    /// Adds the systems updating the [computed state](ComputedStates) `S` in [`StateTransition`],
+    /// after the transitions of its [source states](ComputedStates::SourceStates) were applied.
+    ///
+    /// The source states must be added to the app too.
+    pub fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
+        self.edit_schedule(StateTransition, add_computed_state_transition_systems::<S>)
+    }
+
     /// Adds a system to the given schedule in this app's [`Schedules`].
     ///
     /// # Examples
@@ -379,13 +459,21 @@ impl App {
         schedule: impl ScheduleLabel,
         systems: impl IntoSystemConfigs<M>,
     ) -> &mut Self {
-        let mut schedules = self.world.resource_mut::<Schedules>();
+        // Systems added while a plugin is being built are attributed to it in ambiguity reports.
+        let plugin = self.building_plugins.last().cloned();
+        let add_systems = |schedule: &mut Schedule| {
+            match plugin {
+                Some(plugin) => schedule.add_systems_with_origin(plugin, systems),
+                None => schedule.add_systems(systems),
+            };
+        };
 
+        let mut schedules = self.world.resource_mut::<Schedules>();
         if let Some(schedule) = schedules.get_mut(&schedule) {
-            schedule.add_systems(systems);
+            add_systems(schedule);
         } else {
             let mut new_schedule = Schedule::new();
-            new_schedule.add_systems(systems);
+            add_systems(&mut new_schedule);
             schedules.insert(schedule, new_schedule);
         }
 
@@ -429,7 +517,7 @@ impl App {
     /// Setup the application to manage events of type `T`.
     ///
     /// This is done by adding a [`Resource`] of type [`Events::<T>`],
-    /// and inserting an [`update_system`](Events::update_system) into [`First`].
+    /// and inserting an [`event_update_system`] into [`First`].
     ///
     /// See [`Events`] for defining events.
     ///
@@ -450,12 +538,44 @@ impl App {
         T: Event,
     {
         if !self.world.contains_resource::<Events<T>>() {
-            self.init_resource::<Events<T>>()
-                .add_systems(First, Events::<T>::update_system);
+            self.init_resource::<Events<T>>().add_systems(
+                First,
+                bevy_ecs::event::event_update_system::<T>.in_set(EventUpdates),
+            );
         }
         self
     }
 
+    /// Setup the application to manage persistent events of type `T`, which are kept until every
+    /// [`EventReader`] has read them, even if its system does not run every frame.
+    ///
+    /// At most `capacity` events are kept, see [`EventOverflow`] for what happens when more are sent.
+    /// If the events were already added, they are made persistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::event::EventOverflow;
+    /// #
+    /// # #[derive(Event)]
+    /// # struct MyEvent;
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_persistent_event::<MyEvent>(1024, EventOverflow::DropOldest);
+    /// ```
+    pub fn add_persistent_event<T>(&mut self, capacity: usize, overflow: EventOverflow) -> &mut Self
+    where
+        T: Event,
+    {
+        self.add_event::<T>();
+        self.world
+            .resource_mut::<Events<T>>()
+            .set_persistent(capacity, overflow);
+        self
+    }
+
     /// Inserts a [`Resource`] to the current [`App`] and overwrites any [`Resource`] previously added of the same type.
     ///
     /// A [`Resource`] in Bevy represents globally unique data. [`Resource`]s must be added to Bevy apps
@@ -482,6 +602,34 @@ impl App {
         self
     }
 
+    /// Sets the [`SystemErrorHandler`] called when a system returning a [`Result`] fails.
+    ///
+    /// The handler is used by the main world and every sub app, including the ones inserted later.
+    /// Without a handler, failing systems panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{prelude::*, system::SystemErrorHandler};
+    /// #
+    /// fn load_settings() -> Result<(), SystemError> {
+    ///     let _file = std::fs::read_to_string("settings.ron")?;
+    ///     Ok(())
+    /// }
+    ///
+    /// App::new()
+    ///     .set_system_error_handler(SystemErrorHandler::warn())
+    ///     .add_systems(Startup, load_settings);
+    /// ```
+    pub fn set_system_error_handler(&mut self, handler: SystemErrorHandler) -> &mut Self {
+        for sub_app in self.sub_apps.values_mut() {
+            sub_app.app.world.insert_resource(handler.clone());
+        }
+        self.world.insert_resource(handler);
+        self
+    }
+
     /// Inserts a non-send resource to the app.
     ///
     /// You usually want to use [`insert_resource`](Self::insert_resource),
@@ -596,9 +744,9 @@ impl App {
         let plugin_position_in_registry = self.plugin_registry.len();
         self.plugin_registry.push(Box::new(PlaceholderPlugin));
 
-        self.building_plugin_depth += 1;
+        self.building_plugins.push(plugin.name().to_string());
         let result = catch_unwind(AssertUnwindSafe(|| plugin.build(self)));
-        self.building_plugin_depth -= 1;
+        self.building_plugins.pop();
         if let Err(payload) = result {
             resume_unwind(payload);
         }
@@ -773,10 +921,45 @@ impl App {
     }
 
     /// Inserts an existing sub app into the app
-    pub fn insert_sub_app(&mut self, label: impl AppLabel, sub_app: SubApp) {
+    pub fn insert_sub_app(&mut self, label: impl AppLabel, mut sub_app: SubApp) {
+        if let Some(handler) = self.world.get_resource::<SystemErrorHandler>() {
+            sub_app.app.world.insert_resource(handler.clone());
+        }
         self.sub_apps.insert(label.as_label(), sub_app);
     }
 
+    /// Sets how often the sub app with the given label is stepped by [`App::update`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `SubApp` doesn't exist.
+    pub fn sub_app_step_rate(
+        &mut self,
+        label: impl AppLabel,
+        step_rate: SubAppStepRate,
+    ) -> &mut Self {
+        let label = label.as_label();
+        match self.sub_apps.get_mut(&label) {
+            Some(sub_app) => sub_app.set_step_rate(step_rate),
+            None => panic!("Sub-App with label '{:?}' does not exist", label.as_str()),
+        };
+        self
+    }
+
+    /// Steps the sub app with the given label once, regardless of its [`SubAppStepRate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `SubApp` doesn't exist.
+    pub fn step_sub_app(&mut self, label: impl AppLabel) -> &mut Self {
+        let label = label.as_label();
+        match self.sub_apps.get_mut(&label) {
+            Some(sub_app) => sub_app.step(&mut self.world),
+            None => panic!("Sub-App with label '{:?}' does not exist", label.as_str()),
+        }
+        self
+    }
+
     /// Removes a sub app from the app. Returns [`None`] if the label doesn't exist.
     pub fn remove_sub_app(&mut self, label: impl AppLabel) -> Option<SubApp> {
         self.sub_apps.remove(&label.as_label())
@@ -848,6 +1031,30 @@ impl App {
 
         self
     }
+
+    /// Returns a [`ScheduleExport`] of every schedule of the [`App`], sorted by label, which can be
+    /// formatted as Graphviz DOT or JSON to inspect the systems, system sets and ordering
+    /// constraints of the app.
+    ///
+    /// Schedules of sub-apps are not included, use [`App::sub_app`] to export those. Systems only
+    /// have an execution order once their schedule has run at least once.
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// let mut app = App::new();
+    /// app.add_systems(Update, || {});
+    /// app.update();
+    ///
+    /// for export in app.export_schedules() {
+    ///     println!("{}", export.to_dot());
+    /// }
+    /// ```
+    pub fn export_schedules(&self) -> Vec<ScheduleExport> {
+        self.world
+            .get_resource::<Schedules>()
+            .map(Schedules::export)
+            .unwrap_or_default()
+    }
 }
 
 fn run_once(mut app: App) {
@@ -877,11 +1084,12 @@ pub struct AppExit;
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
-        schedule::{OnEnter, States},
-        system::Commands,
+        schedule::{NextState, OnEnter, Schedules, State, States, SubStates},
+        system::{Commands, ResMut, Resource},
+        world::{AsyncTasks, Mut},
     };
 
-    use crate::{App, Plugin};
+    use crate::{App, Plugin, Update};
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -944,6 +1152,22 @@ mod tests {
     enum AppState {
         #[default]
         MainMenu,
+        InGame,
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    enum Paused {
+        #[default]
+        Running,
+        Paused,
+    }
+
+    impl SubStates for Paused {
+        type SourceStates = AppState;
+
+        fn should_exist(app_state: AppState) -> bool {
+            app_state == AppState::InGame
+        }
     }
     fn bar(mut commands: Commands) {
         commands.spawn_empty();
@@ -972,4 +1196,123 @@ mod tests {
         app.world.run_schedule(OnEnter(AppState::MainMenu));
         assert_eq!(app.world.entities().len(), 2);
     }
+
+    #[test]
+    fn sub_state_exists_while_source_state_matches() {
+        let mut app = App::new();
+        app.add_state::<AppState>()
+            .add_sub_state::<Paused>()
+            .add_systems(OnEnter(Paused::Running), foo);
+
+        app.update();
+        assert!(app.world.get_resource::<State<Paused>>().is_none());
+
+        app.world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        app.update();
+        assert_eq!(*app.world.resource::<State<Paused>>(), Paused::Running);
+        assert_eq!(app.world.entities().len(), 1);
+
+        app.world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::MainMenu);
+        app.update();
+        assert!(app.world.get_resource::<State<Paused>>().is_none());
+    }
+
+    #[test]
+    fn ambiguity_report_names_plugin() {
+        #[derive(Resource)]
+        struct Counter(u32);
+
+        fn increment(mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn reset(mut counter: ResMut<Counter>) {
+            counter.0 = 0;
+        }
+
+        struct CounterPlugin;
+        impl Plugin for CounterPlugin {
+            fn build(&self, app: &mut App) {
+                app.add_systems(Update, increment);
+            }
+        }
+
+        let mut app = App::new();
+        app.insert_resource(Counter(0))
+            .add_plugins(CounterPlugin)
+            .add_systems(Update, reset);
+
+        let report = app
+            .world
+            .resource_scope(|world, mut schedules: Mut<Schedules>| {
+                schedules.get_mut(&Update).unwrap().initialize(world)
+            })
+            .unwrap();
+        assert_eq!(report.len(), 1);
+        let ambiguity = &report.ambiguities[0];
+        let (plugin_system, app_system) = if ambiguity.first.name.ends_with("increment") {
+            (&ambiguity.first, &ambiguity.second)
+        } else {
+            (&ambiguity.second, &ambiguity.first)
+        };
+        let origin = plugin_system.origin.as_deref().unwrap();
+        assert!(origin.ends_with("CounterPlugin"));
+        assert_eq!(app_system.origin, None);
+    }
+
+    #[test]
+    fn export_schedules_includes_app_systems() {
+        fn my_system() {}
+
+        struct MyPlugin;
+        impl Plugin for MyPlugin {
+            fn build(&self, app: &mut App) {
+                app.add_systems(Update, my_system);
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugins(MyPlugin);
+        app.update();
+
+        let exports = app.export_schedules();
+        let update = exports
+            .iter()
+            .find(|export| export.label.as_deref() == Some("Update"))
+            .unwrap();
+        let system = &update.systems[0];
+        assert!(system.name.ends_with("my_system"));
+        assert!(system.origin.as_deref().unwrap().ends_with("MyPlugin"));
+        assert_eq!(system.order, Some(0));
+        assert!(update.to_dot().contains("my_system"));
+    }
+
+    #[test]
+    fn async_tasks_run_each_update() {
+        #[derive(Resource, Default)]
+        struct Frames(u32);
+
+        let mut app = App::new();
+        app.init_resource::<Frames>();
+        app.world
+            .resource_mut::<AsyncTasks>()
+            .spawn(|world| async move {
+                for _ in 0..2 {
+                    world
+                        .with(|world| world.resource_mut::<Frames>().0 += 1)
+                        .await;
+                    world.next_frame().await;
+                }
+            });
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Frames>().0, 2);
+        assert!(app.world.resource::<AsyncTasks>().is_empty());
+    }
 }
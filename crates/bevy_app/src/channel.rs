@@ -0,0 +1,264 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy_ecs::{
+    event::{EventUpdates, Events},
+    prelude::*,
+};
+
+use crate::{App, AppLabel, AppLabelId, First, Last};
+
+/// Creates a typed channel to send values from one [`World`] to another, for example between the
+/// main app and a [`SubApp`](crate::SubApp).
+///
+/// Both ends are [`Resource`]s that can be inserted into different worlds. Values are received in
+/// the order they were sent, as soon as they are sent: since sub-apps are updated after the main
+/// app, values sent from the main app are received by sub-apps in the same
+/// [`update`](App::update), and values sent from sub-apps are received by the main app in the next
+/// one.
+///
+/// See [`App::add_event_channel`] to forward [`Event`]s between apps.
+///
+/// ```
+/// # use bevy_app::world_channel;
+/// # use bevy_ecs::prelude::*;
+/// let (sender, receiver) = world_channel::<u32>();
+///
+/// let mut server = World::new();
+/// let mut client = World::new();
+/// server.insert_resource(sender);
+/// client.insert_resource(receiver);
+///
+/// server.resource::<bevy_app::WorldSender<u32>>().send(7);
+/// let received: Vec<_> = client.resource::<bevy_app::WorldReceiver<u32>>().drain().collect();
+/// assert_eq!(received, vec![7]);
+/// ```
+pub fn world_channel<T: Send + 'static>() -> (WorldSender<T>, WorldReceiver<T>) {
+    let queue = Arc::new(Mutex::new(VecDeque::new()));
+    (
+        WorldSender {
+            queue: queue.clone(),
+        },
+        WorldReceiver { queue },
+    )
+}
+
+/// The sending end of a [`world_channel`].
+#[derive(Resource)]
+pub struct WorldSender<T: Send + 'static> {
+    queue: Arc<Mutex<VecDeque<T>>>,
+}
+
+/// The receiving end of a [`world_channel`].
+#[derive(Resource)]
+pub struct WorldReceiver<T: Send + 'static> {
+    queue: Arc<Mutex<VecDeque<T>>>,
+}
+
+impl<T: Send + 'static> WorldSender<T> {
+    /// Sends `value` to the [`WorldReceiver`] of this channel.
+    pub fn send(&self, value: T) {
+        self.queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push_back(value);
+    }
+
+    /// Sends every value of `values` to the [`WorldReceiver`] of this channel.
+    pub fn send_batch(&self, values: impl IntoIterator<Item = T>) {
+        self.queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(values);
+    }
+}
+
+impl<T: Send + 'static> Clone for WorldSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<T: Send + 'static> WorldReceiver<T> {
+    /// Receives the oldest value that has not been received yet.
+    pub fn try_recv(&self) -> Option<T> {
+        self.queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front()
+    }
+
+    /// Receives every value that has not been received yet, oldest first.
+    pub fn drain(&self) -> impl Iterator<Item = T> {
+        std::mem::take(&mut *self.queue.lock().unwrap_or_else(PoisonError::into_inner)).into_iter()
+    }
+
+    /// Returns the number of values waiting to be received.
+    pub fn len(&self) -> usize {
+        self.queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns `true` if no value is waiting to be received.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Identifies the main [`App`] or one of its sub-apps, as an end of an
+/// [event channel](App::add_event_channel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppTarget {
+    /// The main [`App`].
+    Main,
+    /// The sub-app with the given label.
+    SubApp(AppLabelId),
+}
+
+impl<L: AppLabel> From<L> for AppTarget {
+    fn from(label: L) -> Self {
+        AppTarget::SubApp(label.as_label())
+    }
+}
+
+impl App {
+    /// Forwards the events of type `T` sent in the app `from` to the app `to`, where they can be
+    /// read with an [`EventReader`] like any other event.
+    ///
+    /// Both apps must already exist, and `T` is added as an event to both of them. Events are
+    /// collected at the end of each update of `from` (in [`Last`]) and sent at the start of the next
+    /// update of `to` (in [`First`]).
+    ///
+    /// ```
+    /// # use bevy_app::{prelude::*, AppLabel, AppTarget, SubApp};
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+    /// struct ClientApp;
+    ///
+    /// #[derive(Event, Clone)]
+    /// struct Input(u32);
+    ///
+    /// let mut app = App::new();
+    /// app.insert_sub_app(ClientApp, SubApp::new(App::new(), |_, _| {}));
+    /// app.add_event_channel::<Input>(ClientApp, AppTarget::Main);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` is a sub-app that does not exist.
+    pub fn add_event_channel<T: Event + Clone>(
+        &mut self,
+        from: impl Into<AppTarget>,
+        to: impl Into<AppTarget>,
+    ) -> &mut Self {
+        let (sender, receiver) = world_channel::<T>();
+        self.target_app(from.into()).add_event::<T>().add_systems(
+            Last,
+            move |mut events: EventReader<T>| {
+                sender.send_batch(events.iter().cloned());
+            },
+        );
+        self.target_app(to.into()).add_event::<T>().add_systems(
+            First,
+            (move |mut events: ResMut<Events<T>>| {
+                events.extend(receiver.drain());
+            })
+            .after(EventUpdates),
+        );
+        self
+    }
+
+    fn target_app(&mut self, target: AppTarget) -> &mut App {
+        match target {
+            AppTarget::Main => self,
+            AppTarget::SubApp(label) => self.sub_app_mut(label),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+
+    use crate as bevy_app;
+    use crate::{App, AppLabel, AppTarget, SubApp, SubAppStepRate, Update};
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+    struct ServerApp;
+
+    #[derive(Event, Clone, Debug, PartialEq)]
+    struct Message(u32);
+
+    #[derive(Resource, Default)]
+    struct Received(Vec<u32>);
+
+    fn send_message(mut events: EventWriter<Message>, mut count: Local<u32>) {
+        *count += 1;
+        events.send(Message(*count));
+    }
+
+    fn receive_messages(mut events: EventReader<Message>, mut received: ResMut<Received>) {
+        received.0.extend(events.iter().map(|message| message.0));
+    }
+
+    #[test]
+    fn forwards_events_between_apps() {
+        let mut server = App::new();
+        server
+            .init_resource::<Received>()
+            .add_systems(Update, receive_messages);
+
+        let mut app = App::new();
+        app.insert_sub_app(ServerApp, SubApp::new(server, |_, _| {}));
+        app.add_event_channel::<Message>(AppTarget::Main, ServerApp)
+            .add_systems(Update, send_message);
+
+        app.update();
+        app.update();
+        assert_eq!(
+            app.sub_app(ServerApp).world.resource::<Received>().0,
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn sub_app_step_rates() {
+        #[derive(Resource, Default)]
+        struct Steps(u32);
+
+        fn count_steps(mut steps: ResMut<Steps>) {
+            steps.0 += 1;
+        }
+
+        let mut server = App::new();
+        server
+            .init_resource::<Steps>()
+            .add_systems(Update, count_steps);
+        let mut sub_app = SubApp::new(server, |_, _| {});
+        sub_app.set_step_rate(SubAppStepRate::EveryNthUpdate(3));
+
+        let mut app = App::new();
+        app.insert_sub_app(ServerApp, sub_app);
+        for _ in 0..6 {
+            app.update();
+        }
+        let steps = |app: &App| app.sub_app(ServerApp).world.resource::<Steps>().0;
+        assert_eq!(steps(&app), 2);
+
+        app.sub_app_step_rate(ServerApp, SubAppStepRate::StepsPerUpdate(2));
+        app.update();
+        assert_eq!(steps(&app), 4);
+
+        app.sub_app_step_rate(ServerApp, SubAppStepRate::Manual);
+        app.update();
+        assert_eq!(steps(&app), 4);
+        app.step_sub_app(ServerApp);
+        assert_eq!(steps(&app), 5);
+    }
+}
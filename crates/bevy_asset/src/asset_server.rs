@@ -1,17 +1,22 @@
 use crate::{
+    meta::{is_meta_path, LoaderName},
+    meta_path,
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, Assets, ErasedAssetLoader, Handle, HandleId, HandleUntyped, LabelId, LoadContext,
+    LoadState, RefChange, RefChangeChannel, Settings, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut, Resource};
 use bevy_log::warn;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{get_short_name, Entry, HashMap, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an [`AssetServer`].
@@ -30,6 +35,28 @@ pub enum AssetServerError {
         extensions: Vec<String>,
     },
 
+    /// No asset source with the given name was registered.
+    #[error("no asset source named {0}")]
+    MissingAssetSource(String),
+
+    /// No asset loader with the name given in a `.meta` file was found.
+    #[error("no `AssetLoader` named {0}")]
+    MissingAssetLoaderForName(String),
+
+    /// The `.meta` file of an asset could not be read.
+    #[error("invalid meta file {path:?}: {error}")]
+    InvalidMeta {
+        /// The path of the meta file.
+        path: PathBuf,
+        /// The error encountered while parsing the meta file.
+        error: ron::error::SpannedError,
+    },
+
+    /// The settings passed to [`AssetServer::load_with_settings`] are not the settings of the
+    /// asset's loader.
+    #[error("the settings type {0} does not match the settings of the asset's loader")]
+    IncorrectSettingsType(&'static str),
+
     /// The handle type does not match the type of the loaded asset.
     #[error("the given type does not match the type of the loaded asset")]
     IncorrectHandleType,
@@ -55,6 +82,9 @@ fn format_missing_asset_ext(exts: &[String]) -> String {
     }
 }
 
+type SettingsOverride =
+    Box<dyn Fn(&mut dyn Settings) -> Result<(), AssetServerError> + Send + Sync>;
+
 #[derive(Default)]
 pub(crate) struct AssetRefCounter {
     pub(crate) channel: Arc<RefChangeChannel>,
@@ -66,12 +96,14 @@ pub(crate) struct AssetRefCounter {
 ///
 /// [`AssetServer`] is the public API for interacting with the asset server.
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: Arc<dyn AssetIo>,
+    pub(crate) named_sources: RwLock<HashMap<String, Arc<dyn AssetIo>>>,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
-    loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
+    loaders: RwLock<Vec<Arc<dyn ErasedAssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    settings_overrides: RwLock<HashMap<SourcePathId, SettingsOverride>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
 }
 
@@ -129,20 +161,51 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                settings_overrides: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
-                asset_io,
+                asset_io: asset_io.into(),
+                named_sources: Default::default(),
             }),
         }
     }
 
-    /// Returns the associated asset I/O.
+    /// Returns the asset I/O of the default asset source.
     pub fn asset_io(&self) -> &dyn AssetIo {
         &*self.server.asset_io
     }
 
+    /// Adds an asset source with the given name, whose assets are loaded with paths such as
+    /// `name://path/to/asset.ext`.
+    ///
+    /// If a source with the same name was already added, it is replaced.
+    pub fn add_source(&self, name: impl Into<String>, asset_io: Box<dyn AssetIo>) {
+        self.server
+            .named_sources
+            .write()
+            .insert(name.into(), asset_io.into());
+    }
+
+    /// Returns the asset I/O of the asset source with the given name, or of the default asset
+    /// source if `source` is `None`.
+    pub fn get_source_io(
+        &self,
+        source: Option<&str>,
+    ) -> Result<Arc<dyn AssetIo>, AssetServerError> {
+        match source {
+            None => Ok(self.server.asset_io.clone()),
+            Some(name) => self
+                .server
+                .named_sources
+                .read()
+                .get(name)
+                .cloned()
+                .ok_or_else(|| AssetServerError::MissingAssetSource(name.to_string())),
+        }
+    }
+
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
         if self
             .server
@@ -167,7 +230,7 @@ impl AssetServer {
     {
         let mut loaders = self.server.loaders.write();
         let loader_index = loaders.len();
-        for extension in loader.extensions() {
+        for extension in AssetLoader::extensions(&loader) {
             self.server
                 .extension_to_loader_index
                 .write()
@@ -188,7 +251,10 @@ impl AssetServer {
         HandleUntyped::strong(id.into(), sender)
     }
 
-    fn get_asset_loader(&self, extension: &str) -> Result<Arc<dyn AssetLoader>, AssetServerError> {
+    fn get_asset_loader(
+        &self,
+        extension: &str,
+    ) -> Result<Arc<dyn ErasedAssetLoader>, AssetServerError> {
         let index = {
             // scope map to drop lock as soon as possible
             let map = self.server.extension_to_loader_index.read();
@@ -204,7 +270,7 @@ impl AssetServer {
     fn get_path_asset_loader<P: AsRef<Path>>(
         &self,
         path: P,
-    ) -> Result<Arc<dyn AssetLoader>, AssetServerError> {
+    ) -> Result<Arc<dyn ErasedAssetLoader>, AssetServerError> {
         let s = path
             .as_ref()
             .file_name()
@@ -231,6 +297,67 @@ impl AssetServer {
         })
     }
 
+    fn get_asset_loader_with_name(
+        &self,
+        name: &str,
+    ) -> Result<Arc<dyn ErasedAssetLoader>, AssetServerError> {
+        self.server
+            .loaders
+            .read()
+            .iter()
+            .rev()
+            .find(|loader| {
+                let type_name = loader.type_name();
+                type_name == name || get_short_name(type_name) == name
+            })
+            .cloned()
+            .ok_or_else(|| AssetServerError::MissingAssetLoaderForName(name.to_string()))
+    }
+
+    /// Picks the loader of the asset at `asset_path` and its settings, from the asset's meta file
+    /// if there is one, or from its extension otherwise.
+    fn get_loader_and_settings(
+        &self,
+        asset_path: &AssetPath,
+        meta: Option<&[u8]>,
+    ) -> Result<(Arc<dyn ErasedAssetLoader>, Box<dyn Settings>), AssetServerError> {
+        let path = asset_path.path();
+        let invalid_meta = |error| AssetServerError::InvalidMeta {
+            path: meta_path(path),
+            error,
+        };
+        let loader_name = meta
+            .map(LoaderName::from_bytes)
+            .transpose()
+            .map_err(invalid_meta)?
+            .and_then(|meta| meta.loader);
+        let loader = match loader_name {
+            Some(name) => self.get_asset_loader_with_name(&name)?,
+            None => self.get_path_asset_loader(path)?,
+        };
+        let mut settings = match meta {
+            Some(meta) => loader.deserialize_settings(meta).map_err(invalid_meta)?,
+            None => loader.default_settings(),
+        };
+        if let Some(settings_override) = self
+            .server
+            .settings_overrides
+            .read()
+            .get(&asset_path.get_id().source_path_id())
+        {
+            settings_override(&mut *settings)?;
+        }
+        Ok((loader, settings))
+    }
+
+    /// Returns the contents of a `.meta` file for the asset at `path`, naming the loader that
+    /// would be picked from its extension and holding the default settings of that loader.
+    ///
+    /// This can be used as a starting point to write the meta file of an asset.
+    pub fn get_default_meta<P: AsRef<Path>>(&self, path: P) -> Result<String, AssetServerError> {
+        Ok(self.get_path_asset_loader(path)?.default_meta())
+    }
+
     /// Gets the source path of an asset from the provided handle.
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
         self.server
@@ -305,7 +432,43 @@ impl AssetServer {
         self.load_untyped(path).typed()
     }
 
-    async fn load_async(
+    /// Queues an [`Asset`] at the provided relative path for asynchronous loading, changing the
+    /// settings of its loader with the provided function.
+    ///
+    /// The function is called with the settings read from the asset's `.meta` file, or with the
+    /// default settings of the loader if there is no meta file. `S` must be the
+    /// [`Settings`](AssetLoader::Settings) type of the asset's loader, otherwise the asset fails
+    /// to load. The function also applies when the asset is reloaded.
+    ///
+    /// If the asset is already loaded or loading, it is not loaded again with the new settings
+    /// until it is [reloaded](AssetServer::reload_asset).
+    ///
+    /// The function is kept until the asset is freed, so later calls to [`load`](AssetServer::load)
+    /// for the same path share the changed settings while the asset is alive. Calling this again
+    /// for the same path replaces the previous function.
+    ///
+    /// See [`load`](AssetServer::load).
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings<'a, T: Asset, S: Settings, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Handle<T> {
+        let path = path.into();
+        self.server.settings_overrides.write().insert(
+            path.get_id().source_path_id(),
+            Box::new(move |loader_settings: &mut dyn Settings| {
+                let loader_settings = loader_settings.downcast_mut::<S>().ok_or(
+                    AssetServerError::IncorrectSettingsType(std::any::type_name::<S>()),
+                )?;
+                settings(loader_settings);
+                Ok(())
+            }),
+        );
+        self.load(path)
+    }
+
+    pub(crate) async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
         force: bool,
@@ -353,17 +516,37 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
-        // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
-            Ok(loader) => loader,
+        let asset_io = match self.get_source_io(asset_path.source()) {
+            Ok(asset_io) => asset_io,
             Err(err) => {
                 set_asset_failed();
                 return Err(err);
             }
         };
 
+        // load the meta file of the asset, if it has one
+        let meta_path = meta_path(asset_path.path());
+        let meta = match asset_io.load_path(&meta_path).await {
+            Ok(meta) => Some(meta),
+            Err(AssetIoError::NotFound(_)) => None,
+            Err(err) => {
+                set_asset_failed();
+                return Err(AssetServerError::AssetIoError(err));
+            }
+        };
+
+        // get the according asset loader and its settings
+        let (asset_loader, settings) =
+            match self.get_loader_and_settings(&asset_path, meta.as_deref()) {
+                Ok(loader_and_settings) => loader_and_settings,
+                Err(err) => {
+                    set_asset_failed();
+                    return Err(err);
+                }
+            };
+
         // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
+        let bytes = match asset_io.load_path(asset_path.path()).await {
             Ok(bytes) => bytes,
             Err(err) => {
                 set_asset_failed();
@@ -373,14 +556,15 @@ impl AssetServer {
 
         // load the asset source using the corresponding AssetLoader
         let mut load_context = LoadContext::new(
+            asset_path.source(),
             asset_path.path(),
             &self.server.asset_ref_counter.channel,
-            self.asset_io(),
+            &*asset_io,
             version,
         );
 
         if let Err(err) = asset_loader
-            .load(&bytes, &mut load_context)
+            .load(&bytes, &*settings, &mut load_context)
             .await
             .map_err(AssetServerError::AssetLoaderError)
         {
@@ -422,9 +606,14 @@ impl AssetServer {
             }
         }
 
-        self.asset_io()
+        asset_io
             .watch_path_for_changes(asset_path.path(), None)
             .unwrap();
+        if meta.is_some() {
+            asset_io
+                .watch_path_for_changes(&meta_path, Some(asset_path.path().to_owned()))
+                .unwrap();
+        }
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
@@ -469,34 +658,48 @@ impl AssetServer {
 
     /// Loads assets from the specified folder recursively.
     ///
+    /// The folder may be in a named asset source, such as `mods://levels`.
+    ///
     /// # Errors
     ///
     /// - If the provided path is not a directory, it will fail with
     /// [`AssetServerError::AssetFolderNotADirectory`].
+    /// - If the asset source of the path was not added, it will fail with
+    /// [`AssetServerError::MissingAssetSource`].
     /// - If something unexpected happened while loading an asset, other
     /// [`AssetServerError`]s may be returned.
     #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
-    pub fn load_folder<P: AsRef<Path>>(
+    pub fn load_folder<'a, P: Into<AssetPath<'a>>>(
         &self,
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
-        if !self.asset_io().is_dir(path) {
+        let path = path.into();
+        let asset_io = self.get_source_io(path.source())?;
+        self.load_folder_from(&*asset_io, path.source(), path.path())
+    }
+
+    fn load_folder_from(
+        &self,
+        asset_io: &dyn AssetIo,
+        source: Option<&str>,
+        path: &Path,
+    ) -> Result<Vec<HandleUntyped>, AssetServerError> {
+        if !asset_io.is_dir(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
         let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
-            if self.asset_io().is_dir(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
+        for child_path in asset_io.read_directory(path)? {
+            if asset_io.is_dir(&child_path) {
+                handles.extend(self.load_folder_from(asset_io, source, &child_path)?);
             } else {
-                if self.get_path_asset_loader(&child_path).is_err() {
+                if is_meta_path(&child_path) || self.get_path_asset_loader(&child_path).is_err() {
                     continue;
                 }
                 let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string."));
+                    self.load_untyped(AssetPath::new_ref(&child_path, None).with_source(source));
                 handles.push(handle);
             }
         }
@@ -512,8 +715,19 @@ impl AssetServer {
             let ref_counts = self.server.asset_ref_counter.ref_counts.read();
             let asset_sources = self.server.asset_sources.read();
             let asset_lifecycles = self.server.asset_lifecycles.read();
+            let mut settings_overrides = None;
             for potential_free in potential_frees.drain(..) {
                 if let Some(&0) = ref_counts.get(&potential_free) {
+                    // the settings of `load_with_settings` live as long as the asset they were
+                    // given for, so that later loads of a freed asset use its meta file again
+                    if let HandleId::AssetPathId(id) = potential_free {
+                        if id.label_id() == LabelId::from(None) {
+                            settings_overrides
+                                .get_or_insert_with(|| self.server.settings_overrides.write())
+                                .remove(&id.source_path_id());
+                        }
+                    }
+
                     let type_uuid = match potential_free {
                         HandleId::Id(type_uuid, _) => Some(type_uuid),
                         HandleId::AssetPathId(id) => asset_sources
@@ -568,7 +782,8 @@ impl AssetServer {
                 .expect("Asset should exist at this point.");
             if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_value.type_uuid()) {
                 let asset_path =
-                    AssetPath::new_ref(load_context.path, label.as_ref().map(|l| l.as_str()));
+                    AssetPath::new_ref(load_context.path, label.as_ref().map(|l| l.as_str()))
+                        .with_source(load_context.source);
                 asset_lifecycle.create_asset(asset_path.into(), asset_value, load_context.version);
             } else {
                 panic!(
@@ -656,9 +871,12 @@ mod test {
 
     struct FakePngLoader;
     impl AssetLoader for FakePngLoader {
+        type Settings = ();
+
         fn load<'a>(
             &'a self,
             _: &'a [u8],
+            _: &'a (),
             ctx: &'a mut LoadContext,
         ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
             ctx.set_default_asset(LoadedAsset::new(PngAsset));
@@ -672,9 +890,12 @@ mod test {
 
     struct FailingLoader;
     impl AssetLoader for FailingLoader {
+        type Settings = ();
+
         fn load<'a>(
             &'a self,
             _: &'a [u8],
+            _: &'a (),
             _: &'a mut LoadContext,
         ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
             Box::pin(async { anyhow::bail!("failed") })
@@ -687,9 +908,12 @@ mod test {
 
     struct FakeMultipleDotLoader;
     impl AssetLoader for FakeMultipleDotLoader {
+        type Settings = ();
+
         fn load<'a>(
             &'a self,
             _: &'a [u8],
+            _: &'a (),
             _: &'a mut LoadContext,
         ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
             Box::pin(async move { Ok(()) })
@@ -923,6 +1147,157 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "4f3a4c2e-6a53-4f61-9d5c-0f7e5c8c1b2a"]
+    struct NumAsset(u32);
+
+    #[derive(Default, serde::Serialize, serde::Deserialize)]
+    struct NumSettings {
+        value: u32,
+    }
+
+    struct NumLoader;
+    impl AssetLoader for NumLoader {
+        type Settings = NumSettings;
+
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            settings: &'a NumSettings,
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            ctx.set_default_asset(LoadedAsset::new(NumAsset(settings.value)));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["num"]
+        }
+    }
+
+    #[test]
+    fn test_meta_files_and_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "default.num",
+            "meta.num",
+            "named.data",
+            "override.num",
+            "wrong.num",
+        ] {
+            std::fs::write(dir.path().join(file), []).unwrap();
+        }
+        std::fs::write(dir.path().join("meta.num.meta"), "(settings: (value: 3))").unwrap();
+        std::fs::write(
+            dir.path().join("named.data.meta"),
+            "(loader: \"NumLoader\", settings: (value: 4))",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("override.num.meta"),
+            "(settings: (value: 3))",
+        )
+        .unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(NumLoader);
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<NumAsset>());
+        app.insert_resource(asset_server.clone());
+        app.add_systems(Update, update_asset_storage_system::<NumAsset>);
+
+        let load = |handle: &Handle<NumAsset>| {
+            let path = asset_server.get_handle_path(handle).unwrap();
+            futures_lite::future::block_on(asset_server.load_async(path, true))
+        };
+
+        let default: Handle<NumAsset> = asset_server.load("default.num");
+        let meta: Handle<NumAsset> = asset_server.load("meta.num");
+        let named: Handle<NumAsset> = asset_server.load("named.data");
+        let overridden: Handle<NumAsset> =
+            asset_server.load_with_settings("override.num", |s: &mut NumSettings| s.value *= 2);
+        let wrong: Handle<NumAsset> =
+            asset_server.load_with_settings("wrong.num", |_: &mut u32| {});
+        for handle in [&default, &meta, &named, &overridden] {
+            load(handle).unwrap();
+        }
+        assert!(matches!(
+            load(&wrong),
+            Err(AssetServerError::IncorrectSettingsType(_))
+        ));
+        app.update();
+
+        let assets = app.world.resource::<Assets<NumAsset>>();
+        assert_eq!(assets.get(&default).unwrap().0, 0);
+        assert_eq!(assets.get(&meta).unwrap().0, 3);
+        assert_eq!(assets.get(&named).unwrap().0, 4);
+        assert_eq!(assets.get(&overridden).unwrap().0, 6);
+        assert_eq!(asset_server.get_load_state(&wrong), LoadState::Failed);
+
+        // the changed settings are dropped once the asset is freed
+        drop(overridden);
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+        app.update();
+        let reloaded: Handle<NumAsset> = asset_server.load("override.num");
+        load(&reloaded).unwrap();
+        app.update();
+        let assets = app.world.resource::<Assets<NumAsset>>();
+        assert_eq!(assets.get(&reloaded).unwrap().0, 3);
+    }
+
+    #[test]
+    fn test_default_meta() {
+        let asset_server = setup(".");
+        asset_server.add_loader(NumLoader);
+        let meta = asset_server.get_default_meta("file.num").unwrap();
+        assert!(meta.contains("NumLoader"));
+        assert!(meta.contains("value: 0"));
+    }
+
+    #[test]
+    fn test_named_sources() {
+        use crate::FileAssetIo;
+
+        let default_dir = tempfile::tempdir().unwrap();
+        let mods_dir = tempfile::tempdir().unwrap();
+        std::fs::write(default_dir.path().join("a.num"), []).unwrap();
+        std::fs::create_dir(mods_dir.path().join("levels")).unwrap();
+        std::fs::write(mods_dir.path().join("levels/a.num"), []).unwrap();
+        std::fs::write(
+            mods_dir.path().join("levels/a.num.meta"),
+            "(settings: (value: 5))",
+        )
+        .unwrap();
+
+        let asset_server = setup(default_dir.path());
+        asset_server.add_source("mods", Box::new(FileAssetIo::new(mods_dir.path(), &None)));
+        asset_server.add_loader(NumLoader);
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<NumAsset>());
+        app.insert_resource(asset_server.clone());
+        app.add_systems(Update, update_asset_storage_system::<NumAsset>);
+
+        let load =
+            |path: &str| futures_lite::future::block_on(asset_server.load_async(path.into(), true));
+        load("a.num").unwrap();
+        load("mods://levels/a.num").unwrap();
+        assert!(matches!(
+            load("missing://a.num"),
+            Err(AssetServerError::MissingAssetSource(name)) if name == "missing"
+        ));
+        let folder = asset_server.load_folder("mods://levels").unwrap();
+        assert_eq!(folder.len(), 1);
+        app.update();
+
+        let assets = app.world.resource::<Assets<NumAsset>>();
+        let default: Handle<NumAsset> = asset_server.get_handle("a.num");
+        let named: Handle<NumAsset> = asset_server.get_handle("mods://levels/a.num");
+        assert_eq!(assets.get(&default).unwrap().0, 0);
+        assert_eq!(assets.get(&named).unwrap().0, 5);
+        assert_eq!(folder[0].id(), named.id());
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -1,6 +1,7 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetEvents, AssetLoader, AssetServer, Handle, HandleId,
-    LoadAssets, RefChange, ReflectAsset, ReflectHandle,
+    update_asset_storage_system, Asset, AssetEvents, AssetLoader, AssetPlugin, AssetProcessor,
+    AssetServer, AssetSourceBuilder, AssetSourceBuilders, Handle, HandleId, LoadAssets, Process,
+    RefChange, ReflectAsset, ReflectHandle,
 };
 use bevy_app::App;
 use bevy_ecs::prelude::*;
@@ -317,6 +318,26 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+
+    /// Adds the provided process to the [`AssetProcessor`].
+    ///
+    /// Does nothing if the app does not process assets, see [`AssetMode`](crate::AssetMode).
+    fn add_asset_process<T>(&mut self, process: T) -> &mut Self
+    where
+        T: Process;
+
+    /// Registers an asset source with the given name, whose assets are loaded with paths such as
+    /// `name://path/to/asset.ext`.
+    ///
+    /// # Panics
+    ///
+    /// Asset sources are built by the [`AssetPlugin`], so this panics if the plugin was already
+    /// added.
+    fn register_asset_source(
+        &mut self,
+        name: impl Into<String>,
+        source: AssetSourceBuilder,
+    ) -> &mut Self;
 }
 
 impl AddAsset for App {
@@ -404,6 +425,33 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn add_asset_process<T>(&mut self, process: T) -> &mut Self
+    where
+        T: Process,
+    {
+        if let Some(processor) = self.world.get_resource::<AssetProcessor>() {
+            processor.add_process(process);
+        }
+        self
+    }
+
+    fn register_asset_source(
+        &mut self,
+        name: impl Into<String>,
+        source: AssetSourceBuilder,
+    ) -> &mut Self {
+        let name = name.into();
+        if self.is_plugin_added::<AssetPlugin>() {
+            panic!(
+                "The asset source {name:?} must be registered before the `AssetPlugin` is added."
+            );
+        }
+        self.world
+            .get_resource_or_insert_with(AssetSourceBuilders::default)
+            .insert(name, source);
+        self
+    }
 }
 
 /// Loads an internal asset from a project source file.
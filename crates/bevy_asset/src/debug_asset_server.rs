@@ -74,6 +74,7 @@ impl Plugin for DebugAssetServerPlugin {
         debug_asset_app.add_plugins(AssetPlugin {
             asset_folder: "crates".to_string(),
             watch_for_changes: ChangeWatcher::with_delay(Duration::from_millis(200)),
+            ..Default::default()
         });
         app.insert_non_send_resource(DebugAssetApp(debug_asset_app));
         app.add_systems(Update, run_debug_asset_app);
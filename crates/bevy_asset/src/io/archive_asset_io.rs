@@ -0,0 +1,703 @@
+use crate::{AssetHash, AssetIo, AssetIoError, ChangeWatcher, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// The magic bytes at the start of every asset archive.
+pub const ARCHIVE_MAGIC: [u8; 8] = *b"BEVYPACK";
+
+/// The version of the asset archive format written by [`AssetArchiveBuilder`].
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// How the contents of an archive entry are stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArchiveCompression {
+    /// The contents are stored as-is.
+    #[default]
+    None,
+    /// The contents are compressed with [zstd](https://facebook.github.io/zstd/) at the given
+    /// level. Requires the `zstd` feature to build and to read archives.
+    Zstd(i32),
+}
+
+/// An entry of the index of an asset archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// How the contents of the entry are stored. The level of [`ArchiveCompression::Zstd`] is not
+    /// recorded in archives and is always `0` when read.
+    pub compression: ArchiveCompression,
+    /// The offset of the stored contents from the start of the archive.
+    pub offset: u64,
+    /// The length of the stored contents.
+    pub stored_len: u64,
+    /// The length of the contents once decompressed.
+    pub len: u64,
+    /// The 64-bit FNV-1a hash of the decompressed contents.
+    pub hash: u64,
+}
+
+enum ArchiveData {
+    File(PathBuf),
+    Bytes(Cow<'static, [u8]>),
+}
+
+/// I/O implementation for assets packed in a single archive file.
+///
+/// Archives are built with an [`AssetArchiveBuilder`]. The index is read once when the archive is
+/// opened, and every asset is checked against the hash recorded in the index when it is loaded.
+///
+/// # Format
+///
+/// An archive starts with a header indexing every entry, followed by the contents of the entries.
+/// All integers are little-endian.
+///
+/// | field         | type             | description                 |
+/// |---------------|------------------|-----------------------------|
+/// | `magic`       | `[u8; 8]`        | [`ARCHIVE_MAGIC`]           |
+/// | `version`     | `u32`            | [`ARCHIVE_VERSION`]         |
+/// | `entry_count` | `u32`            | the number of entries       |
+/// | `entries`     | `[entry; count]` | the index, see below        |
+/// | `data`        | `[u8]`           | the contents of the entries |
+///
+/// Each entry of the index is:
+///
+/// | field         | type             | description                                         |
+/// |---------------|------------------|-----------------------------------------------------|
+/// | `path_len`    | `u32`            | the length of `path`                                |
+/// | `path`        | `[u8; path_len]` | the UTF-8 path of the asset, separated by `/`       |
+/// | `compression` | `u8`             | `0` if stored as-is, `1` if compressed with zstd    |
+/// | `offset`      | `u64`            | the offset of the contents from the archive start   |
+/// | `stored_len`  | `u64`            | the length of the stored contents                   |
+/// | `len`         | `u64`            | the length of the contents once decompressed        |
+/// | `hash`        | `u64`            | the 64-bit FNV-1a hash of the decompressed contents |
+///
+/// Directories are not stored, they exist implicitly for the parents of every entry.
+///
+/// ```no_run
+/// # use bevy_asset::*;
+/// # use bevy_app::App;
+/// # let mut app = App::new();
+/// app.register_asset_source(
+///     "packed",
+///     AssetSourceBuilder::new(|| ArchiveAssetIo::open("assets.pak").unwrap()),
+/// );
+/// ```
+pub struct ArchiveAssetIo {
+    data: ArchiveData,
+    entries: HashMap<PathBuf, ArchiveEntry>,
+    directories: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+impl ArchiveAssetIo {
+    /// Opens the archive at `path`, reading its index.
+    ///
+    /// The contents of the assets are read from the file when they are loaded.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, AssetIoError> {
+        let path = path.into();
+        let file = File::open(&path)?;
+        let archive_len = file.metadata()?.len();
+        let entries = read_index(&mut BufReader::new(file), archive_len)?;
+        Ok(Self::with_entries(ArchiveData::File(path), entries))
+    }
+
+    /// Reads the archive held in `bytes`, such as an archive included in the binary with
+    /// [`include_bytes!`].
+    pub fn from_bytes(bytes: impl Into<Cow<'static, [u8]>>) -> Result<Self, AssetIoError> {
+        let bytes = bytes.into();
+        let entries = read_index(&mut &*bytes, bytes.len() as u64)?;
+        Ok(Self::with_entries(ArchiveData::Bytes(bytes), entries))
+    }
+
+    fn with_entries(data: ArchiveData, entries: HashMap<PathBuf, ArchiveEntry>) -> Self {
+        let mut directories: HashMap<PathBuf, HashSet<PathBuf>> = HashMap::default();
+        for path in entries.keys() {
+            let mut child = path.as_path();
+            while let Some(parent) = child.parent() {
+                let new_child = directories
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .insert(child.to_owned());
+                if !new_child {
+                    // the ancestors of `parent` were already added
+                    break;
+                }
+                child = parent;
+            }
+        }
+        Self {
+            data,
+            entries,
+            directories,
+        }
+    }
+
+    /// Gets the index entry of the asset at `path`.
+    pub fn entry(&self, path: &Path) -> Option<&ArchiveEntry> {
+        self.entries.get(path)
+    }
+
+    /// Iterates over the paths of the assets in the archive and their index entries.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &ArchiveEntry)> {
+        self.entries
+            .iter()
+            .map(|(path, entry)| (path.as_path(), entry))
+    }
+
+    fn read_stored(&self, entry: &ArchiveEntry) -> Result<Vec<u8>, AssetIoError> {
+        match &self.data {
+            ArchiveData::File(path) => {
+                let mut file = File::open(path)?;
+                file.seek(SeekFrom::Start(entry.offset))?;
+                // the file may have been truncated since its index was read
+                let stored = read_at_most(&mut file, entry.stored_len)?;
+                if stored.len() as u64 != entry.stored_len {
+                    return Err(invalid_data("archive entry is out of bounds").into());
+                }
+                Ok(stored)
+            }
+            ArchiveData::Bytes(bytes) => {
+                let start = to_usize(entry.offset)?;
+                let end = start.saturating_add(to_usize(entry.stored_len)?);
+                bytes
+                    .get(start..end)
+                    .map(<[u8]>::to_vec)
+                    .ok_or_else(|| invalid_data("archive entry is out of bounds").into())
+            }
+        }
+    }
+}
+
+impl AssetIo for ArchiveAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let entry = self
+                .entries
+                .get(path)
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+            let stored = self.read_stored(entry)?;
+            let bytes = decompress(entry, stored)?;
+            if bytes.len() as u64 != entry.len || content_hash(&bytes) != entry.hash {
+                return Err(invalid_data(format!(
+                    "the contents of {path:?} do not match the archive index"
+                ))
+                .into());
+            }
+            Ok(bytes)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        match self.directories.get(path) {
+            Some(children) => Ok(Box::new(children.clone().into_iter())),
+            None if path.as_os_str().is_empty() => Ok(Box::new(std::iter::empty())),
+            None => Err(AssetIoError::NotFound(path.to_owned())),
+        }
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        if self.entries.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if self.directories.contains_key(path) || path.as_os_str().is_empty() {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(
+        &self,
+        _to_watch: &Path,
+        _to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self, _configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+        bevy_log::warn!("Watching for changes is not supported for asset archives");
+        Ok(())
+    }
+}
+
+/// Builds an asset archive that can be read with an [`ArchiveAssetIo`].
+///
+/// ```no_run
+/// # use bevy_asset::*;
+/// AssetArchiveBuilder::new()
+///     .with_compression(ArchiveCompression::Zstd(3))
+///     .add_directory("assets")
+///     .unwrap()
+///     .write_to_file("assets.pak")
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct AssetArchiveBuilder {
+    compression: ArchiveCompression,
+    files: BTreeMap<String, (ArchiveCompression, Vec<u8>)>,
+}
+
+impl AssetArchiveBuilder {
+    /// Creates a builder for an empty archive.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compression of the files added after this call without an explicit compression.
+    ///
+    /// Compressed files that end up larger than their contents are stored as-is.
+    #[must_use]
+    pub fn with_compression(mut self, compression: ArchiveCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Adds the file at the relative `path` in the archive, replacing any file at the same path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is not a relative path made of UTF-8 names.
+    pub fn add_file(&mut self, path: impl AsRef<Path>, bytes: impl Into<Vec<u8>>) -> &mut Self {
+        let compression = self.compression;
+        self.add_file_with_compression(path, bytes, compression)
+    }
+
+    /// Adds the file at the relative `path` in the archive with the given compression, replacing
+    /// any file at the same path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is not a relative path made of UTF-8 names.
+    pub fn add_file_with_compression(
+        &mut self,
+        path: impl AsRef<Path>,
+        bytes: impl Into<Vec<u8>>,
+        compression: ArchiveCompression,
+    ) -> &mut Self {
+        self.files
+            .insert(archive_path(path.as_ref()), (compression, bytes.into()));
+        self
+    }
+
+    /// Adds every file in the directory at `directory`, recursively, with paths relative to it.
+    pub fn add_directory(
+        &mut self,
+        directory: impl AsRef<Path>,
+    ) -> Result<&mut Self, AssetIoError> {
+        let directory = directory.as_ref();
+        let mut pending = vec![directory.to_owned()];
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    let bytes = fs::read(&path)?;
+                    self.add_file(path.strip_prefix(directory).unwrap(), bytes);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Writes the archive to `writer`.
+    pub fn write(&self, mut writer: impl Write) -> Result<(), AssetIoError> {
+        let mut stored_files = Vec::with_capacity(self.files.len());
+        for (path, (compression, bytes)) in &self.files {
+            let (compression, stored) = match compress(*compression, bytes)? {
+                Some(compressed) if compressed.len() < bytes.len() => (*compression, compressed),
+                _ => (ArchiveCompression::None, bytes.clone()),
+            };
+            stored_files.push((path, compression, stored, bytes));
+        }
+
+        let index_len: usize = stored_files
+            .iter()
+            .map(|(path, ..)| 4 + path.len() + 1 + 4 * 8)
+            .sum();
+        let mut offset = (ARCHIVE_MAGIC.len() + 4 + 4 + index_len) as u64;
+
+        writer.write_all(&ARCHIVE_MAGIC)?;
+        writer.write_all(&ARCHIVE_VERSION.to_le_bytes())?;
+        writer.write_all(&to_u32(stored_files.len())?.to_le_bytes())?;
+        for (path, compression, stored, bytes) in &stored_files {
+            writer.write_all(&to_u32(path.len())?.to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&[match compression {
+                ArchiveCompression::None => 0,
+                ArchiveCompression::Zstd(_) => 1,
+            }])?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&(stored.len() as u64).to_le_bytes())?;
+            writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+            writer.write_all(&content_hash(bytes).to_le_bytes())?;
+            offset += stored.len() as u64;
+        }
+        for (_, _, stored, _) in &stored_files {
+            writer.write_all(stored)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the archive to the file at `path`.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), AssetIoError> {
+        self.write(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Reads the index of an archive of `archive_len` bytes, checking that every entry is inside the
+/// archive so that corrupt archives are reported as errors instead of causing huge allocations.
+fn read_index(
+    reader: &mut impl Read,
+    archive_len: u64,
+) -> Result<HashMap<PathBuf, ArchiveEntry>, AssetIoError> {
+    let mut magic = [0; ARCHIVE_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != ARCHIVE_MAGIC {
+        return Err(invalid_data("not an asset archive").into());
+    }
+    let version = read_u32(reader)?;
+    if version != ARCHIVE_VERSION {
+        return Err(invalid_data(format!("unsupported asset archive version {version}")).into());
+    }
+
+    let entry_count = read_u32(reader)?;
+    let mut entries = HashMap::default();
+    for _ in 0..entry_count {
+        let path_len = read_u32(reader)?;
+        let path = read_at_most(reader, path_len.into())?;
+        if path.len() != path_len as usize {
+            return Err(invalid_data("archive index is truncated").into());
+        }
+        let path = String::from_utf8(path)
+            .map_err(|_| invalid_data("archive entry path is not valid UTF-8"))?;
+        let mut compression = [0];
+        reader.read_exact(&mut compression)?;
+        let compression = match compression[0] {
+            0 => ArchiveCompression::None,
+            1 => ArchiveCompression::Zstd(0),
+            other => {
+                return Err(invalid_data(format!("unknown archive compression {other}")).into())
+            }
+        };
+        let entry = ArchiveEntry {
+            compression,
+            offset: read_u64(reader)?,
+            stored_len: read_u64(reader)?,
+            len: read_u64(reader)?,
+            hash: read_u64(reader)?,
+        };
+        let in_bounds = entry
+            .offset
+            .checked_add(entry.stored_len)
+            .map_or(false, |end| end <= archive_len);
+        if !in_bounds {
+            return Err(invalid_data(format!("archive entry {path:?} is out of bounds")).into());
+        }
+        if entry.compression == ArchiveCompression::None && entry.len != entry.stored_len {
+            return Err(
+                invalid_data(format!("archive entry {path:?} has an invalid length")).into(),
+            );
+        }
+        // the decompressed contents must fit in memory
+        to_usize(entry.len)?;
+        entries.insert(PathBuf::from(path), entry);
+    }
+    Ok(entries)
+}
+
+/// Reads up to `len` bytes, without allocating more than what is actually read.
+fn read_at_most(reader: &mut impl Read, len: u64) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn to_u32(value: usize) -> io::Result<u32> {
+    u32::try_from(value).map_err(|_| invalid_data("too large for an asset archive"))
+}
+
+fn to_usize(value: u64) -> io::Result<usize> {
+    usize::try_from(value).map_err(|_| invalid_data("archive entry is too large"))
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Returns the path of a file in an archive, with names separated by `/`.
+fn archive_path(path: &Path) -> String {
+    let mut names = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => names.push(
+                name.to_str()
+                    .unwrap_or_else(|| panic!("{path:?} is not valid UTF-8")),
+            ),
+            Component::CurDir => {}
+            _ => panic!("{path:?} is not a relative path inside the archive"),
+        }
+    }
+    names.join("/")
+}
+
+/// Hashes `bytes` with 64-bit FNV-1a, which gives the same results on every platform.
+fn content_hash(bytes: &[u8]) -> u64 {
+    AssetHash::of(bytes).0
+}
+
+fn compress(compression: ArchiveCompression, bytes: &[u8]) -> io::Result<Option<Vec<u8>>> {
+    #![allow(unused_variables)]
+    match compression {
+        ArchiveCompression::None => Ok(None),
+        #[cfg(feature = "zstd")]
+        ArchiveCompression::Zstd(level) => zstd::stream::encode_all(bytes, level).map(Some),
+        #[cfg(not(feature = "zstd"))]
+        ArchiveCompression::Zstd(_) => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "zstd compression requires the `zstd` feature",
+        )),
+    }
+}
+
+/// Decompresses the `stored` contents of `entry`.
+///
+/// At most one byte more than the length recorded in the index is decompressed, which is enough
+/// for the length check to fail without inflating a corrupt entry without limit.
+fn decompress(entry: &ArchiveEntry, stored: Vec<u8>) -> io::Result<Vec<u8>> {
+    match entry.compression {
+        ArchiveCompression::None => Ok(stored),
+        #[cfg(feature = "zstd")]
+        ArchiveCompression::Zstd(_) => {
+            let mut decoder = zstd::stream::read::Decoder::new(&*stored)?;
+            read_at_most(&mut decoder, entry.len.saturating_add(1))
+        }
+        #[cfg(not(feature = "zstd"))]
+        ArchiveCompression::Zstd(_) => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "zstd decompression requires the `zstd` feature",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{update_asset_storage_system, AssetServer, Assets, LoadContext, LoadedAsset};
+    use bevy_app::{App, Update};
+    use bevy_reflect::{TypePath, TypeUuid};
+    use bevy_tasks::IoTaskPool;
+    use futures_lite::future::block_on;
+
+    fn test_archive() -> Vec<u8> {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("levels/extra")).unwrap();
+        fs::write(dir.path().join("levels/one.txt"), "one").unwrap();
+        fs::write(dir.path().join("levels/extra/two.txt"), "two").unwrap();
+        fs::write(dir.path().join("icon.png"), [1, 2, 3]).unwrap();
+
+        let mut archive = Vec::new();
+        AssetArchiveBuilder::new()
+            .add_directory(dir.path())
+            .unwrap()
+            .add_file("./notes/readme.md", "readme")
+            .write(&mut archive)
+            .unwrap();
+        archive
+    }
+
+    #[test]
+    fn read_archive() {
+        let archive = ArchiveAssetIo::from_bytes(test_archive()).unwrap();
+
+        assert_eq!(archive.iter().count(), 4);
+        assert!(archive.is_file(Path::new("levels/extra/two.txt")));
+        assert!(archive.is_dir(Path::new("levels/extra")));
+        assert!(archive.is_dir(Path::new("")));
+        assert!(archive.get_metadata(Path::new("missing")).is_err());
+        assert_eq!(
+            block_on(archive.load_path(Path::new("notes/readme.md"))).unwrap(),
+            b"readme"
+        );
+        assert_eq!(
+            archive.entry(Path::new("icon.png")).unwrap().hash,
+            content_hash(&[1, 2, 3])
+        );
+
+        let mut children: Vec<_> = archive
+            .read_directory(Path::new("levels"))
+            .unwrap()
+            .collect();
+        children.sort();
+        assert_eq!(
+            children,
+            [
+                PathBuf::from("levels/extra"),
+                PathBuf::from("levels/one.txt")
+            ]
+        );
+        assert_eq!(archive.read_directory(Path::new("")).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn open_archive_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("assets.pak");
+        fs::write(&path, test_archive()).unwrap();
+
+        let archive = ArchiveAssetIo::open(&path).unwrap();
+        assert_eq!(
+            block_on(archive.load_path(Path::new("levels/one.txt"))).unwrap(),
+            b"one"
+        );
+    }
+
+    #[test]
+    fn detect_corruption() {
+        let mut bytes = test_archive();
+        let archive = ArchiveAssetIo::from_bytes(bytes.clone()).unwrap();
+        let offset = archive.entry(Path::new("icon.png")).unwrap().offset as usize;
+        bytes[offset] ^= 0xff;
+        let archive = ArchiveAssetIo::from_bytes(bytes).unwrap();
+        assert!(block_on(archive.load_path(Path::new("icon.png"))).is_err());
+        assert!(block_on(archive.load_path(Path::new("levels/one.txt"))).is_ok());
+
+        assert!(ArchiveAssetIo::from_bytes(&b"not an archive"[..]).is_err());
+    }
+
+    #[test]
+    fn malformed_index() {
+        let mut bytes = Vec::new();
+        AssetArchiveBuilder::new()
+            .add_file("a", "contents")
+            .write(&mut bytes)
+            .unwrap();
+        assert!(ArchiveAssetIo::from_bytes(bytes.clone()).is_ok());
+
+        // the offset of the first entry, followed by its stored length
+        let entry = ARCHIVE_MAGIC.len() + 4 + 4 + 4 + "a".len() + 1;
+        let corrupt = |at: usize, value: &[u8]| {
+            let mut corrupt = bytes.clone();
+            corrupt[at..at + value.len()].copy_from_slice(value);
+            corrupt
+        };
+        let corrupt_archives = [
+            // huge path length
+            corrupt(ARCHIVE_MAGIC.len() + 8, &u32::MAX.to_le_bytes()),
+            // offset past the end
+            corrupt(entry, &(bytes.len() as u64).to_le_bytes()),
+            // stored length overflowing the offset
+            corrupt(entry + 8, &u64::MAX.to_le_bytes()),
+            // stored length past the end
+            corrupt(entry + 8, &(bytes.len() as u64).to_le_bytes()),
+            // truncated index
+            bytes[..entry].to_vec(),
+        ];
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("assets.pak");
+        for corrupt in corrupt_archives {
+            assert!(ArchiveAssetIo::from_bytes(corrupt.clone()).is_err());
+            fs::write(&path, corrupt).unwrap();
+            assert!(ArchiveAssetIo::open(&path).is_err());
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn compressed_entries() {
+        let text = "compressible ".repeat(100);
+        let mut bytes = Vec::new();
+        AssetArchiveBuilder::new()
+            .with_compression(ArchiveCompression::Zstd(3))
+            .add_file("text.txt", text.clone())
+            .add_file("tiny.txt", "a")
+            .write(&mut bytes)
+            .unwrap();
+
+        let archive = ArchiveAssetIo::from_bytes(bytes).unwrap();
+        let entry = archive.entry(Path::new("text.txt")).unwrap();
+        assert_eq!(entry.compression, ArchiveCompression::Zstd(0));
+        assert!(entry.stored_len < entry.len);
+        assert_eq!(
+            archive.entry(Path::new("tiny.txt")).unwrap().compression,
+            ArchiveCompression::None
+        );
+        assert_eq!(
+            block_on(archive.load_path(Path::new("text.txt"))).unwrap(),
+            text.as_bytes()
+        );
+    }
+
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "0f5c4ad2-38f6-4f0c-9b19-6e0e7bd6f1a3"]
+    struct Text(String);
+
+    struct TextLoader;
+    impl crate::AssetLoader for TextLoader {
+        type Settings = ();
+
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _settings: &'a (),
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            let text = String::from_utf8(bytes.to_vec()).unwrap();
+            load_context.set_default_asset(LoadedAsset::new(Text(text)));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[test]
+    fn load_folder_from_archive() {
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(ArchiveAssetIo::from_bytes(test_archive()).unwrap());
+        asset_server.add_loader(TextLoader);
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<Text>());
+        app.insert_resource(asset_server.clone());
+        app.add_systems(Update, update_asset_storage_system::<Text>);
+
+        let handles = asset_server.load_folder("levels").unwrap();
+        assert_eq!(handles.len(), 2);
+        for handle in &handles {
+            let path = asset_server.get_handle_path(handle).unwrap();
+            block_on(asset_server.load_async(path, true)).unwrap();
+        }
+        app.update();
+
+        let assets = app.world.resource::<Assets<Text>>();
+        assert_eq!(
+            assets
+                .get(&asset_server.get_handle("levels/extra/two.txt"))
+                .unwrap()
+                .0,
+            "two"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use crate::{filesystem_watcher::FilesystemWatcher, AssetPath, AssetServer};
+use crate::{ChangeWatcher, MemoryAssetIo};
+use bevy_ecs::system::Resource;
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use bevy_ecs::system::{Local, Res};
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use bevy_utils::{HashMap, Instant};
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use crossbeam_channel::TryRecvError;
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+
+/// The name of the asset source holding the assets embedded with [`embedded_asset!`].
+pub const EMBEDDED: &str = "embedded";
+
+/// The assets embedded in the app binary with [`embedded_asset!`], which are loaded from the
+/// [`EMBEDDED`] asset source.
+///
+/// When the [`AssetPlugin`](crate::AssetPlugin) watches for changes and the `filesystem_watcher`
+/// feature is enabled, the source files of embedded assets are watched, and the assets are
+/// reloaded from them when they change.
+#[derive(Resource)]
+pub struct EmbeddedAssetRegistry {
+    asset_io: MemoryAssetIo,
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    watcher: Option<Mutex<FilesystemWatcher>>,
+}
+
+impl EmbeddedAssetRegistry {
+    /// Creates an empty registry, watching the source files of the embedded assets if
+    /// `watch_for_changes` is set.
+    pub fn new(watch_for_changes: &Option<ChangeWatcher>) -> Self {
+        #![allow(unused_variables)]
+        Self {
+            asset_io: MemoryAssetIo::new(),
+            #[cfg(all(
+                feature = "filesystem_watcher",
+                all(not(target_arch = "wasm32"), not(target_os = "android"))
+            ))]
+            watcher: watch_for_changes
+                .as_ref()
+                .map(|configuration| Mutex::new(FilesystemWatcher::new(configuration))),
+        }
+    }
+
+    /// Gets the asset I/O of the [`EMBEDDED`] asset source.
+    pub fn asset_io(&self) -> &MemoryAssetIo {
+        &self.asset_io
+    }
+
+    /// Embeds an asset at `asset_path` in the [`EMBEDDED`] asset source. `source_path` is the
+    /// file the asset was read from at compile time, which is watched for changes if the registry
+    /// watches for changes.
+    ///
+    /// This is usually called through [`embedded_asset!`].
+    pub fn insert_asset(&self, source_path: PathBuf, asset_path: &Path, bytes: &'static [u8]) {
+        #![allow(unused_variables)]
+        #[cfg(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
+        if let Some(watcher) = &self.watcher {
+            // the source file may not exist when the binary runs on another machine
+            if source_path.exists() {
+                if let Err(err) = watcher.lock().watch(&source_path, asset_path.to_owned()) {
+                    bevy_log::warn!("failed to watch embedded asset {:?}: {}", source_path, err);
+                }
+            }
+        }
+        self.asset_io.insert(asset_path, bytes);
+    }
+}
+
+/// Returns the path of an asset embedded from `source_file_path` by [`embedded_asset!`].
+///
+/// The path is made of the crate name, the directory of the source file relative to the `src`
+/// folder of the crate, and `asset_path`. This is an implementation detail of the macro.
+#[doc(hidden)]
+pub fn _embedded_asset_path(
+    crate_name: &str,
+    source_file_path: &Path,
+    asset_path: &Path,
+) -> PathBuf {
+    let source_dir = source_file_path
+        .parent()
+        .expect("the source file should be in a directory");
+    let after_src = source_dir
+        .ancestors()
+        .find(|ancestor| ancestor.ends_with("src"))
+        .and_then(|src| source_dir.strip_prefix(src).ok())
+        .unwrap_or_else(|| panic!("{source_file_path:?} is not in a `src` folder"));
+    Path::new(crate_name).join(after_src).join(asset_path)
+}
+
+/// Embeds the bytes of an asset in the app binary, and registers them in the
+/// [`EmbeddedAssetRegistry`] of the app.
+///
+/// The asset path is relative to the file calling the macro, and the embedded asset is loaded with
+/// `embedded://<crate name>/<directory of the calling file inside src>/<asset path>`.
+///
+/// ```ignore
+/// // in `my_crate/src/render/mod.rs`
+/// embedded_asset!(app, "shaders/outline.wgsl");
+/// // in a system
+/// let shader: Handle<Shader> = asset_server.load("embedded://my_crate/render/shaders/outline.wgsl");
+/// ```
+///
+/// The [`AssetPlugin`](crate::AssetPlugin) must have been added to the app. When it watches for
+/// changes, the asset is reloaded when the file it was embedded from changes.
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path: expr) => {{
+        let crate_name = module_path!().split(':').next().unwrap();
+        let asset_path = $crate::_embedded_asset_path(
+            crate_name,
+            ::std::path::Path::new(file!()),
+            ::std::path::Path::new($path),
+        );
+        let source_path = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join(asset_path.strip_prefix(crate_name).unwrap());
+        $app.world
+            .resource::<$crate::EmbeddedAssetRegistry>()
+            .insert_asset(source_path, &asset_path, include_bytes!($path));
+    }};
+}
+
+/// Reloads the embedded assets whose source files changed.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+pub fn embedded_watcher_system(
+    asset_server: Res<AssetServer>,
+    registry: Res<EmbeddedAssetRegistry>,
+    mut changed: Local<HashMap<PathBuf, (PathBuf, Instant)>>,
+) {
+    let Some(watcher) = &registry.watcher else {
+        return;
+    };
+    let watcher = watcher.lock();
+    loop {
+        let event = match watcher.receiver.try_recv() {
+            Ok(result) => result.unwrap(),
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+        };
+
+        if let notify::event::Event {
+            kind: notify::event::EventKind::Modify(_),
+            paths,
+            ..
+        } = event
+        {
+            for path in &paths {
+                let Some(set) = watcher.path_map.get(path) else {
+                    continue;
+                };
+                for to_reload in set {
+                    changed.insert(to_reload.to_owned(), (path.to_owned(), Instant::now()));
+                }
+            }
+        }
+    }
+
+    // wait for the file to be fully written before reading it, see `filesystem_watcher_system`
+    for (to_reload, (source_path, _)) in
+        changed.extract_if(|_, (_, last_modified)| last_modified.elapsed() >= watcher.delay)
+    {
+        match std::fs::read(&source_path) {
+            Ok(bytes) => {
+                registry.asset_io.insert(to_reload.clone(), bytes);
+                asset_server
+                    .reload_asset(AssetPath::new(to_reload, None).with_source(Some(EMBEDDED)));
+            }
+            Err(err) => {
+                bevy_log::warn!("failed to read embedded asset {:?}: {}", source_path, err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AssetIo;
+    use bevy_app::App;
+
+    #[test]
+    fn embedded_asset_path() {
+        assert_eq!(
+            _embedded_asset_path(
+                "my_crate",
+                Path::new("crates/my_crate/src/render/mod.rs"),
+                Path::new("shaders/outline.wgsl")
+            ),
+            Path::new("my_crate/render/shaders/outline.wgsl")
+        );
+        assert_eq!(
+            _embedded_asset_path(
+                "my_crate",
+                Path::new("/home/me/my_crate/src/lib.rs"),
+                Path::new("icon.png")
+            ),
+            Path::new("my_crate/icon.png")
+        );
+    }
+
+    #[test]
+    fn embed_asset() {
+        let mut app = App::new();
+        app.insert_resource(EmbeddedAssetRegistry::new(&None));
+        embedded_asset!(app, "memory_asset_io.rs");
+
+        let registry = app.world.resource::<EmbeddedAssetRegistry>();
+        let path = Path::new("bevy_asset/io/memory_asset_io.rs");
+        assert!(registry.asset_io().is_file(path));
+        assert_eq!(
+            futures_lite::future::block_on(registry.asset_io().load_path(path)).unwrap(),
+            include_bytes!("memory_asset_io.rs")
+        );
+    }
+}
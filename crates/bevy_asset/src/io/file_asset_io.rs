@@ -1,5 +1,5 @@
 #[cfg(feature = "filesystem_watcher")]
-use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
+use crate::{filesystem_watcher::FilesystemWatcher, AssetPath, AssetServer};
 use crate::{AssetIo, AssetIoError, ChangeWatcher, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
@@ -169,24 +169,31 @@ impl AssetIo for FileAssetIo {
     }
 }
 
-/// Watches for file changes in the local file system.
+/// Watches for file changes in the local file system, in the default asset source and in every
+/// named asset source backed by a [`FileAssetIo`].
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 pub fn filesystem_watcher_system(
     asset_server: Res<AssetServer>,
-    mut changed: Local<HashMap<PathBuf, Instant>>,
+    mut changed: Local<HashMap<AssetPath<'static>, Instant>>,
 ) {
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
+    let named_sources = asset_server.server.named_sources.read();
+    let sources = std::iter::once((None, &asset_server.server.asset_io)).chain(
+        named_sources
+            .iter()
+            .map(|(name, asset_io)| (Some(name.as_str()), asset_io)),
+    );
+    for (source, asset_io) in sources {
+        let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() else {
+            continue;
+        };
+        let watcher = asset_io.filesystem_watcher.read();
+        let Some(ref watcher) = *watcher else {
+            continue;
         };
-    let watcher = asset_io.filesystem_watcher.read();
 
-    if let Some(ref watcher) = *watcher {
         loop {
             let event = match watcher.receiver.try_recv() {
                 Ok(result) => result.unwrap(),
@@ -201,10 +208,14 @@ pub fn filesystem_watcher_system(
             } = event
             {
                 for path in &paths {
-                    let Some(set) = watcher.path_map.get(path) else {continue};
+                    let Some(set) = watcher.path_map.get(path) else {
+                        continue;
+                    };
                     for to_reload in set {
                         // When an asset is modified, note down the timestamp (overriding any previous modification events)
-                        changed.insert(to_reload.to_owned(), Instant::now());
+                        let to_reload = AssetPath::new(to_reload.to_owned(), None)
+                            .with_source(source.map(String::from));
+                        changed.insert(to_reload, Instant::now());
                     }
                 }
             }
@@ -215,10 +226,10 @@ pub fn filesystem_watcher_system(
         // When changing and then saving a shader, several modification events are sent in short succession.
         // Unless we wait until we are sure the shader is finished being modified (and that there will be no more events coming),
         // we will sometimes get a crash when trying to reload a partially-modified shader.
-        for (to_reload, _) in
-            changed.extract_if(|_, last_modified| last_modified.elapsed() >= watcher.delay)
-        {
-            let _ = asset_server.load_untracked(to_reload.as_path().into(), true);
+        for (to_reload, _) in changed.extract_if(|to_reload, last_modified| {
+            to_reload.source() == source && last_modified.elapsed() >= watcher.delay
+        }) {
+            let _ = asset_server.load_untracked(to_reload, true);
         }
     }
 }
@@ -0,0 +1,139 @@
+use crate::{AssetIo, AssetIoError, ChangeWatcher, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// I/O implementation for assets held in memory.
+///
+/// Files are added with [`insert`](MemoryAssetIo::insert), and directories exist implicitly for
+/// the parents of every file. Cloning a `MemoryAssetIo` returns a handle to the same files, so
+/// files can still be added after it was given to an [`AssetServer`](crate::AssetServer).
+///
+/// Changing a file does not reload the assets loaded from it, use
+/// [`AssetServer::reload_asset`](crate::AssetServer::reload_asset) for that.
+#[derive(Clone, Default)]
+pub struct MemoryAssetIo {
+    files: Arc<RwLock<HashMap<PathBuf, Cow<'static, [u8]>>>>,
+}
+
+impl MemoryAssetIo {
+    /// Creates an empty `MemoryAssetIo`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the file at `path` with the given contents, replacing any file at the same path.
+    pub fn insert(&self, path: impl Into<PathBuf>, bytes: impl Into<Cow<'static, [u8]>>) {
+        self.files.write().insert(path.into(), bytes.into());
+    }
+
+    /// Removes the file at `path`, returning its contents if it existed.
+    pub fn remove(&self, path: &Path) -> Option<Cow<'static, [u8]>> {
+        self.files.write().remove(path)
+    }
+
+    /// Returns `true` if there is a file at `path`.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.read().contains_key(path)
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.files
+                .read()
+                .get(path)
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        if !self.is_dir(path) {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        let children: HashSet<PathBuf> = self
+            .files
+            .read()
+            .keys()
+            .filter_map(|file| file.strip_prefix(path).ok()?.components().next())
+            .map(|child| path.join(child))
+            .collect();
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let files = self.files.read();
+        if files.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if path.as_os_str().is_empty() || files.keys().any(|file| file.starts_with(path)) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(
+        &self,
+        _to_watch: &Path,
+        _to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self, _configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_and_directories() {
+        let asset_io = MemoryAssetIo::new();
+        asset_io.insert("textures/grass.png", &b"grass"[..]);
+        asset_io.insert("textures/ui/button.png", vec![1, 2]);
+        asset_io.insert("music.ogg", &b"music"[..]);
+
+        assert!(asset_io.is_file(Path::new("music.ogg")));
+        assert!(asset_io.is_dir(Path::new("textures")));
+        assert!(asset_io.is_dir(Path::new("")));
+        assert!(asset_io.get_metadata(Path::new("missing")).is_err());
+        assert_eq!(
+            futures_lite::future::block_on(asset_io.load_path(Path::new("textures/grass.png")))
+                .unwrap(),
+            b"grass"
+        );
+
+        let mut children: Vec<_> = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect();
+        children.sort();
+        assert_eq!(
+            children,
+            [
+                PathBuf::from("textures/grass.png"),
+                PathBuf::from("textures/ui")
+            ]
+        );
+        assert_eq!(asset_io.read_directory(Path::new("")).unwrap().count(), 2);
+
+        asset_io.remove(Path::new("music.ogg"));
+        assert!(!asset_io.contains(Path::new("music.ogg")));
+        assert!(
+            futures_lite::future::block_on(asset_io.load_path(Path::new("music.ogg"))).is_err()
+        );
+    }
+}
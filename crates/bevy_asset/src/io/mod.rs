@@ -5,7 +5,11 @@ mod file_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod archive_asset_io;
+mod embedded;
+mod memory_asset_io;
 mod metadata;
+mod source;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
@@ -14,7 +18,11 @@ pub use file_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use archive_asset_io::*;
+pub use embedded::*;
+pub use memory_asset_io::*;
 pub use metadata::*;
+pub use source::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
@@ -0,0 +1,91 @@
+use crate::{AssetIo, ChangeWatcher};
+use bevy_ecs::system::Resource;
+use bevy_log::warn;
+use bevy_utils::HashMap;
+use std::path::PathBuf;
+
+#[cfg(target_os = "android")]
+use crate::AndroidAssetIo;
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+use crate::FileAssetIo;
+#[cfg(target_arch = "wasm32")]
+use crate::WasmAssetIo;
+
+type BuildAssetIo = dyn Fn(&Option<ChangeWatcher>) -> Box<dyn AssetIo> + Send + Sync;
+
+/// Creates the [`AssetIo`] of an asset source.
+///
+/// Asset sources other than the default one are referred to by name in asset paths, such as
+/// `mods://levels/one.scn.ron`. They are registered with
+/// [`AddAsset::register_asset_source`](crate::AddAsset::register_asset_source).
+pub struct AssetSourceBuilder {
+    build: Box<BuildAssetIo>,
+}
+
+impl AssetSourceBuilder {
+    /// Creates a builder for an asset source using the asset I/O returned by `asset_io`.
+    ///
+    /// If the [`AssetPlugin`](crate::AssetPlugin) watches for changes,
+    /// [`AssetIo::watch_for_changes`] is called on the built asset I/O.
+    pub fn new<T: AssetIo>(asset_io: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        Self {
+            build: Box::new(move |watch_for_changes| {
+                let asset_io = asset_io();
+                if let Some(configuration) = watch_for_changes {
+                    if let Err(err) = asset_io.watch_for_changes(configuration) {
+                        warn!("failed to watch asset source for changes: {}", err);
+                    }
+                }
+                Box::new(asset_io)
+            }),
+        }
+    }
+
+    /// Creates a builder for an asset source reading from the folder at `path` with the
+    /// platform's default [`AssetIo`].
+    ///
+    /// This is a [`FileAssetIo`](crate::FileAssetIo) on desktop platforms, a
+    /// [`WasmAssetIo`](crate::WasmAssetIo) on the web and an
+    /// [`AndroidAssetIo`](crate::AndroidAssetIo) on Android.
+    pub fn platform_default(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            build: Box::new(move |_watch_for_changes| {
+                #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+                let source = FileAssetIo::new(&path, _watch_for_changes);
+                #[cfg(target_arch = "wasm32")]
+                let source = WasmAssetIo::new(&path);
+                #[cfg(target_os = "android")]
+                let source = AndroidAssetIo::new(&path);
+
+                Box::new(source)
+            }),
+        }
+    }
+
+    /// Builds the asset I/O of the asset source, optionally watching it for changes.
+    pub fn build(&self, watch_for_changes: &Option<ChangeWatcher>) -> Box<dyn AssetIo> {
+        (self.build)(watch_for_changes)
+    }
+}
+
+/// The named asset sources registered on an app, which are built by the
+/// [`AssetPlugin`](crate::AssetPlugin).
+#[derive(Resource, Default)]
+pub struct AssetSourceBuilders {
+    sources: HashMap<String, AssetSourceBuilder>,
+}
+
+impl AssetSourceBuilders {
+    /// Registers the asset source with the given name, replacing any source with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, source: AssetSourceBuilder) {
+        self.sources.insert(name.into(), source);
+    }
+
+    /// Iterates over the registered asset sources and their names.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &AssetSourceBuilder)> {
+        self.sources
+            .iter()
+            .map(|(name, source)| (name.as_str(), source))
+    }
+}
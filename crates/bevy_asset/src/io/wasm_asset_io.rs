@@ -42,6 +42,9 @@ impl AssetIo for WasmAssetIo {
                 .await
                 .unwrap();
             let resp: Response = resp_value.dyn_into().unwrap();
+            if resp.status() == 404 {
+                return Err(AssetIoError::NotFound(path));
+            }
             let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
             let bytes = Uint8Array::new(&data).to_vec();
             Ok(bytes)
@@ -25,7 +25,9 @@ mod handle;
 mod info;
 mod io;
 mod loader;
+mod meta;
 mod path;
+mod processor;
 mod reflect;
 
 /// The `bevy_asset` prelude.
@@ -44,11 +46,14 @@ pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
+pub use meta::*;
 pub use path::*;
+pub use processor::*;
 pub use reflect::*;
 
 use bevy_app::{prelude::*, MainScheduleOrder};
 use bevy_ecs::schedule::ScheduleLabel;
+use bevy_log::{error, info};
 use bevy_utils::Duration;
 
 /// Asset storages are updated.
@@ -82,10 +87,33 @@ impl ChangeWatcher {
     }
 }
 
+/// Where the [`AssetPlugin`] loads assets from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AssetMode {
+    /// Assets are loaded from [`AssetPlugin::asset_folder`] and run through their loaders as-is.
+    #[default]
+    Unprocessed,
+    /// Assets are loaded exclusively from [`AssetPlugin::imported_asset_folder`], which must have
+    /// been filled by an [`AssetProcessor`] ahead of time. This is meant for shipping builds.
+    Processed,
+    /// An [`AssetProcessor`] brings [`AssetPlugin::imported_asset_folder`] up to date with
+    /// [`AssetPlugin::asset_folder`] when the app starts, then assets are loaded from the imported
+    /// asset folder.
+    ///
+    /// Processes are registered with [`AddAsset::add_asset_process`]. This mode is only supported
+    /// on platforms with a filesystem, and behaves like [`AssetMode::Processed`] elsewhere.
+    ProcessedDev,
+}
+
 /// Adds support for [`Assets`] to an App.
 ///
 /// Assets are typed collections with change tracking, which are added as App Resources. Examples of
 /// assets: textures, sounds, 3d models, maps, scenes
+///
+/// Assets are loaded from the asset folder, or from the named asset sources registered with
+/// [`AddAsset::register_asset_source`] before this plugin is added.
+/// Assets embedded in the app binary with [`embedded_asset!`] are loaded from the [`EMBEDDED`]
+/// asset source.
 #[derive(Debug, Clone)]
 pub struct AssetPlugin {
     /// The base folder where assets are loaded from, relative to the executable.
@@ -93,6 +121,11 @@ pub struct AssetPlugin {
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
     /// and cannot be supported on the wasm32 arch nor android os.
     pub watch_for_changes: Option<ChangeWatcher>,
+    /// The folder where processed assets are written to and loaded from, relative to the
+    /// executable.
+    pub imported_asset_folder: String,
+    /// Whether assets are loaded from their sources or from their processed versions.
+    pub mode: AssetMode,
 }
 
 impl Default for AssetPlugin {
@@ -100,6 +133,8 @@ impl Default for AssetPlugin {
         Self {
             asset_folder: "assets".to_string(),
             watch_for_changes: None,
+            imported_asset_folder: "imported_assets".to_string(),
+            mode: AssetMode::Unprocessed,
         }
     }
 }
@@ -109,15 +144,15 @@ impl AssetPlugin {
     ///
     /// This is useful when providing a custom `AssetIo` instance that needs to
     /// delegate to the default `AssetIo` for the platform.
+    ///
+    /// The asset I/O reads from the asset folder or from the imported asset folder, depending
+    /// on the [`AssetMode`].
     pub fn create_platform_default_asset_io(&self) -> Box<dyn AssetIo> {
-        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-        let source = FileAssetIo::new(&self.asset_folder, &self.watch_for_changes);
-        #[cfg(target_arch = "wasm32")]
-        let source = WasmAssetIo::new(&self.asset_folder);
-        #[cfg(target_os = "android")]
-        let source = AndroidAssetIo::new(&self.asset_folder);
-
-        Box::new(source)
+        let folder = match self.mode {
+            AssetMode::Unprocessed => &self.asset_folder,
+            AssetMode::Processed | AssetMode::ProcessedDev => &self.imported_asset_folder,
+        };
+        AssetSourceBuilder::platform_default(folder).build(&self.watch_for_changes)
     }
 }
 
@@ -129,6 +164,29 @@ impl Plugin for AssetPlugin {
             app.insert_resource(asset_server);
         }
 
+        let embedded = EmbeddedAssetRegistry::new(&self.watch_for_changes);
+        app.world
+            .resource::<AssetServer>()
+            .add_source(EMBEDDED, Box::new(embedded.asset_io().clone()));
+        app.insert_resource(embedded);
+
+        if let Some(sources) = app.world.get_resource::<AssetSourceBuilders>() {
+            let asset_server = app.world.resource::<AssetServer>();
+            for (name, source) in sources.iter() {
+                asset_server.add_source(name, source.build(&self.watch_for_changes));
+            }
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        if self.mode == AssetMode::ProcessedDev && !app.world.contains_resource::<AssetProcessor>()
+        {
+            let processor = AssetProcessor::new(
+                FileAssetIo::new(&self.asset_folder, &None),
+                FileAssetIo::get_base_path().join(&self.imported_asset_folder),
+            );
+            app.insert_resource(processor);
+        }
+
         app.register_type::<HandleId>();
         app.register_type::<AssetPath>();
 
@@ -140,10 +198,38 @@ impl Plugin for AssetPlugin {
             feature = "filesystem_watcher",
             all(not(target_arch = "wasm32"), not(target_os = "android"))
         ))]
-        app.add_systems(LoadAssets, io::filesystem_watcher_system);
+        app.add_systems(
+            LoadAssets,
+            (io::filesystem_watcher_system, io::embedded_watcher_system),
+        );
 
         let mut order = app.world.resource_mut::<MainScheduleOrder>();
         order.insert_after(First, LoadAssets);
         order.insert_after(PostUpdate, AssetEvents);
     }
+
+    fn finish(&self, app: &mut App) {
+        // in the other modes, the imported asset folder is only read from
+        if self.mode != AssetMode::ProcessedDev {
+            return;
+        }
+        let Some(processor) = app.world.get_resource::<AssetProcessor>() else {
+            return;
+        };
+        match processor.process_all() {
+            Ok(summary) => {
+                for (path, err) in &summary.failed {
+                    error!("failed to process asset {:?}: {}", path, err);
+                }
+                info!(
+                    "processed {} assets ({} up to date, {} failed, {} removed)",
+                    summary.processed.len(),
+                    summary.up_to_date.len(),
+                    summary.failed.len(),
+                    summary.removed.len()
+                );
+            }
+            Err(err) => error!("failed to process assets: {}", err),
+        }
+    }
 }
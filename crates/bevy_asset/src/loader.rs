@@ -1,6 +1,6 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    HandleUntyped, RefChangeChannel,
+    meta::ron_options, path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetMetaFile,
+    AssetServer, Assets, Handle, HandleId, HandleUntyped, RefChangeChannel, Settings,
 };
 use anyhow::Error;
 use anyhow::Result;
@@ -10,17 +10,27 @@ use bevy_reflect::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use parking_lot::Mutex;
+use ron::error::SpannedError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// A loader for an asset source.
 ///
 /// Types implementing this trait are used by the [`AssetServer`] to load assets
 /// into their respective asset storages.
 pub trait AssetLoader: Send + Sync + 'static {
+    /// The settings of this loader.
+    ///
+    /// Settings are read from the `.meta` file next to the asset, see [`AssetMetaFile`], and can
+    /// be overridden with [`AssetServer::load_with_settings`]. Loaders without settings use `()`.
+    type Settings: Settings + Default + Serialize + for<'de> Deserialize<'de>;
+
     /// Processes the asset in an asynchronous closure.
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
+        settings: &'a Self::Settings,
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), Error>>;
 
@@ -28,6 +38,86 @@ pub trait AssetLoader: Send + Sync + 'static {
     fn extensions(&self) -> &[&str];
 }
 
+/// A type-erased version of [`AssetLoader`], implemented for every asset loader.
+pub trait ErasedAssetLoader: Send + Sync + 'static {
+    /// Processes the asset in an asynchronous closure.
+    ///
+    /// `settings` must be of the loader's [`Settings`](AssetLoader::Settings) type.
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        settings: &'a dyn Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>>;
+
+    /// Returns a list of extensions supported by this asset loader, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Reads the settings of this loader from the contents of a `.meta` file.
+    fn deserialize_settings(&self, meta: &[u8]) -> Result<Box<dyn Settings>, SpannedError>;
+
+    /// Returns the default settings of this loader.
+    fn default_settings(&self) -> Box<dyn Settings>;
+
+    /// Returns the contents of a `.meta` file naming this loader, with its default settings.
+    fn default_meta(&self) -> String;
+
+    /// Returns the type name of this loader, which names it in `.meta` files.
+    fn type_name(&self) -> &'static str;
+}
+
+impl<L: AssetLoader> ErasedAssetLoader for L {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        settings: &'a dyn Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        match settings.downcast_ref::<L::Settings>() {
+            Some(settings) => <L as AssetLoader>::load(self, bytes, settings, load_context),
+            None => Box::pin(async {
+                Err(anyhow::anyhow!(
+                    "{} expects settings of type {}",
+                    std::any::type_name::<L>(),
+                    std::any::type_name::<L::Settings>()
+                ))
+            }),
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        <L as AssetLoader>::extensions(self)
+    }
+
+    fn deserialize_settings(&self, meta: &[u8]) -> Result<Box<dyn Settings>, SpannedError> {
+        let meta: AssetMetaFile<L::Settings> = ron_options().from_bytes(meta)?;
+        Ok(Box::new(meta.settings))
+    }
+
+    fn default_settings(&self) -> Box<dyn Settings> {
+        Box::<L::Settings>::default()
+    }
+
+    fn default_meta(&self) -> String {
+        default_meta::<L>()
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<L>()
+    }
+}
+
+/// Returns the contents of a `.meta` file naming the loader `L`, with its default settings.
+pub(crate) fn default_meta<L: AssetLoader>() -> String {
+    let meta = AssetMetaFile {
+        loader: Some(std::any::type_name::<L>().to_string()),
+        settings: L::Settings::default(),
+    };
+    ron_options()
+        .to_string_pretty(&meta, Default::default())
+        .expect("Loader settings should be serializable.")
+}
+
 /// An essential piece of data of an application.
 ///
 /// Assets are the building blocks of games. They can be anything, from images and sounds to scenes
@@ -126,12 +216,15 @@ pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
+    pub(crate) source: Option<&'a str>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) read_paths: Mutex<Vec<PathBuf>>,
 }
 
 impl<'a> LoadContext<'a> {
     pub(crate) fn new(
+        source: Option<&'a str>,
         path: &'a Path,
         ref_change_channel: &'a RefChangeChannel,
         asset_io: &'a dyn AssetIo,
@@ -142,7 +235,9 @@ impl<'a> LoadContext<'a> {
             asset_io,
             labeled_assets: Default::default(),
             version,
+            source,
             path,
+            read_paths: Default::default(),
         }
     }
 
@@ -151,6 +246,22 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Gets the name of the asset source the asset is loaded from, or `None` for the default
+    /// asset source.
+    pub fn source(&self) -> Option<&str> {
+        self.source
+    }
+
+    /// Gets the asset path of the asset source for this load context.
+    pub fn asset_path(&self) -> AssetPath<'_> {
+        AssetPath::new_ref(self.path, None).with_source(self.source)
+    }
+
+    /// Gets the asset path of the sub-asset with the given label in this load context.
+    pub fn labeled_asset_path<'b>(&'b self, label: &'b str) -> AssetPath<'b> {
+        AssetPath::new_ref(self.path, Some(label)).with_source(self.source)
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
@@ -166,7 +277,7 @@ impl<'a> LoadContext<'a> {
         assert!(!label.is_empty());
         self.labeled_assets
             .insert(Some(label.to_string()), asset.into());
-        self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
+        self.get_handle(self.labeled_asset_path(label))
     }
 
     /// Gets a strong handle to an asset of type `T` from its id.
@@ -184,6 +295,7 @@ impl<'a> LoadContext<'a> {
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
         self.asset_io
             .watch_path_for_changes(path.as_ref(), Some(self.path.to_owned()))?;
+        self.read_paths.lock().push(path.as_ref().to_owned());
         self.asset_io.load_path(path.as_ref()).await
     }
 
@@ -0,0 +1,141 @@
+use downcast_rs::{impl_downcast, Downcast};
+use ron::extensions::Extensions;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The extension of the meta files placed next to asset sources.
+///
+/// The meta file of `textures/grass.png` is `textures/grass.png.meta`.
+pub const META_FILE_EXTENSION: &str = "meta";
+
+/// Settings passed to an [`AssetLoader`](crate::AssetLoader) when loading an asset.
+///
+/// This is implemented for every type that can be used as
+/// [`AssetLoader::Settings`](crate::AssetLoader::Settings).
+pub trait Settings: Downcast + Send + Sync + 'static {}
+impl_downcast!(Settings);
+
+impl<T: Send + Sync + 'static> Settings for T {}
+
+/// The contents of a `.meta` file, written in [RON](https://github.com/ron-rs/ron).
+///
+/// A meta file picks the [`AssetLoader`](crate::AssetLoader) used for the asset next to it and
+/// the settings passed to that loader. Both fields are optional: without a `loader`, the loader
+/// is picked from the extension of the asset, and missing settings take their default values.
+///
+/// ```ron
+/// (
+///     loader: "ImageTextureLoader",
+///     settings: (
+///         is_srgb: false,
+///         sampler: Nearest,
+///     ),
+/// )
+/// ```
+///
+/// The loader is named by its full type name or by its type name without module paths.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "S: Deserialize<'de> + Default"))]
+pub struct AssetMetaFile<S> {
+    /// The name of the loader used for the asset.
+    #[serde(default)]
+    pub loader: Option<String>,
+    /// The settings passed to the loader.
+    #[serde(default)]
+    pub settings: S,
+}
+
+/// The part of a meta file that can be read without knowing the settings type of its loader.
+#[derive(Deserialize)]
+pub(crate) struct LoaderName {
+    #[serde(default)]
+    pub(crate) loader: Option<String>,
+}
+
+impl LoaderName {
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, ron::error::SpannedError> {
+        ron_options().from_bytes(bytes)
+    }
+}
+
+/// The RON options used for meta files, which allow writing optional values without `Some`.
+pub(crate) fn ron_options() -> ron::Options {
+    ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+}
+
+/// Returns the path of the meta file of the asset at `path`.
+pub fn meta_path(path: &Path) -> PathBuf {
+    let mut meta_path = path.as_os_str().to_owned();
+    meta_path.push(".");
+    meta_path.push(META_FILE_EXTENSION);
+    meta_path.into()
+}
+
+/// Returns `true` if `path` is a meta file.
+pub(crate) fn is_meta_path(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some(META_FILE_EXTENSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    struct TestSettings {
+        value: u32,
+        #[serde(default)]
+        flag: bool,
+    }
+
+    #[test]
+    fn meta_file_fields_are_optional() {
+        let meta = br#"(loader: "TestLoader", settings: (value: 3))"#;
+        assert_eq!(
+            LoaderName::from_bytes(meta).unwrap().loader.as_deref(),
+            Some("TestLoader")
+        );
+        let meta: AssetMetaFile<TestSettings> = ron_options().from_bytes(meta).unwrap();
+        assert_eq!(
+            meta.settings,
+            TestSettings {
+                value: 3,
+                flag: false
+            }
+        );
+
+        let meta = b"()";
+        assert!(LoaderName::from_bytes(meta).unwrap().loader.is_none());
+        let meta: AssetMetaFile<TestSettings> = ron_options().from_bytes(meta).unwrap();
+        assert_eq!(meta.settings, TestSettings::default());
+    }
+
+    #[test]
+    fn meta_file_round_trip() {
+        let meta = AssetMetaFile {
+            loader: Some("TestLoader".to_string()),
+            settings: TestSettings {
+                value: 7,
+                flag: true,
+            },
+        };
+        let serialized = ron_options()
+            .to_string_pretty(&meta, Default::default())
+            .unwrap();
+        assert!(serialized.contains("loader: \"TestLoader\""));
+        assert_eq!(
+            ron_options()
+                .from_str::<AssetMetaFile<TestSettings>>(&serialized)
+                .unwrap(),
+            meta
+        );
+    }
+
+    #[test]
+    fn meta_path_appends_extension() {
+        assert_eq!(
+            meta_path(Path::new("textures/grass.png")),
+            Path::new("textures/grass.png.meta")
+        );
+        assert!(is_meta_path(&meta_path(Path::new("grass.png"))));
+    }
+}
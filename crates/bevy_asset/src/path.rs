@@ -7,10 +7,17 @@ use std::{
     path::{Path, PathBuf},
 };
 
-/// Represents a path to an asset in the file system.
+/// Represents a path to an asset in an asset source.
+///
+/// Asset paths are written as `source://path/to/asset.ext#label`, where both the source and the
+/// label are optional. Paths without a source refer to the default asset source, which is usually
+/// the asset folder. Other sources are registered with
+/// [`AddAsset::register_asset_source`](crate::AddAsset::register_asset_source).
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, Reflect)]
 #[reflect(Debug, PartialEq, Hash, Serialize, Deserialize)]
 pub struct AssetPath<'a> {
+    #[serde(default)]
+    source: Option<Cow<'a, str>>,
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
 }
@@ -20,6 +27,7 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new_ref(path: &'a Path, label: Option<&'a str>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
             path: Cow::Borrowed(path),
             label: label.map(Cow::Borrowed),
         }
@@ -29,11 +37,27 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new(path: PathBuf, label: Option<String>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: label.map(Cow::Owned),
         }
     }
 
+    /// Returns this asset path in the asset source with the given name, or in the default asset
+    /// source if `source` is `None`.
+    #[inline]
+    #[must_use]
+    pub fn with_source(mut self, source: Option<impl Into<Cow<'a, str>>>) -> AssetPath<'a> {
+        self.source = source.map(Into::into);
+        self
+    }
+
+    /// Gets the name of the asset source, or `None` for the default asset source.
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
     /// Constructs an identifier from this asset path.
     #[inline]
     pub fn get_id(&self) -> AssetPathId {
@@ -56,6 +80,10 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
         AssetPath {
+            source: self
+                .source
+                .as_ref()
+                .map(|value| Cow::Owned(value.to_string())),
             path: Cow::Owned(self.path.to_path_buf()),
             label: self
                 .label
@@ -86,14 +114,26 @@ pub struct SourcePathId(u64);
 #[reflect_value(PartialEq, Hash, Serialize, Deserialize)]
 pub struct LabelId(u64);
 
-impl<'a> From<&'a Path> for SourcePathId {
-    fn from(value: &'a Path) -> Self {
+impl SourcePathId {
+    /// Creates the id of the asset source path `path` in the asset source named `source`, or in
+    /// the default asset source if `source` is `None`.
+    pub fn new(source: Option<&str>, path: &Path) -> Self {
         let mut hasher = get_hasher();
-        value.hash(&mut hasher);
+        path.hash(&mut hasher);
+        // paths in the default source keep the ids they had before named sources existed
+        if let Some(source) = source {
+            source.hash(&mut hasher);
+        }
         SourcePathId(hasher.finish())
     }
 }
 
+impl<'a> From<&'a Path> for SourcePathId {
+    fn from(value: &'a Path) -> Self {
+        SourcePathId::new(None, value)
+    }
+}
+
 impl From<AssetPathId> for SourcePathId {
     fn from(id: AssetPathId) -> Self {
         id.source_path_id()
@@ -138,7 +178,7 @@ where
     fn from(value: T) -> Self {
         let asset_path: AssetPath = value.into();
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.source(), asset_path.path()),
             LabelId::from(asset_path.label()),
         )
     }
@@ -147,19 +187,29 @@ where
 impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     fn from(asset_path: &'a AssetPath<'b>) -> Self {
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.source(), asset_path.path()),
             LabelId::from(asset_path.label()),
         )
     }
 }
 
+/// Splits an asset path string into its source, path and label.
+fn parse_asset_path(asset_path: &str) -> (Option<&str>, &str, Option<&str>) {
+    let mut parts = asset_path.splitn(2, '#');
+    let path = parts.next().expect("Path must be set.");
+    let label = parts.next();
+    match path.split_once("://") {
+        Some((source, path)) if !source.is_empty() => (Some(source), path, label),
+        _ => (None, path, label),
+    }
+}
+
 impl<'a> From<&'a str> for AssetPath<'a> {
     fn from(asset_path: &'a str) -> Self {
-        let mut parts = asset_path.splitn(2, '#');
-        let path = Path::new(parts.next().expect("Path must be set."));
-        let label = parts.next();
+        let (source, path, label) = parse_asset_path(asset_path);
         AssetPath {
-            path: Cow::Borrowed(path),
+            source: source.map(Cow::Borrowed),
+            path: Cow::Borrowed(Path::new(path)),
             label: label.map(Cow::Borrowed),
         }
     }
@@ -174,6 +224,7 @@ impl<'a> From<&'a String> for AssetPath<'a> {
 impl<'a> From<&'a Path> for AssetPath<'a> {
     fn from(path: &'a Path) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Borrowed(path),
             label: None,
         }
@@ -183,6 +234,7 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
 impl<'a> From<PathBuf> for AssetPath<'a> {
     fn from(path: PathBuf) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: None,
         }
@@ -191,12 +243,44 @@ impl<'a> From<PathBuf> for AssetPath<'a> {
 
 impl<'a> From<String> for AssetPath<'a> {
     fn from(asset_path: String) -> Self {
-        let mut parts = asset_path.splitn(2, '#');
-        let path = PathBuf::from(parts.next().expect("Path must be set."));
-        let label = parts.next().map(String::from);
-        AssetPath {
-            path: Cow::Owned(path),
-            label: label.map(Cow::Owned),
-        }
+        AssetPath::from(asset_path.as_str()).to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_source_and_label() {
+        let path = AssetPath::from("mods://levels/one.scn.ron#Scene");
+        assert_eq!(path.source(), Some("mods"));
+        assert_eq!(path.path(), Path::new("levels/one.scn.ron"));
+        assert_eq!(path.label(), Some("Scene"));
+
+        let path = AssetPath::from(String::from("textures/grass.png"));
+        assert_eq!(path.source(), None);
+        assert_eq!(path.path(), Path::new("textures/grass.png"));
+        assert_eq!(path.label(), None);
+
+        let path = AssetPath::from("://grass.png");
+        assert_eq!(path.source(), None);
+    }
+
+    #[test]
+    fn sources_have_distinct_ids() {
+        let default = AssetPath::from("grass.png");
+        let named = AssetPath::from("mods://grass.png");
+        assert_ne!(default.get_id(), named.get_id());
+        assert_eq!(
+            default.get_id().source_path_id(),
+            SourcePathId::from(Path::new("grass.png"))
+        );
+        assert_eq!(
+            named.get_id(),
+            AssetPath::new_ref(Path::new("grass.png"), None)
+                .with_source(Some("mods"))
+                .get_id()
+        );
     }
 }
@@ -0,0 +1,937 @@
+use crate::{
+    loader::default_meta,
+    meta::{is_meta_path, meta_path, LoaderName},
+    Asset, AssetIo, AssetIoError, AssetLoader, ErasedAssetLoader, LoadContext, RefChangeChannel,
+};
+use anyhow::{anyhow, Error};
+use bevy_ecs::system::Resource;
+use bevy_tasks::IoTaskPool;
+use bevy_utils::{get_short_name, BoxedFuture, HashMap, HashSet};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The extension of the files recording how a processed asset was produced.
+///
+/// A record is written next to each artifact in the imported asset folder, so the processed
+/// version of `textures/grass.png` is recorded in `textures/grass.png.processed`.
+pub const PROCESSED_INFO_EXTENSION: &str = "processed";
+
+/// Errors that occur while processing assets with an [`AssetProcessor`].
+#[derive(Error, Debug)]
+pub enum AssetProcessorError {
+    /// Asset folder is not a directory.
+    #[error("asset folder path is not a directory: {0}")]
+    AssetFolderNotADirectory(PathBuf),
+
+    /// Encountered an error while reading an asset source.
+    #[error("encountered an error while reading an asset source: {0}")]
+    AssetIoError(#[from] AssetIoError),
+
+    /// The [`Process`] of an asset failed.
+    #[error("encountered an error while processing an asset: {0}")]
+    ProcessError(Error),
+
+    /// The processed asset or its record could not be written to the imported asset folder.
+    #[error("failed to write processed asset to {path:?}: {error}")]
+    WriteError {
+        /// The path that could not be written.
+        path: PathBuf,
+        /// The underlying I/O error.
+        error: io::Error,
+    },
+}
+
+/// A hash of the contents of an asset source.
+///
+/// Hashes are computed with 64-bit FNV-1a, which gives the same results on every platform and
+/// with every version of the crate, so they can be compared with the ones recorded by a previous
+/// run of the [`AssetProcessor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AssetHash(pub u64);
+
+impl AssetHash {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+    /// Hashes the given bytes.
+    pub fn of(bytes: &[u8]) -> Self {
+        AssetHash(fnv1a(Self::OFFSET_BASIS, bytes))
+    }
+
+    /// Hashes an asset source along with the contents of its `.meta` file, if it has one.
+    pub fn of_source(bytes: &[u8], meta: Option<&[u8]>) -> Self {
+        // the length prefix and the marker keep the source and the meta file apart
+        let mut hash = fnv1a(Self::OFFSET_BASIS, &(bytes.len() as u64).to_le_bytes());
+        hash = fnv1a(hash, bytes);
+        if let Some(meta) = meta {
+            hash = fnv1a(fnv1a(hash, &[1]), meta);
+        }
+        AssetHash(hash)
+    }
+}
+
+/// Feeds `bytes` to the 64-bit FNV-1a `hash`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// A file that was read while processing an asset, along with the hash of its contents at the
+/// time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessDependency {
+    /// The path of the dependency, relative to the asset folder.
+    pub path: PathBuf,
+    /// The hash of the dependency when the asset was processed.
+    pub hash: AssetHash,
+}
+
+/// The record written next to a processed asset, describing how it was produced.
+///
+/// The [`AssetProcessor`] compares it to the current state of the asset folder to decide whether
+/// the asset has to be processed again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessedInfo {
+    /// The [name](Process::name) of the process that produced the asset, or `None` if the source
+    /// was copied as-is.
+    pub process: Option<String>,
+    /// The hash of the asset source and its `.meta` file.
+    pub hash: AssetHash,
+    /// The other files that were read while processing the asset.
+    pub dependencies: Vec<ProcessDependency>,
+}
+
+/// A step run by the [`AssetProcessor`] to turn an asset source into the bytes that are loaded at
+/// runtime.
+///
+/// Most processes are built by combining an [`AssetLoader`] with an [`AssetSaver`], see
+/// [`LoadAndSave`] and [`LoadTransformAndSave`].
+pub trait Process: Send + Sync + 'static {
+    /// Processes the asset source found in the context and returns the processed bytes.
+    fn process<'a>(
+        &'a self,
+        context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// Returns a list of extensions supported by this process, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Returns the name identifying this process in [`ProcessedInfo`].
+    ///
+    /// Assets processed by a process with a different name are processed again.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Returns the contents of the `.meta` file written next to the processed asset, which names
+    /// the loader of the processed bytes.
+    ///
+    /// Without one, the processed asset is loaded by the loader registered for its extension,
+    /// with its default settings.
+    fn output_meta(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Turns an [`Asset`] back into bytes once it has been processed.
+pub trait AssetSaver: Send + Sync + 'static {
+    /// The type of asset saved by this saver.
+    type Asset: Asset;
+    /// The loader reading the bytes returned by [`save`](AssetSaver::save).
+    ///
+    /// The `.meta` file written next to the processed asset names this loader, with its default
+    /// settings.
+    type OutputLoader: AssetLoader;
+
+    /// Serializes the asset into the bytes written to the imported asset folder.
+    fn save<'a>(&'a self, asset: &'a Self::Asset) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+}
+
+/// Modifies an [`Asset`] between loading and saving it, for example to optimize a mesh.
+pub trait AssetTransformer: Send + Sync + 'static {
+    /// The type of asset taken by this transformer.
+    type AssetInput: Asset;
+    /// The type of asset returned by this transformer.
+    type AssetOutput: Asset;
+
+    /// Transforms the asset.
+    fn transform<'a>(
+        &'a self,
+        asset: Self::AssetInput,
+    ) -> BoxedFuture<'a, Result<Self::AssetOutput, Error>>;
+}
+
+/// A [`Process`] that loads an asset with an [`AssetLoader`] and writes it with an
+/// [`AssetSaver`].
+pub struct LoadAndSave<L, S> {
+    loader: L,
+    saver: S,
+}
+
+impl<L: AssetLoader, S: AssetSaver> LoadAndSave<L, S> {
+    /// Creates a process loading assets with `loader` and saving them with `saver`.
+    pub fn new(loader: L, saver: S) -> Self {
+        Self { loader, saver }
+    }
+}
+
+impl<L: AssetLoader, S: AssetSaver> Process for LoadAndSave<L, S> {
+    fn process<'a>(
+        &'a self,
+        context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(async move {
+            let asset = context.load_source_asset::<S::Asset>(&self.loader).await?;
+            self.saver.save(&asset).await
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.loader.extensions()
+    }
+
+    fn output_meta(&self) -> Option<String> {
+        Some(default_meta::<S::OutputLoader>())
+    }
+}
+
+/// A [`Process`] that loads an asset with an [`AssetLoader`], modifies it with an
+/// [`AssetTransformer`] and writes it with an [`AssetSaver`].
+pub struct LoadTransformAndSave<L, T, S> {
+    loader: L,
+    transformer: T,
+    saver: S,
+}
+
+impl<L, T, S> LoadTransformAndSave<L, T, S>
+where
+    L: AssetLoader,
+    T: AssetTransformer,
+    S: AssetSaver<Asset = T::AssetOutput>,
+{
+    /// Creates a process loading assets with `loader`, modifying them with `transformer` and
+    /// saving them with `saver`.
+    pub fn new(loader: L, transformer: T, saver: S) -> Self {
+        Self {
+            loader,
+            transformer,
+            saver,
+        }
+    }
+}
+
+impl<L, T, S> Process for LoadTransformAndSave<L, T, S>
+where
+    L: AssetLoader,
+    T: AssetTransformer,
+    S: AssetSaver<Asset = T::AssetOutput>,
+{
+    fn process<'a>(
+        &'a self,
+        context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(async move {
+            let asset = context
+                .load_source_asset::<T::AssetInput>(&self.loader)
+                .await?;
+            let asset = self.transformer.transform(asset).await?;
+            self.saver.save(&asset).await
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.loader.extensions()
+    }
+
+    fn output_meta(&self) -> Option<String> {
+        Some(default_meta::<S::OutputLoader>())
+    }
+}
+
+/// The context in which a [`Process`] runs.
+///
+/// Every file read through the context is recorded as a dependency of the processed asset, so
+/// the asset is processed again when one of them changes.
+pub struct ProcessContext<'a> {
+    path: &'a Path,
+    bytes: &'a [u8],
+    meta: Option<&'a [u8]>,
+    asset_io: &'a dyn AssetIo,
+    dependencies: Vec<PathBuf>,
+}
+
+impl<'a> ProcessContext<'a> {
+    pub(crate) fn new(
+        path: &'a Path,
+        bytes: &'a [u8],
+        meta: Option<&'a [u8]>,
+        asset_io: &'a dyn AssetIo,
+    ) -> Self {
+        Self {
+            path,
+            bytes,
+            meta,
+            asset_io,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Gets the path of the asset source being processed.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Gets the contents of the asset source being processed.
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes
+    }
+
+    /// Records a dependency on the file at the provided path, relative to the asset folder.
+    pub fn add_dependency(&mut self, path: impl Into<PathBuf>) {
+        self.dependencies.push(path.into());
+    }
+
+    /// Reads the contents of another asset source and records it as a dependency.
+    pub async fn read_asset_bytes<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        self.add_dependency(path.as_ref());
+        self.asset_io.load_path(path.as_ref()).await
+    }
+
+    /// Runs `loader` on the asset source and returns the default asset it produced.
+    ///
+    /// The loader gets the settings from the `.meta` file of the asset source, or its default
+    /// settings if there is none. The files read by the loader and the dependencies of the assets it produced are recorded
+    /// as dependencies. Labeled assets are discarded.
+    ///
+    /// Fails if the `.meta` file names a loader other than `loader`, since its settings are meant
+    /// for that loader.
+    pub async fn load_source_asset<A: Asset>(
+        &mut self,
+        loader: &dyn ErasedAssetLoader,
+    ) -> Result<A, Error> {
+        let settings = match self.meta {
+            Some(meta) => {
+                if let Some(name) = LoaderName::from_bytes(meta)?.loader {
+                    let type_name = loader.type_name();
+                    if name != type_name && name != get_short_name(type_name) {
+                        return Err(anyhow!(
+                            "the meta file of {:?} names the loader {name}, \
+                            but the asset is processed with {type_name}",
+                            self.path
+                        ));
+                    }
+                }
+                loader.deserialize_settings(meta)?
+            }
+            None => loader.default_settings(),
+        };
+        let ref_change_channel = RefChangeChannel::default();
+        let mut load_context =
+            LoadContext::new(None, self.path, &ref_change_channel, self.asset_io, 0);
+        loader
+            .load(self.bytes, &*settings, &mut load_context)
+            .await?;
+
+        self.dependencies.append(load_context.read_paths.get_mut());
+        for asset in load_context.labeled_assets.values() {
+            self.dependencies.extend(
+                asset
+                    .dependencies
+                    .iter()
+                    .map(|dependency| dependency.path().to_owned()),
+            );
+        }
+
+        let asset = load_context
+            .labeled_assets
+            .remove(&None)
+            .and_then(|mut asset| asset.value.take())
+            .ok_or_else(|| anyhow!("the loader did not set a default asset"))?;
+        asset.downcast::<A>().map(|asset| *asset).map_err(|asset| {
+            anyhow!(
+                "expected the loader to produce a {}, got a {}",
+                std::any::type_name::<A>(),
+                asset.type_name()
+            )
+        })
+    }
+}
+
+/// What the [`AssetProcessor`] did with an asset source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessResult {
+    /// The source was run through its [`Process`].
+    Processed,
+    /// The source has no [`Process`] and was copied as-is.
+    Copied,
+    /// Neither the source nor its dependencies changed since it was last processed.
+    UpToDate,
+}
+
+/// The outcome of [`AssetProcessor::process_all`].
+#[derive(Debug, Default)]
+pub struct ProcessSummary {
+    /// The assets that were processed or copied.
+    pub processed: Vec<PathBuf>,
+    /// The assets that did not need to be processed again.
+    pub up_to_date: Vec<PathBuf>,
+    /// The assets that failed to process.
+    pub failed: Vec<(PathBuf, AssetProcessorError)>,
+    /// The processed assets that were removed because their source no longer exists.
+    pub removed: Vec<PathBuf>,
+}
+
+/// Internal data for the asset processor.
+///
+/// [`AssetProcessor`] is the public API for interacting with the asset processor.
+pub struct AssetProcessorInternal {
+    source_io: Box<dyn AssetIo>,
+    destination: PathBuf,
+    processes: RwLock<Vec<Arc<dyn Process>>>,
+    extension_to_process_index: RwLock<HashMap<String, usize>>,
+}
+
+/// Processes asset sources ahead of time and writes the results to an imported asset folder.
+///
+/// Each asset source is run through the [`Process`] registered for its extension, and sources
+/// without a process are copied as-is, so the imported asset folder contains every asset the app
+/// needs. A [`ProcessedInfo`] record holding the hashes of the source and of every file read
+/// while processing it is written next to each artifact. Assets are only processed again when one
+/// of these hashes or the process changes. Copied sources keep their `.meta` file, while processed
+/// assets get the one returned by [`Process::output_meta`].
+///
+/// Shipping builds can then load exclusively from the imported asset folder by setting
+/// [`AssetPlugin::mode`](crate::AssetPlugin::mode) to [`AssetMode::Processed`](crate::AssetMode).
+/// With [`AssetMode::ProcessedDev`](crate::AssetMode), the [`AssetPlugin`](crate::AssetPlugin)
+/// inserts this resource and brings the imported asset folder up to date when the app starts.
+/// In the other modes, the processor only runs when [`process_all`](Self::process_all) is called.
+/// Only the default asset source is processed: named asset sources are always loaded as-is.
+///
+/// ```no_run
+/// # use bevy_asset::*;
+/// # use bevy_tasks::IoTaskPool;
+/// IoTaskPool::init(Default::default);
+/// let processor = AssetProcessor::new(
+///     FileAssetIo::new("assets", &None),
+///     FileAssetIo::get_base_path().join("imported_assets"),
+/// );
+/// // processor.add_process(LoadAndSave::new(MyLoader, MySaver));
+/// let summary = processor.process_all().unwrap();
+/// assert!(summary.failed.is_empty());
+/// ```
+#[derive(Clone, Resource)]
+pub struct AssetProcessor {
+    processor: Arc<AssetProcessorInternal>,
+}
+
+impl AssetProcessor {
+    /// Creates a new asset processor reading sources from `source_io` and writing processed
+    /// assets to the `destination` folder.
+    pub fn new<T: AssetIo>(source_io: T, destination: impl Into<PathBuf>) -> Self {
+        Self::with_boxed_io(Box::new(source_io), destination)
+    }
+
+    /// Creates a new asset processor with a boxed asset I/O.
+    pub fn with_boxed_io(source_io: Box<dyn AssetIo>, destination: impl Into<PathBuf>) -> Self {
+        AssetProcessor {
+            processor: Arc::new(AssetProcessorInternal {
+                source_io,
+                destination: destination.into(),
+                processes: Default::default(),
+                extension_to_process_index: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the asset I/O the sources are read from.
+    pub fn source_io(&self) -> &dyn AssetIo {
+        &*self.processor.source_io
+    }
+
+    /// Returns the folder processed assets are written to.
+    pub fn destination(&self) -> &Path {
+        &self.processor.destination
+    }
+
+    /// Adds the provided process to the processor.
+    ///
+    /// If `process` has one or more supported extensions in conflict with processes that came
+    /// before it, it will replace them.
+    pub fn add_process<T: Process>(&self, process: T) {
+        let mut processes = self.processor.processes.write();
+        let process_index = processes.len();
+        for extension in process.extensions() {
+            self.processor
+                .extension_to_process_index
+                .write()
+                .insert(extension.to_string(), process_index);
+        }
+        processes.push(Arc::new(process));
+    }
+
+    /// Returns the process used for the asset source at the provided path, if any.
+    ///
+    /// Like loaders, processes are looked up by every extension of the file name, so `foo.bar.baz`
+    /// is processed by the process for `bar.baz` or, if there is none, the one for `baz`.
+    pub fn get_path_process<P: AsRef<Path>>(&self, path: P) -> Option<Arc<dyn Process>> {
+        let file_name = path.as_ref().file_name()?.to_str()?.to_lowercase();
+        let map = self.processor.extension_to_process_index.read();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(&index) = map.get(ext) {
+                return Some(self.processor.processes.read()[index].clone());
+            }
+        }
+        None
+    }
+
+    /// Returns the path of the processed version of the asset source at `path`.
+    pub fn processed_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.processor.destination.join(path)
+    }
+
+    /// Returns the [`ProcessedInfo`] recorded when the asset source at `path` was last processed.
+    pub fn processed_info<P: AsRef<Path>>(&self, path: P) -> Option<ProcessedInfo> {
+        let bytes = fs::read(info_path(&self.processed_path(path))).ok()?;
+        ron::de::from_bytes(&bytes).ok()
+    }
+
+    /// Processes every asset source that changed since the last run, and removes the processed
+    /// assets whose source was deleted.
+    ///
+    /// Assets are processed in parallel on the [`IoTaskPool`], and this method blocks until all
+    /// of them are done.
+    pub fn process_all(&self) -> Result<ProcessSummary, AssetProcessorError> {
+        let root = Path::new("");
+        if !self.source_io().is_dir(root) {
+            return Err(AssetProcessorError::AssetFolderNotADirectory(
+                root.to_owned(),
+            ));
+        }
+        let mut paths = Vec::new();
+        self.collect_sources(root, &mut paths)?;
+
+        let results = IoTaskPool::get().scope(|scope| {
+            for path in &paths {
+                scope.spawn(async move { (path.clone(), self.process_path(path).await) });
+            }
+        });
+
+        let mut summary = ProcessSummary::default();
+        for (path, result) in results {
+            match result {
+                Ok(ProcessResult::Processed | ProcessResult::Copied) => {
+                    summary.processed.push(path)
+                }
+                Ok(ProcessResult::UpToDate) => summary.up_to_date.push(path),
+                Err(err) => summary.failed.push((path, err)),
+            }
+        }
+
+        let sources: HashSet<&Path> = paths.iter().map(PathBuf::as_path).collect();
+        self.remove_stale(&self.processor.destination, &sources, &mut summary.removed)?;
+        Ok(summary)
+    }
+
+    /// Processes the asset source at the provided path, unless neither it nor its dependencies
+    /// changed since it was last processed.
+    pub async fn process_path(&self, path: &Path) -> Result<ProcessResult, AssetProcessorError> {
+        let bytes = self.source_io().load_path(path).await?;
+        let meta = match self.source_io().load_path(&meta_path(path)).await {
+            Ok(meta) => Some(meta),
+            Err(AssetIoError::NotFound(_)) => None,
+            Err(err) => return Err(err.into()),
+        };
+        let hash = AssetHash::of_source(&bytes, meta.as_deref());
+        let process = self.get_path_process(path);
+        let process_name = process.as_ref().map(|process| process.name().to_string());
+
+        let output_path = self.processed_path(path);
+        if let Some(info) = self.processed_info(path) {
+            if info.hash == hash
+                && info.process == process_name
+                && output_path.is_file()
+                && self.dependencies_unchanged(&info.dependencies).await
+            {
+                return Ok(ProcessResult::UpToDate);
+            }
+        }
+
+        // the source `.meta` file describes how to load the source, so it is only kept for
+        // copied sources
+        let (output, output_meta, dependency_paths, result) = match &process {
+            Some(process) => {
+                let mut context =
+                    ProcessContext::new(path, &bytes, meta.as_deref(), self.source_io());
+                let output = process
+                    .process(&mut context)
+                    .await
+                    .map_err(AssetProcessorError::ProcessError)?;
+                let output_meta = process.output_meta().map(String::into_bytes);
+                (
+                    output,
+                    output_meta,
+                    context.dependencies,
+                    ProcessResult::Processed,
+                )
+            }
+            None => (bytes, meta, Vec::new(), ProcessResult::Copied),
+        };
+
+        let mut dependencies = Vec::new();
+        let mut seen = HashSet::new();
+        for dependency in dependency_paths {
+            if dependency == path || !seen.insert(dependency.clone()) {
+                continue;
+            }
+            let bytes = self.source_io().load_path(&dependency).await?;
+            dependencies.push(ProcessDependency {
+                path: dependency,
+                hash: AssetHash::of(&bytes),
+            });
+        }
+
+        let info = ProcessedInfo {
+            process: process_name,
+            hash,
+            dependencies,
+        };
+        let info = ron::ser::to_string_pretty(&info, Default::default())
+            .expect("`ProcessedInfo` should always be serializable.");
+        write(&output_path, output)?;
+        match output_meta {
+            Some(output_meta) => write(&meta_path(&output_path), output_meta)?,
+            None => remove(&meta_path(&output_path))?,
+        }
+        write(&info_path(&output_path), info)?;
+        Ok(result)
+    }
+
+    async fn dependencies_unchanged(&self, dependencies: &[ProcessDependency]) -> bool {
+        for dependency in dependencies {
+            match self.source_io().load_path(&dependency.path).await {
+                Ok(bytes) if AssetHash::of(&bytes) == dependency.hash => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn collect_sources(
+        &self,
+        path: &Path,
+        paths: &mut Vec<PathBuf>,
+    ) -> Result<(), AssetProcessorError> {
+        for child_path in self.source_io().read_directory(path)? {
+            if self.source_io().is_dir(&child_path) {
+                self.collect_sources(&child_path, paths)?;
+            } else if !is_meta_path(&child_path) {
+                paths.push(child_path);
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_stale(
+        &self,
+        folder: &Path,
+        sources: &HashSet<&Path>,
+        removed: &mut Vec<PathBuf>,
+    ) -> Result<(), AssetProcessorError> {
+        let Ok(entries) = fs::read_dir(folder) else {
+            return Ok(());
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                self.remove_stale(&path, sources, removed)?;
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some(PROCESSED_INFO_EXTENSION) {
+                continue;
+            }
+            let relative_path = path.strip_prefix(&self.processor.destination).unwrap();
+            let output_path = path.with_extension("");
+            let source = relative_path.with_extension("");
+            // a source whose own extension matches is an artifact, not a record
+            if !sources.contains(relative_path) && !sources.contains(source.as_path()) {
+                for path in [&meta_path(&output_path), &output_path, &path] {
+                    remove(path)?;
+                }
+                removed.push(source);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn info_path(output_path: &Path) -> PathBuf {
+    let mut path = output_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(PROCESSED_INFO_EXTENSION);
+    path.into()
+}
+
+fn remove(path: &Path) -> Result<(), AssetProcessorError> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(AssetProcessorError::WriteError {
+            path: path.to_owned(),
+            error,
+        }),
+    }
+}
+
+fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), AssetProcessorError> {
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(path, contents))
+        .map_err(|error| AssetProcessorError::WriteError {
+            path: path.to_owned(),
+            error,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AssetMode, AssetPlugin, FileAssetIo, LoadedAsset};
+    use bevy_app::App;
+    use bevy_reflect::{TypePath, TypeUuid};
+
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "2b0b7c8e-8f2b-4a4f-9d0e-0c6e3c2f6b61"]
+    struct Text(String);
+
+    /// Loads a `.txt` file, appending the contents of the files named in `include:` lines.
+    struct TextLoader;
+    impl AssetLoader for TextLoader {
+        type Settings = ();
+
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _settings: &'a (),
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            Box::pin(async move {
+                let mut text = String::new();
+                for line in std::str::from_utf8(bytes)?.lines() {
+                    match line.strip_prefix("include:") {
+                        Some(path) => {
+                            let included = load_context.read_asset_bytes(path).await?;
+                            text.push_str(std::str::from_utf8(&included)?);
+                        }
+                        None => text.push_str(line),
+                    }
+                }
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    struct Uppercase;
+    impl AssetTransformer for Uppercase {
+        type AssetInput = Text;
+        type AssetOutput = Text;
+
+        fn transform<'a>(&'a self, asset: Text) -> BoxedFuture<'a, Result<Text, Error>> {
+            Box::pin(async move { Ok(Text(asset.0.to_uppercase())) })
+        }
+    }
+
+    struct TextSaver;
+    impl AssetSaver for TextSaver {
+        type Asset = Text;
+        type OutputLoader = TextLoader;
+
+        fn save<'a>(&'a self, asset: &'a Text) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+            Box::pin(async move { Ok(asset.0.clone().into_bytes()) })
+        }
+    }
+
+    fn setup() -> (tempfile::TempDir, AssetProcessor) {
+        IoTaskPool::init(Default::default);
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("assets/nested")).unwrap();
+        fs::write(dir.path().join("assets/nested/a.txt"), "a\ninclude:b.inc").unwrap();
+        fs::write(dir.path().join("assets/b.inc"), "b").unwrap();
+        fs::write(dir.path().join("assets/raw.bin"), [1, 2, 3]).unwrap();
+        let processor = AssetProcessor::new(
+            FileAssetIo::new(dir.path().join("assets"), &None),
+            dir.path().join("imported"),
+        );
+        processor.add_process(LoadTransformAndSave::new(TextLoader, Uppercase, TextSaver));
+        (dir, processor)
+    }
+
+    fn sorted(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn asset_hashes_are_fnv1a() {
+        assert_eq!(AssetHash::of(b""), AssetHash(0xcbf2_9ce4_8422_2325));
+        assert_eq!(AssetHash::of(b"a"), AssetHash(0xaf63_dc4c_8601_ec8c));
+        assert_ne!(
+            AssetHash::of_source(b"a", None),
+            AssetHash::of_source(b"a", Some(b""))
+        );
+        assert_ne!(
+            AssetHash::of_source(b"ab", Some(b"c")),
+            AssetHash::of_source(b"a", Some(b"bc"))
+        );
+    }
+
+    #[test]
+    fn processes_and_copies_sources() {
+        let (dir, processor) = setup();
+        let summary = processor.process_all().unwrap();
+        assert!(summary.failed.is_empty());
+        assert_eq!(
+            sorted(summary.processed),
+            [
+                PathBuf::from("b.inc"),
+                PathBuf::from("nested/a.txt"),
+                PathBuf::from("raw.bin"),
+            ]
+        );
+
+        let imported = dir.path().join("imported");
+        assert_eq!(fs::read(imported.join("nested/a.txt")).unwrap(), b"AB");
+        assert_eq!(fs::read(imported.join("raw.bin")).unwrap(), [1, 2, 3]);
+
+        let info = processor.processed_info("nested/a.txt").unwrap();
+        assert!(info.process.is_some());
+        assert_eq!(info.dependencies.len(), 1);
+        assert_eq!(info.dependencies[0].path, Path::new("b.inc"));
+        assert!(processor
+            .processed_info("raw.bin")
+            .unwrap()
+            .process
+            .is_none());
+    }
+
+    #[test]
+    fn only_reprocesses_changed_assets() {
+        let (dir, processor) = setup();
+        processor.process_all().unwrap();
+
+        let summary = processor.process_all().unwrap();
+        assert!(summary.processed.is_empty());
+        assert_eq!(summary.up_to_date.len(), 3);
+
+        // changing a dependency reprocesses the assets that read it
+        fs::write(dir.path().join("assets/b.inc"), "c").unwrap();
+        let summary = processor.process_all().unwrap();
+        assert_eq!(
+            sorted(summary.processed),
+            [PathBuf::from("b.inc"), PathBuf::from("nested/a.txt")]
+        );
+        let imported = dir.path().join("imported");
+        assert_eq!(fs::read(imported.join("nested/a.txt")).unwrap(), b"AC");
+
+        // deleting an artifact reprocesses it
+        fs::remove_file(imported.join("raw.bin")).unwrap();
+        let summary = processor.process_all().unwrap();
+        assert_eq!(summary.processed, [PathBuf::from("raw.bin")]);
+    }
+
+    #[test]
+    fn removes_assets_without_source() {
+        let (dir, processor) = setup();
+        processor.process_all().unwrap();
+
+        fs::remove_file(dir.path().join("assets/raw.bin")).unwrap();
+        let summary = processor.process_all().unwrap();
+        assert_eq!(summary.removed, [PathBuf::from("raw.bin")]);
+        let imported = dir.path().join("imported");
+        assert!(!imported.join("raw.bin").exists());
+        assert!(!info_path(&imported.join("raw.bin")).exists());
+    }
+
+    #[test]
+    fn plugin_only_processes_in_processed_dev_mode() {
+        for mode in [
+            AssetMode::Unprocessed,
+            AssetMode::Processed,
+            AssetMode::ProcessedDev,
+        ] {
+            let (dir, processor) = setup();
+            let mut app = App::new();
+            app.insert_resource(processor)
+                .add_plugins(AssetPlugin {
+                    mode,
+                    ..Default::default()
+                })
+                .finish();
+            let processed = dir.path().join("imported/raw.bin").exists();
+            assert_eq!(processed, mode == AssetMode::ProcessedDev, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn writes_meta_files_for_the_output() {
+        let (dir, processor) = setup();
+        let source_meta = r#"(loader: "SourceLoader")"#;
+        fs::write(
+            dir.path().join("assets/nested/a.txt.meta"),
+            r#"(loader: "TextLoader")"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("assets/raw.bin.meta"), source_meta).unwrap();
+
+        let summary = processor.process_all().unwrap();
+        assert!(summary.failed.is_empty());
+        // meta files are not assets on their own
+        assert_eq!(summary.processed.len(), 3);
+
+        // processed assets are loaded by the loader of the saver's output, copied ones as before
+        let imported = dir.path().join("imported");
+        let meta = fs::read(imported.join("nested/a.txt.meta")).unwrap();
+        assert_eq!(meta, default_meta::<TextLoader>().into_bytes());
+        let meta = fs::read(imported.join("raw.bin.meta")).unwrap();
+        assert_eq!(meta, source_meta.as_bytes());
+
+        fs::remove_file(dir.path().join("assets/raw.bin")).unwrap();
+        fs::remove_file(dir.path().join("assets/raw.bin.meta")).unwrap();
+        let summary = processor.process_all().unwrap();
+        assert_eq!(summary.removed, [PathBuf::from("raw.bin")]);
+        assert!(!imported.join("raw.bin.meta").exists());
+    }
+
+    #[test]
+    fn meta_naming_another_loader_fails() {
+        let (dir, processor) = setup();
+        fs::write(
+            dir.path().join("assets/nested/a.txt.meta"),
+            r#"(loader: "OtherLoader")"#,
+        )
+        .unwrap();
+
+        let summary = processor.process_all().unwrap();
+        let [(path, AssetProcessorError::ProcessError(error))] = &summary.failed[..] else {
+            panic!("expected a process error, got {:?}", summary.failed);
+        };
+        assert_eq!(path, Path::new("nested/a.txt"));
+        assert!(error.to_string().contains("OtherLoader"));
+        assert!(!dir.path().join("imported/nested/a.txt").exists());
+    }
+}
@@ -4,6 +4,7 @@ mod diagnostic;
 mod entity_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod schedule_timing_diagnostics_plugin;
 mod system_information_diagnostics_plugin;
 
 use bevy_app::prelude::*;
@@ -11,6 +12,9 @@ pub use diagnostic::*;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use schedule_timing_diagnostics_plugin::{
+    ScheduleTimingDiagnostics, ScheduleTimingDiagnosticsPlugin, TimingDiagnosticIds,
+};
 pub use system_information_diagnostics_plugin::SystemInformationDiagnosticsPlugin;
 
 /// Adds core diagnostics resources to an App.
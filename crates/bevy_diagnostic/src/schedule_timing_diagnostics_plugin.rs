@@ -0,0 +1,305 @@
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{RunTiming, ScheduleTimings, SystemKey},
+};
+use bevy_utils::{get_short_name, FixedState, HashMap, Instant};
+
+use crate::{Diagnostic, DiagnosticId, DiagnosticMeasurement, DiagnosticsStore};
+
+/// Adds per-system and per-schedule run time diagnostics to an App.
+///
+/// Inserts [`ScheduleTimings`] so that executors measure how long each system and schedule takes
+/// to run, then turns the measurements of every frame into [`Diagnostic`]s in the
+/// [`DiagnosticsStore`]. Each system and schedule gets a diagnostic of its total run time during
+/// the frame (in milliseconds) and one of its run count. The [`ScheduleTimingDiagnostics`] resource
+/// maps systems and schedules to those diagnostics.
+pub struct ScheduleTimingDiagnosticsPlugin {
+    /// The maximum history length of the created diagnostics.
+    pub max_history_length: usize,
+    /// The smoothing factor of the created diagnostics,
+    /// see [`Diagnostic::with_smoothing_factor`].
+    pub smoothing_factor: f64,
+}
+
+impl Default for ScheduleTimingDiagnosticsPlugin {
+    fn default() -> Self {
+        ScheduleTimingDiagnosticsPlugin {
+            max_history_length: 120,
+            smoothing_factor: 2.0 / 21.0,
+        }
+    }
+}
+
+impl Plugin for ScheduleTimingDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<ScheduleTimings>()
+            .insert_resource(ScheduleTimingDiagnostics {
+                max_history_length: self.max_history_length,
+                smoothing_factor: self.smoothing_factor,
+                systems: HashMap::new(),
+                schedules: HashMap::new(),
+            })
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl ScheduleTimingDiagnosticsPlugin {
+    /// Moves the measurements collected in [`ScheduleTimings`] into the [`DiagnosticsStore`].
+    ///
+    /// Systems and schedules that have a diagnostic but did not run this frame are measured as
+    /// taking no time, so averages reflect the cost per frame.
+    pub fn diagnostic_system(
+        mut timings: ResMut<ScheduleTimings>,
+        mut timing_diagnostics: ResMut<ScheduleTimingDiagnostics>,
+        mut store: ResMut<DiagnosticsStore>,
+    ) {
+        let (systems, schedules) = timings.drain();
+        let time = Instant::now();
+        let systems = systems
+            .into_iter()
+            .map(|(key, system)| (key, (get_short_name(&system.name), system.timing)))
+            .collect();
+        let schedules = schedules
+            .into_iter()
+            .map(|(name, timing)| (name.clone(), (name, timing)))
+            .collect();
+        let ScheduleTimingDiagnostics {
+            max_history_length,
+            smoothing_factor,
+            systems: system_ids,
+            schedules: schedule_ids,
+        } = &mut *timing_diagnostics;
+        let settings = (*max_history_length, *smoothing_factor);
+
+        record_timings(&mut store, system_ids, systems, time, settings);
+        record_timings(&mut store, schedule_ids, schedules, time, settings);
+    }
+}
+
+/// Records the `timings` of systems or schedules, given along with their display name, creating
+/// the diagnostics of the ones measured for the first time.
+fn record_timings<K: Hash + Eq>(
+    store: &mut DiagnosticsStore,
+    ids: &mut HashMap<K, TimingDiagnosticIds>,
+    mut timings: HashMap<K, (String, RunTiming)>,
+    time: Instant,
+    (max_history_length, smoothing_factor): (usize, f64),
+) {
+    for (key, diagnostic_ids) in ids.iter() {
+        let timing = timings
+            .remove(key)
+            .map(|(_, timing)| timing)
+            .unwrap_or_default();
+        diagnostic_ids.add_measurements(store, time, timing);
+    }
+    for (key, (name, timing)) in timings {
+        let diagnostic_ids = TimingDiagnosticIds::of(&key);
+        diagnostic_ids.register(store, &name, max_history_length, smoothing_factor);
+        diagnostic_ids.add_measurements(store, time, timing);
+        ids.insert(key, diagnostic_ids);
+    }
+}
+
+/// The [`DiagnosticId`]s of the run time and run count diagnostics of a system or schedule.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TimingDiagnosticIds {
+    /// The diagnostic of the total run time during a frame, in milliseconds.
+    pub duration: DiagnosticId,
+    /// The diagnostic of the number of runs during a frame.
+    pub runs: DiagnosticId,
+}
+
+impl TimingDiagnosticIds {
+    /// Derives the ids from a hash of the [`SystemKey`] of a system or the name of a schedule, so
+    /// they are the same in every run of the app as long as systems are added in the same order.
+    fn of(key: &impl Hash) -> Self {
+        let mut hasher = FixedState.build_hasher();
+        key.hash(&mut hasher);
+        let hash = u128::from(hasher.finish()) << 64;
+        TimingDiagnosticIds {
+            duration: DiagnosticId::from_u128(hash),
+            runs: DiagnosticId::from_u128(hash | 1),
+        }
+    }
+
+    fn register(
+        &self,
+        store: &mut DiagnosticsStore,
+        name: &str,
+        max_history_length: usize,
+        smoothing_factor: f64,
+    ) {
+        store.add(
+            Diagnostic::new(self.duration, format!("{name} time"), max_history_length)
+                .with_suffix("ms")
+                .with_smoothing_factor(smoothing_factor),
+        );
+        store.add(
+            Diagnostic::new(self.runs, format!("{name} runs"), max_history_length)
+                .with_smoothing_factor(smoothing_factor),
+        );
+    }
+
+    fn add_measurements(&self, store: &mut DiagnosticsStore, time: Instant, timing: RunTiming) {
+        if let Some(diagnostic) = store.get_mut(self.duration) {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time,
+                value: timing.duration.as_secs_f64() * 1000.0,
+            });
+        }
+        if let Some(diagnostic) = store.get_mut(self.runs) {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time,
+                value: timing.runs as f64,
+            });
+        }
+    }
+}
+
+/// Maps the systems and schedules measured by the [`ScheduleTimingDiagnosticsPlugin`] to their
+/// [`Diagnostic`]s.
+///
+/// Systems are identified by their [`SystemKey`], and schedules by the [`Debug`] representation
+/// of their label. The diagnostics of a system are named after its short
+/// [name](bevy_ecs::system::System::name).
+#[derive(Resource, Debug)]
+pub struct ScheduleTimingDiagnostics {
+    max_history_length: usize,
+    smoothing_factor: f64,
+    systems: HashMap<SystemKey, TimingDiagnosticIds>,
+    schedules: HashMap<String, TimingDiagnosticIds>,
+}
+
+impl ScheduleTimingDiagnostics {
+    /// Returns the diagnostics of the given system.
+    pub fn system(&self, key: &SystemKey) -> Option<TimingDiagnosticIds> {
+        self.systems.get(key).copied()
+    }
+
+    /// Returns the diagnostics of the schedule with the given name.
+    pub fn schedule(&self, name: &str) -> Option<TimingDiagnosticIds> {
+        self.schedules.get(name).copied()
+    }
+
+    /// Returns the diagnostics of every measured system.
+    pub fn systems(&self) -> impl Iterator<Item = (&SystemKey, TimingDiagnosticIds)> {
+        self.systems.iter().map(|(key, ids)| (key, *ids))
+    }
+
+    /// Returns the diagnostics of every measured schedule.
+    pub fn schedules(&self) -> impl Iterator<Item = (&str, TimingDiagnosticIds)> {
+        self.schedules
+            .iter()
+            .map(|(name, ids)| (name.as_str(), *ids))
+    }
+
+    /// Returns up to `count` systems with the highest smoothed run time, slowest first,
+    /// along with their run time diagnostic.
+    pub fn slowest_systems<'a>(
+        &'a self,
+        store: &'a DiagnosticsStore,
+        count: usize,
+    ) -> Vec<(&'a SystemKey, &'a Diagnostic)> {
+        let mut systems: Vec<_> = self
+            .systems
+            .iter()
+            .filter_map(|(key, ids)| Some((key, store.get(ids.duration)?)))
+            .filter(|(_, diagnostic)| diagnostic.is_enabled)
+            .collect();
+        systems.sort_by(|(_, a), (_, b)| {
+            let a = a.smoothed().unwrap_or_default();
+            let b = b.smoothed().unwrap_or_default();
+            b.total_cmp(&a)
+        });
+        systems.truncate(count);
+        systems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(ScheduleTimingDiagnosticsPlugin::default())
+            .add_systems(Update, (|| {}, || {}));
+        app.update();
+        app
+    }
+
+    #[test]
+    fn records_system_and_schedule_diagnostics() {
+        let app = app();
+        let store = app.world.resource::<DiagnosticsStore>();
+        let diagnostics = app.world.resource::<ScheduleTimingDiagnostics>();
+
+        // both closures have the same name, but are measured separately
+        let mut systems: Vec<_> = diagnostics
+            .systems()
+            .filter(|(key, _)| key.schedule == "Update")
+            .collect();
+        systems.sort_by_key(|(key, _)| key.index);
+        assert_eq!(systems.len(), 2);
+        assert_ne!(systems[0].1, systems[1].1);
+        for (key, ids) in systems {
+            assert_eq!(diagnostics.system(key), Some(ids));
+            let duration = store.get(ids.duration).unwrap();
+            let runs = store.get(ids.runs).unwrap();
+            assert_ne!(duration.name, runs.name);
+            assert!(duration.value().is_some());
+            assert_eq!(runs.value(), Some(1.0));
+        }
+
+        let ids = diagnostics.schedule("Update").unwrap();
+        assert_eq!(store.get(ids.runs).unwrap().value(), Some(1.0));
+    }
+
+    #[test]
+    fn diagnostics_follow_systems_when_the_schedule_is_rebuilt() {
+        fn quick() {}
+        fn slow() {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let mut app = App::new();
+        app.add_plugins(ScheduleTimingDiagnosticsPlugin::default())
+            .add_systems(Update, slow);
+        app.update();
+        // `quick` now runs first, which changes the position of `slow` in the schedule
+        app.add_systems(Update, quick.before(slow));
+        app.update();
+
+        let store = app.world.resource::<DiagnosticsStore>();
+        let diagnostics = app.world.resource::<ScheduleTimingDiagnostics>();
+        let duration_of = |name: &str| {
+            let (_, ids) = diagnostics
+                .systems()
+                .find(|(_, ids)| store.get(ids.duration).unwrap().name == format!("{name} time"))
+                .unwrap();
+            store.get(ids.duration).unwrap().value().unwrap()
+        };
+        assert!(duration_of("slow") >= 5.0);
+        assert!(duration_of("quick") < 5.0);
+    }
+
+    #[test]
+    fn diagnostic_ids_are_stable() {
+        let first = app();
+        let second = app();
+        let ids = |app: &App| {
+            let diagnostics = app.world.resource::<ScheduleTimingDiagnostics>();
+            let mut ids: Vec<_> = diagnostics
+                .systems()
+                .map(|(key, ids)| (key.clone(), ids.duration, ids.runs))
+                .collect();
+            ids.sort_by(|a, b| a.1.cmp(&b.1));
+            ids
+        };
+        assert_eq!(ids(&first), ids(&second));
+    }
+}
@@ -1,7 +1,10 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, DeriveInput, Ident, LitStr, Path, Result};
+use syn::{
+    parse_macro_input, parse_quote, Data, DataStruct, DeriveInput, Ident, Index, LitStr, Member,
+    Path, Result, Type,
+};
 
 pub fn derive_event(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
@@ -12,15 +15,57 @@ pub fn derive_event(input: TokenStream) -> TokenStream {
         .predicates
         .push(parse_quote! { Self: Send + Sync + 'static });
 
+    let attrs = match parse_event_attr(&ast) {
+        Ok(attrs) => attrs,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let traversal = attrs.traversal.unwrap_or_else(|| parse_quote! { () });
+    let auto_propagate = attrs.auto_propagate;
+
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
 
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::event::Event for #struct_name #type_generics #where_clause {
+            type Traversal = #traversal;
+            const AUTO_PROPAGATE: bool = #auto_propagate;
         }
     })
 }
 
+pub const EVENT: &str = "event";
+pub const TRAVERSAL: &str = "traversal";
+pub const AUTO_PROPAGATE: &str = "auto_propagate";
+
+struct EventAttrs {
+    traversal: Option<Type>,
+    auto_propagate: bool,
+}
+
+fn parse_event_attr(ast: &DeriveInput) -> Result<EventAttrs> {
+    let mut attrs = EventAttrs {
+        traversal: None,
+        auto_propagate: false,
+    };
+
+    for meta in ast.attrs.iter().filter(|a| a.path().is_ident(EVENT)) {
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident(TRAVERSAL) {
+                attrs.traversal = Some(nested.value()?.parse::<Type>()?);
+                Ok(())
+            } else if nested.path.is_ident(AUTO_PROPAGATE) {
+                attrs.auto_propagate = true;
+                Ok(())
+            } else {
+                Err(nested.error("Unsupported attribute"))
+            }
+        })?;
+    }
+
+    Ok(attrs)
+}
+
 pub fn derive_resource(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
     let bevy_ecs_path: Path = crate::bevy_ecs_path();
@@ -50,6 +95,19 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
 
+    let relationship = match derive_relationship(&ast, &attrs, &bevy_ecs_path) {
+        Ok(relationship) => relationship,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let register_hooks = register_relationship_hooks(&attrs, &bevy_ecs_path);
+    let track_previous = attrs.track_previous.then(|| {
+        quote! {
+            fn track_previous() -> Option<#bevy_ecs_path::component::TrackPrevious> {
+                Some(#bevy_ecs_path::component::TrackPrevious::new::<Self>())
+            }
+        }
+    });
+
     ast.generics
         .make_where_clause()
         .predicates
@@ -61,17 +119,40 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             type Storage = #storage;
+            #register_hooks
+            #track_previous
         }
+
+        #relationship
     })
 }
 
 pub const COMPONENT: &str = "component";
 pub const STORAGE: &str = "storage";
+pub const TRACK_PREVIOUS: &str = "track_previous";
+pub const RELATIONSHIP: &str = "relationship";
+pub const RELATIONSHIP_TARGET: &str = "relationship_target";
+pub const DESPAWN_BEHAVIOR: &str = "despawn_behavior";
 
 struct Attrs {
     storage: StorageTy,
+    track_previous: bool,
+    relationship: Option<Type>,
+    relationship_target: Option<(Type, DespawnBehavior)>,
 }
 
+#[derive(Clone, Copy)]
+enum DespawnBehavior {
+    Cascade,
+    Orphan,
+    Remove,
+}
+
+// values for `despawn_behavior` attribute
+const CASCADE: &str = "Cascade";
+const ORPHAN: &str = "Orphan";
+const REMOVE: &str = "Remove";
+
 #[derive(Clone, Copy)]
 enum StorageTy {
     Table,
@@ -85,6 +166,9 @@ const SPARSE_SET: &str = "SparseSet";
 fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        track_previous: false,
+        relationship: None,
+        relationship_target: None,
     };
 
     for meta in ast.attrs.iter().filter(|a| a.path().is_ident(COMPONENT)) {
@@ -100,15 +184,184 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                     }
                 };
                 Ok(())
+            } else if nested.path.is_ident(TRACK_PREVIOUS) {
+                attrs.track_previous = true;
+                Ok(())
+            } else {
+                Err(nested.error("Unsupported attribute"))
+            }
+        })?;
+    }
+
+    for meta in ast.attrs.iter().filter(|a| a.path().is_ident(RELATIONSHIP)) {
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident(RELATIONSHIP_TARGET) {
+                attrs.relationship = Some(nested.value()?.parse::<Type>()?);
+                Ok(())
             } else {
                 Err(nested.error("Unsupported attribute"))
             }
         })?;
     }
 
+    for meta in ast
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident(RELATIONSHIP_TARGET))
+    {
+        let mut relationship = None;
+        let mut despawn_behavior = DespawnBehavior::Remove;
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident(RELATIONSHIP) {
+                relationship = Some(nested.value()?.parse::<Type>()?);
+                Ok(())
+            } else if nested.path.is_ident(DESPAWN_BEHAVIOR) {
+                despawn_behavior = match nested.value()?.parse::<LitStr>()?.value() {
+                    s if s == CASCADE => DespawnBehavior::Cascade,
+                    s if s == ORPHAN => DespawnBehavior::Orphan,
+                    s if s == REMOVE => DespawnBehavior::Remove,
+                    s => {
+                        return Err(nested.error(format!(
+                            "Invalid despawn behavior `{s}`, expected '{CASCADE}', '{ORPHAN}' or '{REMOVE}'.",
+                        )));
+                    }
+                };
+                Ok(())
+            } else {
+                Err(nested.error("Unsupported attribute"))
+            }
+        })?;
+        let Some(relationship) = relationship else {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "Missing `relationship = T` in `relationship_target` attribute.",
+            ));
+        };
+        attrs.relationship_target = Some((relationship, despawn_behavior));
+    }
+
+    if attrs.relationship.is_some() && attrs.relationship_target.is_some() {
+        return Err(syn::Error::new_spanned(
+            &ast.ident,
+            "A component cannot be both a `relationship` and a `relationship_target`.",
+        ));
+    }
+
     Ok(attrs)
 }
 
+/// Returns the single field of a struct, used to store the entity or sources of a relationship.
+fn single_field(ast: &DeriveInput) -> Result<(Member, &Type)> {
+    let Data::Struct(DataStruct { fields, .. }) = &ast.data else {
+        return Err(syn::Error::new_spanned(
+            &ast.ident,
+            "Relationships can only be derived for structs.",
+        ));
+    };
+    if fields.len() != 1 {
+        return Err(syn::Error::new_spanned(
+            &ast.ident,
+            "Relationships can only be derived for structs with a single field.",
+        ));
+    }
+    let field = fields.iter().next().unwrap();
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(0)),
+    };
+    Ok((member, &field.ty))
+}
+
+fn derive_relationship(
+    ast: &DeriveInput,
+    attrs: &Attrs,
+    bevy_ecs_path: &Path,
+) -> Result<TokenStream2> {
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    if let Some(relationship_target) = &attrs.relationship {
+        let (member, _) = single_field(ast)?;
+        return Ok(quote! {
+            impl #impl_generics #bevy_ecs_path::relationship::Relationship for #struct_name #type_generics #where_clause {
+                type RelationshipTarget = #relationship_target;
+
+                #[inline]
+                fn get(&self) -> #bevy_ecs_path::entity::Entity {
+                    self.#member
+                }
+
+                #[inline]
+                fn from(entity: #bevy_ecs_path::entity::Entity) -> Self {
+                    Self { #member: entity }
+                }
+            }
+        });
+    }
+
+    if let Some((relationship, despawn_behavior)) = &attrs.relationship_target {
+        let (member, sources) = single_field(ast)?;
+        let despawn_behavior = match despawn_behavior {
+            DespawnBehavior::Cascade => Ident::new(CASCADE, Span::call_site()),
+            DespawnBehavior::Orphan => Ident::new(ORPHAN, Span::call_site()),
+            DespawnBehavior::Remove => Ident::new(REMOVE, Span::call_site()),
+        };
+        return Ok(quote! {
+            impl #impl_generics #bevy_ecs_path::relationship::RelationshipTarget for #struct_name #type_generics #where_clause {
+                type Relationship = #relationship;
+                type Sources = #sources;
+                const DESPAWN_BEHAVIOR: #bevy_ecs_path::relationship::RelationshipDespawnBehavior =
+                    #bevy_ecs_path::relationship::RelationshipDespawnBehavior::#despawn_behavior;
+
+                #[inline]
+                fn sources(&self) -> &Self::Sources {
+                    &self.#member
+                }
+
+                #[inline]
+                fn sources_mut(&mut self) -> &mut Self::Sources {
+                    &mut self.#member
+                }
+
+                #[inline]
+                fn from_sources(sources: Self::Sources) -> Self {
+                    Self { #member: sources }
+                }
+            }
+        });
+    }
+
+    Ok(TokenStream2::new())
+}
+
+fn register_relationship_hooks(attrs: &Attrs, bevy_ecs_path: &Path) -> TokenStream2 {
+    if attrs.relationship.is_some() {
+        quote! {
+            fn register_component_hooks(hooks: &mut #bevy_ecs_path::component::ComponentHooks) {
+                hooks
+                    .on_insert(<Self as #bevy_ecs_path::relationship::Relationship>::on_insert)
+                    .on_replace(<Self as #bevy_ecs_path::relationship::Relationship>::on_replace);
+            }
+
+            fn clone_handler() -> #bevy_ecs_path::component::ComponentCloneHandler {
+                #bevy_ecs_path::component::ComponentCloneHandler::relationship::<Self>()
+            }
+        }
+    } else if attrs.relationship_target.is_some() {
+        quote! {
+            fn register_component_hooks(hooks: &mut #bevy_ecs_path::component::ComponentHooks) {
+                hooks.on_remove(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_remove);
+            }
+
+            fn clone_handler() -> #bevy_ecs_path::component::ComponentCloneHandler {
+                #bevy_ecs_path::component::ComponentCloneHandler::relationship_target::<Self>()
+            }
+        }
+    } else {
+        TokenStream2::new()
+    }
+}
+
 fn storage_path(bevy_ecs_path: &Path, ty: StorageTy) -> TokenStream2 {
     let typename = match ty {
         StorageTy::Table => Ident::new("TableStorage", Span::call_site()),
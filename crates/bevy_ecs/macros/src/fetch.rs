@@ -352,6 +352,16 @@ pub fn derive_world_query_impl(input: TokenStream) -> TokenStream {
                     }
                 }
 
+                fn get_state(world: &#path::world::World) -> Option<#state_struct_name #user_ty_generics> {
+                    Some(#state_struct_name {
+                        #(#named_field_idents: <#field_types>::get_state(world)?,)*
+                    })
+                }
+
+                fn set_access(state: &mut Self::State, _access: &#path::query::FilteredAccess<#path::component::ComponentId>) {
+                    #(<#field_types>::set_access(&mut state.#named_field_idents, _access);)*
+                }
+
                 fn matches_component_set(state: &Self::State, _set_contains_id: &impl Fn(#path::component::ComponentId) -> bool) -> bool {
                     true #(&& <#field_types>::matches_component_set(&state.#named_field_idents, _set_contains_id))*
                 }
@@ -21,7 +21,7 @@
 
 use crate::{
     bundle::BundleId,
-    component::{ComponentId, StorageType},
+    component::{ComponentId, Components, StorageType},
     entity::{Entity, EntityLocation},
     storage::{ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId, TableRow},
 };
@@ -297,10 +297,34 @@ pub struct Archetype {
     edges: Edges,
     entities: Vec<ArchetypeEntity>,
     components: ImmutableSparseSet<ComponentId, ArchetypeComponentInfo>,
+    flags: ArchetypeFlags,
+}
+
+/// Caches whether any component of an [`Archetype`] has a given
+/// [`ComponentHooks`](crate::component::ComponentHooks) hook registered.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ArchetypeFlags {
+    on_add: bool,
+    on_insert: bool,
+    on_replace: bool,
+    on_remove: bool,
+}
+
+impl ArchetypeFlags {
+    fn add_component(&mut self, components: &Components, component_id: ComponentId) {
+        if let Some(info) = components.get_info(component_id) {
+            let hooks = info.hooks();
+            self.on_add |= hooks.on_add.is_some();
+            self.on_insert |= hooks.on_insert.is_some();
+            self.on_replace |= hooks.on_replace.is_some();
+            self.on_remove |= hooks.on_remove.is_some();
+        }
+    }
 }
 
 impl Archetype {
     pub(crate) fn new(
+        components_info: &Components,
         id: ArchetypeId,
         table_id: TableId,
         table_components: impl Iterator<Item = (ComponentId, ArchetypeComponentId)>,
@@ -309,7 +333,9 @@ impl Archetype {
         let (min_table, _) = table_components.size_hint();
         let (min_sparse, _) = sparse_set_components.size_hint();
         let mut components = SparseSet::with_capacity(min_table + min_sparse);
+        let mut flags = ArchetypeFlags::default();
         for (component_id, archetype_component_id) in table_components {
+            flags.add_component(components_info, component_id);
             components.insert(
                 component_id,
                 ArchetypeComponentInfo {
@@ -320,6 +346,7 @@ impl Archetype {
         }
 
         for (component_id, archetype_component_id) in sparse_set_components {
+            flags.add_component(components_info, component_id);
             components.insert(
                 component_id,
                 ArchetypeComponentInfo {
@@ -334,9 +361,34 @@ impl Archetype {
             entities: Vec::new(),
             components: components.into_immutable(),
             edges: Default::default(),
+            flags,
         }
     }
 
+    /// Returns `true` if any of the components in this archetype have an `on_add` hook.
+    #[inline]
+    pub(crate) fn has_on_add(&self) -> bool {
+        self.flags.on_add
+    }
+
+    /// Returns `true` if any of the components in this archetype have an `on_insert` hook.
+    #[inline]
+    pub(crate) fn has_on_insert(&self) -> bool {
+        self.flags.on_insert
+    }
+
+    /// Returns `true` if any of the components in this archetype have an `on_replace` hook.
+    #[inline]
+    pub(crate) fn has_on_replace(&self) -> bool {
+        self.flags.on_replace
+    }
+
+    /// Returns `true` if any of the components in this archetype have an `on_remove` hook.
+    #[inline]
+    pub(crate) fn has_on_remove(&self) -> bool {
+        self.flags.on_remove
+    }
+
     /// Fetches the ID for the archetype.
     #[inline]
     pub fn id(&self) -> ArchetypeId {
@@ -523,6 +575,11 @@ impl Archetype {
     pub(crate) fn clear_entities(&mut self) {
         self.entities.clear();
     }
+
+    /// Shrinks the capacity of the archetype's entity list to its length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+    }
 }
 
 /// An opaque generational id that changes every time the set of [`Archetypes`] changes.
@@ -613,7 +670,12 @@ impl Archetypes {
             archetype_ids: Default::default(),
             archetype_component_count: 0,
         };
-        archetypes.get_id_or_insert(TableId::empty(), Vec::new(), Vec::new());
+        archetypes.get_id_or_insert(
+            &Components::default(),
+            TableId::empty(),
+            Vec::new(),
+            Vec::new(),
+        );
         archetypes
     }
 
@@ -688,6 +750,7 @@ impl Archetypes {
     /// [`TableId`] must exist in tables
     pub(crate) fn get_id_or_insert(
         &mut self,
+        components: &Components,
         table_id: TableId,
         table_components: Vec<ComponentId>,
         sparse_set_components: Vec<ComponentId>,
@@ -713,6 +776,7 @@ impl Archetypes {
                 let sparse_set_archetype_components =
                     (sparse_start..*archetype_component_count).map(ArchetypeComponentId);
                 archetypes.push(Archetype::new(
+                    components,
                     id,
                     table_id,
                     table_components.into_iter().zip(table_archetype_components),
@@ -737,6 +801,13 @@ impl Archetypes {
             archetype.clear_entities();
         }
     }
+
+    /// Shrinks the entity lists of all archetypes to their lengths.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for archetype in &mut self.archetypes {
+            archetype.shrink_to_fit();
+        }
+    }
 }
 
 impl Index<ArchetypeId> for Archetypes {
@@ -345,6 +345,16 @@ impl BundleInfo {
         &self.component_ids
     }
 
+    /// Returns `true` if any component in this bundle has a
+    /// [`ComponentHooks`](crate::component::ComponentHooks) hook registered.
+    pub(crate) fn has_hooks(&self, components: &Components) -> bool {
+        self.component_ids.iter().any(|&id| {
+            components
+                .get_info(id)
+                .map_or(false, |info| !info.hooks().is_empty())
+        })
+    }
+
     pub(crate) fn get_bundle_inserter<'a, 'b>(
         &'b self,
         entities: &'a mut Entities,
@@ -559,8 +569,12 @@ impl BundleInfo {
                     new_sparse_set_components
                 };
             };
-            let new_archetype_id =
-                archetypes.get_id_or_insert(table_id, table_components, sparse_set_components);
+            let new_archetype_id = archetypes.get_id_or_insert(
+                components,
+                table_id,
+                table_components,
+                sparse_set_components,
+            );
             // add an edge from the old archetype to the new archetype
             archetypes[archetype_id].edges_mut().insert_add_bundle(
                 self.id,
@@ -928,3 +942,193 @@ fn initialize_dynamic_bundle(
 
     (id, storage_types)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct C;
+
+    #[derive(Resource, Default)]
+    struct R(usize);
+
+    impl R {
+        #[track_caller]
+        fn assert_order(&mut self, count: usize) {
+            assert_eq!(count, self.0);
+            self.0 += 1;
+        }
+    }
+
+    #[test]
+    fn component_hook_order_spawn_despawn() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .on_insert(|mut world, _, _| world.resource_mut::<R>().assert_order(1))
+            .on_remove(|mut world, _, _| world.resource_mut::<R>().assert_order(2));
+
+        let entity = world.spawn(A).id();
+        world.despawn(entity);
+        assert_eq!(3, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_insert_remove() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .on_insert(|mut world, _, _| world.resource_mut::<R>().assert_order(1))
+            .on_remove(|mut world, _, _| world.resource_mut::<R>().assert_order(2));
+
+        let mut entity = world.spawn_empty();
+        entity.insert(A);
+        entity.remove::<A>();
+        assert_eq!(3, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_replace() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .on_insert(|mut world, _, _| world.resource_mut::<R>().0 += 10);
+
+        let mut entity = world.spawn(A);
+        entity.insert(A);
+        entity.insert((A, B));
+        // `on_add` only runs once, `on_insert` runs for every insertion.
+        assert_eq!(31, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_take_and_batch() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().0 += 1)
+            .on_remove(|mut world, _, _| world.resource_mut::<R>().0 -= 1);
+
+        let entities: Vec<Entity> = world.spawn_batch([(A, B), (A, B), (A, B)]).collect();
+        assert_eq!(3, entities.len());
+        assert_eq!(3, world.resource::<R>().0);
+
+        assert!(world.entity_mut(entities[0]).take::<(A, C)>().is_none());
+        assert_eq!(3, world.resource::<R>().0);
+        assert!(world.entity_mut(entities[0]).take::<A>().is_some());
+        assert_eq!(2, world.resource::<R>().0);
+
+        world
+            .insert_or_spawn_batch([(entities[0], A), (entities[1], A)])
+            .unwrap();
+        assert_eq!(3, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_commands() {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, entity, _| {
+                world.commands().entity(entity).insert(B);
+            })
+            .on_remove(|mut world, entity, _| {
+                // The component is still present while `on_remove` runs.
+                assert!(world.get::<A>(entity).is_some());
+                world.commands().spawn(C);
+            });
+
+        let entity = world.spawn(A).id();
+        world.flush_commands();
+        assert!(world.entity(entity).contains::<B>());
+
+        world.despawn(entity);
+        assert_eq!(1, world.query::<&C>().iter(&world).count());
+    }
+
+    #[test]
+    fn component_hook_with_command_queue() {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<A>()
+            .on_insert(|mut world, entity, _| {
+                world.commands().entity(entity).insert(B);
+            });
+
+        let mut queue = crate::system::CommandQueue::default();
+        let entity = {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.spawn(A).id()
+        };
+        queue.apply(&mut world);
+        assert!(world.entity(entity).contains::<B>());
+    }
+
+    #[test]
+    fn component_hook_despawns_on_spawn() {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, entity, _| {
+                world.commands().entity(entity).despawn();
+            });
+
+        let entity = world.spawn(A);
+        assert!(entity.is_despawned());
+        let entity = entity.id();
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    #[test]
+    fn component_hook_despawns_on_insert() {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<B>()
+            .on_insert(|mut world, entity, _| {
+                world.commands().entity(entity).despawn();
+            });
+
+        let mut entity = world.spawn(A);
+        entity.insert(B);
+        assert!(entity.is_despawned());
+        // despawning an entity that is already despawned does nothing
+        entity.despawn();
+        assert_eq!(world.entities().len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "was despawned by a command queued by a hook or observer")]
+    fn component_hook_despawned_entity_mut_panics() {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<B>()
+            .on_insert(|mut world, entity, _| {
+                world.commands().entity(entity).despawn();
+            });
+
+        world.spawn(A).insert(B).insert(C);
+    }
+
+    #[test]
+    #[should_panic]
+    fn component_hook_registered_after_use() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.register_component_hooks::<A>().on_add(|_, _, _| {});
+    }
+}
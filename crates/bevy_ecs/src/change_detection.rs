@@ -2,12 +2,15 @@
 
 use crate::{
     component::{Tick, TickCells},
+    entity::Entity,
     ptr::PtrMut,
+    storage::PreviousValues,
     system::Resource,
 };
 use bevy_ptr::{Ptr, UnsafeCellDeref};
 use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
 
 /// The (arbitrarily chosen) minimum number of world tick increments between `check_tick` scans.
 ///
@@ -164,10 +167,10 @@ pub trait DetectChangesMut: DetectChanges {
     where
         Self::Inner: Sized + PartialEq,
     {
-        let old = self.bypass_change_detection();
-        if *old != value {
-            *old = value;
+        if *self.bypass_change_detection() != value {
+            // flag the change first, so that tracked previous values are captured before writing
             self.set_changed();
+            *self.bypass_change_detection() = value;
         }
     }
 
@@ -232,11 +235,9 @@ pub trait DetectChangesMut: DetectChanges {
     where
         Self::Inner: Sized + PartialEq,
     {
-        let old = self.bypass_change_detection();
-        if *old != value {
-            let previous = mem::replace(old, value);
+        if *self.bypass_change_detection() != value {
             self.set_changed();
-            Some(previous)
+            Some(mem::replace(self.bypass_change_detection(), value))
         } else {
             None
         }
@@ -291,6 +292,8 @@ macro_rules! change_detection_mut_impl {
 
             #[inline]
             fn set_changed(&mut self) {
+                // SAFETY: the ticks belong to `self.value`
+                unsafe { self.ticks.capture_previous(ptr_of(self.value)) };
                 *self.ticks.changed = self.ticks.this_run;
             }
 
@@ -345,6 +348,7 @@ macro_rules! impl_methods {
                         changed: self.ticks.changed,
                         last_run: self.ticks.last_run,
                         this_run: self.ticks.this_run,
+                        previous: self.ticks.previous,
                     }
                 }
             }
@@ -371,7 +375,12 @@ macro_rules! impl_methods {
             /// }
             /// # bevy_ecs::system::assert_is_system(reset_positions);
             /// ```
-            pub fn map_unchanged<U: ?Sized>(self, f: impl FnOnce(&mut $target) -> &mut U) -> Mut<'a, U> {
+            ///
+            /// For components with [`TrackPrevious`](crate::component::TrackPrevious) enabled,
+            /// this captures the previous value as if the component was mutated.
+            pub fn map_unchanged<U: ?Sized>(mut self, f: impl FnOnce(&mut $target) -> &mut U) -> Mut<'a, U> {
+                // SAFETY: the ticks belong to `self.value`
+                unsafe { self.ticks.capture_previous(ptr_of(self.value)) };
                 Mut {
                     value: f(self.value),
                     ticks: self.ticks,
@@ -427,6 +436,9 @@ pub(crate) struct TicksMut<'a> {
     pub(crate) changed: &'a mut Tick,
     pub(crate) last_run: Tick,
     pub(crate) this_run: Tick,
+    /// Where to capture the previous value of a component with
+    /// [`TrackPrevious`](crate::component::TrackPrevious) enabled.
+    pub(crate) previous: Option<PreviousSlot<'a>>,
 }
 
 impl<'a> TicksMut<'a> {
@@ -443,10 +455,51 @@ impl<'a> TicksMut<'a> {
             changed: cells.changed.deref_mut(),
             last_run,
             this_run,
+            previous: None,
+        }
+    }
+
+    /// Captures the value behind `value` if these ticks belong to a component tracking previous
+    /// values, and it was not captured yet.
+    ///
+    /// # Safety
+    /// `value` must point to the value these ticks belong to.
+    #[inline]
+    pub(crate) unsafe fn capture_previous(&mut self, value: Ptr<'_>) {
+        if let Some(previous) = self.previous.take() {
+            // SAFETY: `value` is the component of `previous.entity`, which we have mutable
+            // access to, as ensured by the caller
+            unsafe {
+                previous
+                    .values
+                    .capture(previous.entity, value, *self.changed)
+            };
         }
     }
 }
 
+/// Where the previous value of a component with
+/// [`TrackPrevious`](crate::component::TrackPrevious) enabled is stored.
+#[derive(Clone, Copy)]
+pub(crate) struct PreviousSlot<'a> {
+    pub(crate) values: &'a PreviousValues,
+    pub(crate) entity: Entity,
+}
+
+impl<'a> PreviousSlot<'a> {
+    #[inline]
+    pub(crate) fn new(values: Option<&'a PreviousValues>, entity: Entity) -> Option<Self> {
+        values.map(|values| Self { values, entity })
+    }
+}
+
+/// Type-erases a reference to a possibly unsized value.
+#[inline]
+fn ptr_of<T: ?Sized>(value: &T) -> Ptr<'_> {
+    // SAFETY: the pointer comes from a reference, so it is valid for its lifetime
+    unsafe { Ptr::new(NonNull::from(value).cast()) }
+}
+
 impl<'a> From<TicksMut<'a>> for Ticks<'a> {
     fn from(ticks: TicksMut<'a>) -> Self {
         Ticks {
@@ -613,6 +666,7 @@ impl<'a, T: 'static> From<NonSendMut<'a, T>> for Mut<'a, T> {
 pub struct Ref<'a, T: ?Sized> {
     pub(crate) value: &'a T,
     pub(crate) ticks: Ticks<'a>,
+    pub(crate) previous: Option<PreviousSlot<'a>>,
 }
 
 impl<'a, T: ?Sized> Ref<'a, T> {
@@ -629,9 +683,44 @@ impl<'a, T: ?Sized> Ref<'a, T> {
         Ref {
             value: f(self.value),
             ticks: self.ticks,
+            previous: None,
         }
     }
 
+    /// Returns the value this component had before its first mutation in the current frame, or
+    /// its current value if it has not been mutated since.
+    ///
+    /// Returns `None` if the component does not have
+    /// [`TrackPrevious`](crate::component::TrackPrevious) enabled, or if this `Ref` was not
+    /// created for a component, for example with [`Ref::map`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Clone)]
+    /// #[component(track_previous)]
+    /// struct Health(u32);
+    ///
+    /// fn report_damage(query: Query<Ref<Health>>) {
+    ///     for health in &query {
+    ///         if let Some(previous) = health.previous() {
+    ///             if health.0 < previous.0 {
+    ///                 println!("lost {} health", previous.0 - health.0);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(report_damage);
+    /// ```
+    pub fn previous(&self) -> Option<&'a T>
+    where
+        T: Sized + 'static,
+    {
+        let previous = self.previous?;
+        // SAFETY: this `Ref` has shared access to the component of `previous.entity` for `'a`
+        let value = unsafe { previous.values.get::<T>(previous.entity) };
+        Some(value.unwrap_or(self.value))
+    }
+
     /// Create a new `Ref` using provided values.
     ///
     /// This is an advanced feature, `Ref`s are designed to be _created_ by
@@ -658,6 +747,7 @@ impl<'a, T: ?Sized> Ref<'a, T> {
                 last_run,
                 this_run,
             },
+            previous: None,
         }
     }
 }
@@ -712,6 +802,7 @@ impl<'a, T: ?Sized> Mut<'a, T> {
                 changed: last_changed,
                 last_run,
                 this_run,
+                previous: None,
             },
         }
     }
@@ -722,6 +813,7 @@ impl<'a, T: ?Sized> From<Mut<'a, T>> for Ref<'a, T> {
         Self {
             value: mut_ref.value,
             ticks: mut_ref.ticks.into(),
+            previous: None,
         }
     }
 }
@@ -790,6 +882,7 @@ impl<'a> MutUntyped<'a> {
                 changed: self.ticks.changed,
                 last_run: self.ticks.last_run,
                 this_run: self.ticks.this_run,
+                previous: self.ticks.previous,
             },
         }
     }
@@ -831,7 +924,15 @@ impl<'a> MutUntyped<'a> {
     /// // SAFETY: from the context it is known that `ReflectFromPtr` was made for the type of the `MutUntyped`
     /// mut_untyped.map_unchanged(|ptr| unsafe { reflect_from_ptr.as_reflect_ptr_mut(ptr) });
     /// ```
-    pub fn map_unchanged<T: ?Sized>(self, f: impl FnOnce(PtrMut<'a>) -> &'a mut T) -> Mut<'a, T> {
+    ///
+    /// Like [`Mut::map_unchanged`], this captures the previous value of components with
+    /// [`TrackPrevious`](crate::component::TrackPrevious) enabled.
+    pub fn map_unchanged<T: ?Sized>(
+        mut self,
+        f: impl FnOnce(PtrMut<'a>) -> &'a mut T,
+    ) -> Mut<'a, T> {
+        // SAFETY: the ticks belong to `self.value`
+        unsafe { self.ticks.capture_previous(self.value.as_ref()) };
         Mut {
             value: f(self.value),
             ticks: self.ticks,
@@ -876,6 +977,8 @@ impl<'a> DetectChangesMut for MutUntyped<'a> {
 
     #[inline]
     fn set_changed(&mut self) {
+        // SAFETY: the ticks belong to `self.value`
+        unsafe { self.ticks.capture_previous(self.value.as_ref()) };
         *self.ticks.changed = self.ticks.this_run;
     }
 
@@ -1018,6 +1121,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            previous: None,
         };
         let mut res = R {};
         let res_mut = ResMut {
@@ -1063,6 +1167,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            previous: None,
         };
         let mut res = R {};
         let non_send_mut = NonSendMut {
@@ -1093,6 +1198,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run,
             this_run,
+            previous: None,
         };
 
         let mut outer = Outer(0);
@@ -1152,6 +1258,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run,
             this_run,
+            previous: None,
         };
 
         let mut value: i32 = 5;
@@ -3,13 +3,15 @@
 use crate::{
     self as bevy_ecs,
     change_detection::MAX_CHANGE_AGE,
-    storage::{SparseSetIndex, Storages},
+    entity::{Entity, EntityMapper},
+    relationship::{Relationship, RelationshipTarget},
+    storage::{PreviousValues, SparseSetIndex, Storages},
     system::{Local, Resource, SystemParam},
-    world::{FromWorld, World},
+    world::{DeferredWorld, FromWorld, World},
     TypeIdMap,
 };
 pub use bevy_ecs_macros::Component;
-use bevy_ptr::{OwningPtr, UnsafeCellDeref};
+use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
 use std::cell::UnsafeCell;
 use std::{
     alloc::Layout,
@@ -89,6 +91,23 @@ use std::{
 /// [`Table`]: crate::storage::Table
 /// [`SparseSet`]: crate::storage::SparseSet
 ///
+/// # Tracking previous values
+///
+/// Components implementing [`Clone`] can keep the value they had before their first mutation in
+/// the current frame, which is read with [`Previous`](crate::query::Previous) or
+/// [`Ref::previous`](crate::change_detection::Ref::previous).
+/// This is enabled with the `#[component(track_previous)]` attribute:
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// #
+/// #[derive(Component, Clone)]
+/// #[component(track_previous)]
+/// struct Health(u32);
+/// ```
+///
+/// See [`TrackPrevious`] for details.
+///
 /// # Implementing the trait for foreign types
 ///
 /// As a consequence of the [orphan rule], it is not possible to separate into two different crates the implementation of `Component` from the definition of a type.
@@ -150,6 +169,28 @@ pub trait Component: Send + Sync + 'static {
     /// A marker type indicating the storage type used for this component.
     /// This must be either [`TableStorage`] or [`SparseStorage`].
     type Storage: ComponentStorage;
+
+    /// Called when this component is first registered in a [`World`](crate::world::World),
+    /// to set up hooks that should always run for it.
+    ///
+    /// This is used by [relationships](crate::relationship) to keep both sides of the
+    /// relationship in sync.
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {}
+
+    /// Returns how this component is copied when its entity is cloned with
+    /// [`World::clone_entity`](crate::world::World::clone_entity).
+    ///
+    /// [Relationships](crate::relationship) use this to keep both sides in sync on the clones.
+    fn clone_handler() -> ComponentCloneHandler {
+        ComponentCloneHandler::Default
+    }
+
+    /// Returns how the previous values of this component are tracked, if they are.
+    ///
+    /// See [`TrackPrevious`].
+    fn track_previous() -> Option<TrackPrevious> {
+        None
+    }
 }
 
 /// Marker type for components stored in a [`Table`](crate::storage::Table).
@@ -201,11 +242,289 @@ pub enum StorageType {
     SparseSet,
 }
 
+/// The type used for [`Component`] lifecycle hooks such as `on_add`, `on_insert`, `on_replace` or `on_remove`.
+///
+/// Hooks receive a [`DeferredWorld`], the [`Entity`] being modified and the [`ComponentId`]
+/// of the component the hook was registered for.
+pub type ComponentHook = for<'w> fn(DeferredWorld<'w>, Entity, ComponentId);
+
+/// Lifecycle hooks for a given [`Component`], stored in its [`ComponentInfo`].
+///
+/// Hooks are functions that run synchronously whenever a specific component is added to,
+/// inserted on, or removed from an entity. They are used to maintain invariants, such as
+/// keeping an index structure in sync with the components that are present in the [`World`].
+///
+/// Hooks are registered through [`World::register_component_hooks`] and must be registered
+/// before the component is used by any entity. Each hook can only be set once per component.
+///
+/// Hooks run with a [`DeferredWorld`]: they may read and mutate existing components and
+/// resources, but structural changes (spawning, despawning, inserting or removing components)
+/// must be queued with [`DeferredWorld::commands`]. Those commands are applied the next time the
+/// world flushes its commands (see [`World::flush_commands`]).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_utils::HashSet;
+/// #[derive(Component)]
+/// struct MyTrackedComponent;
+///
+/// #[derive(Resource, Default)]
+/// struct TrackedEntities(HashSet<Entity>);
+///
+/// let mut world = World::new();
+/// world.init_resource::<TrackedEntities>();
+///
+/// world
+///     .register_component_hooks::<MyTrackedComponent>()
+///     .on_add(|mut world, entity, _| {
+///         world.resource_mut::<TrackedEntities>().0.insert(entity);
+///     })
+///     .on_remove(|mut world, entity, _| {
+///         world.resource_mut::<TrackedEntities>().0.remove(&entity);
+///     });
+///
+/// let entity = world.spawn(MyTrackedComponent).id();
+/// assert!(world.resource::<TrackedEntities>().0.contains(&entity));
+///
+/// world.despawn(entity);
+/// assert!(world.resource::<TrackedEntities>().0.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ComponentHooks {
+    pub(crate) on_add: Option<ComponentHook>,
+    pub(crate) on_insert: Option<ComponentHook>,
+    pub(crate) on_replace: Option<ComponentHook>,
+    pub(crate) on_remove: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Register a [`ComponentHook`] that will be run when this component is added to an entity.
+    /// An `on_add` hook will always run before `on_insert` hooks. Spawning an entity counts as
+    /// adding all of its components.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_add` hook
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_add(hook)
+            .expect("Component already has an on_add hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when this component is added (with `.insert`)
+    /// or replaced.
+    ///
+    /// An `on_insert` hook always runs after any `on_add` hooks (if the entity didn't already have the component).
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_insert` hook
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_insert(hook)
+            .expect("Component already has an on_insert hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when the value of this component is about to be
+    /// dropped: either because a new value is inserted over it, or because it is removed.
+    ///
+    /// The hook runs before the old value is overwritten or removed, so it can still be read.
+    /// This makes it the counterpart of `on_insert` for keeping derived data in sync.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_replace` hook
+    pub fn on_replace(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_replace(hook)
+            .expect("Component already has an on_replace hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when this component is removed from an entity.
+    /// Despawning an entity counts as removing all of its components.
+    ///
+    /// The hook runs before the component is removed, so its value can still be read.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_remove` hook
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_remove(hook)
+            .expect("Component already has an on_remove hook")
+    }
+
+    /// Returns `true` if no hooks are registered.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.on_add.is_none()
+            && self.on_insert.is_none()
+            && self.on_replace.is_none()
+            && self.on_remove.is_none()
+    }
+
+    /// Fallible version of [`Self::on_add`].
+    /// Returns `None` if the component already has an `on_add` hook.
+    pub fn try_on_add(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_add.is_some() {
+            return None;
+        }
+        self.on_add = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_insert`].
+    /// Returns `None` if the component already has an `on_insert` hook.
+    pub fn try_on_insert(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_insert.is_some() {
+            return None;
+        }
+        self.on_insert = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_replace`].
+    /// Returns `None` if the component already has an `on_replace` hook.
+    pub fn try_on_replace(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_replace.is_some() {
+            return None;
+        }
+        self.on_replace = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_remove`].
+    /// Returns `None` if the component already has an `on_remove` hook.
+    pub fn try_on_remove(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_remove.is_some() {
+            return None;
+        }
+        self.on_remove = Some(hook);
+        Some(self)
+    }
+}
+
+/// A function that copies a component from the `source` entity to the `target` entity.
+///
+/// Entity references held by the component should be mapped with the [`EntityMapper`],
+/// which leaves the entities that are not being cloned unchanged.
+pub type ComponentCloneFn = fn(&mut World, source: Entity, target: Entity, &mut EntityMapper);
+
+/// Describes how a component is copied when an entity is cloned with
+/// [`World::clone_entity`](crate::world::World::clone_entity).
+///
+/// The handler of a component type can be changed with [`World::set_component_clone_handler`].
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ComponentCloneHandler {
+    /// Clones the component through reflection, if its type is registered with `ReflectComponent`
+    /// in the `AppTypeRegistry` resource. Its entities are mapped if the type also registered
+    /// `ReflectMapEntities`. Otherwise, the component is not cloned.
+    #[default]
+    Default,
+    /// The component is never cloned.
+    Ignore,
+    /// The component is cloned by the given function.
+    Custom(ComponentCloneFn),
+    /// The component lists entities related to its entity, and is not cloned itself.
+    ///
+    /// When cloning recursively, the listed entities are cloned too.
+    RelationshipTarget(fn(&World, Entity) -> Vec<Entity>),
+}
+
+impl ComponentCloneHandler {
+    /// Clones the component with its [`Clone`] implementation, without reflection.
+    pub fn from_clone<C: Component + Clone>() -> Self {
+        Self::Custom(|world, source, target, _| {
+            if let Some(component) = world.get::<C>(source).cloned() {
+                world.entity_mut(target).insert(component);
+            }
+        })
+    }
+
+    /// Clones the component with its [`Clone`] implementation, then maps the entities it references
+    /// with its [`MapEntities`](crate::entity::MapEntities) implementation.
+    pub fn from_clone_and_map<C: Component + Clone + crate::entity::MapEntities>() -> Self {
+        Self::Custom(|world, source, target, entity_mapper| {
+            if let Some(mut component) = world.get::<C>(source).cloned() {
+                component.map_entities(entity_mapper);
+                world.entity_mut(target).insert(component);
+            }
+        })
+    }
+
+    /// The handler of [`Relationship`] components: the clone points at the clone of the
+    /// target entity if it was cloned too, or at the same target otherwise.
+    pub fn relationship<R: Relationship>() -> Self {
+        Self::Custom(|world, source, target, entity_mapper| {
+            if let Some(relationship) = world.get::<R>(source) {
+                let related = entity_mapper.get_or_reserve(relationship.get());
+                world.entity_mut(target).insert(R::from(related));
+            }
+        })
+    }
+
+    /// The handler of [`RelationshipTarget`] components, which are rebuilt from the
+    /// relationships of the cloned sources.
+    pub fn relationship_target<T: RelationshipTarget>() -> Self {
+        Self::RelationshipTarget(|world, entity| {
+            world
+                .get::<T>(entity)
+                .map(|target| target.iter().collect())
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Makes a component keep a clone of its value from before its first mutation in each frame.
+///
+/// The value is captured when the component is first mutably dereferenced through a [`Mut`]
+/// (including [`Mut::map_unchanged`]) since the last call to [`World::clear_trackers`], which
+/// `App` calls at the end of every update.
+/// [`DetectChangesMut::bypass_change_detection`] does not capture the value.
+///
+/// The captured value can be read with the [`Previous`](crate::query::Previous) query item or
+/// with [`Ref::previous`](crate::change_detection::Ref::previous), and
+/// [`World::suppress_unchanged_writes`] resets the change ticks of values that were written
+/// without actually changing.
+/// Systems reading previous values should run after the systems that write them.
+///
+/// Tracking is enabled with `#[component(track_previous)]`, or with [`World::track_previous`]
+/// for components defined in other crates.
+///
+/// [`Mut`]: crate::change_detection::Mut
+/// [`Mut::map_unchanged`]: crate::change_detection::Mut::map_unchanged
+/// [`DetectChangesMut::bypass_change_detection`]: crate::change_detection::DetectChangesMut::bypass_change_detection
+#[derive(Debug, Clone, Copy)]
+pub struct TrackPrevious {
+    clone: unsafe fn(Ptr<'_>) -> Box<dyn Any + Send + Sync>,
+}
+
+impl TrackPrevious {
+    /// Tracks the previous values of `C` using its [`Clone`] implementation.
+    pub fn new<C: Component + Clone>() -> Self {
+        Self {
+            clone: clone_previous::<C>,
+        }
+    }
+
+    /// Clones the value behind `value`.
+    ///
+    /// # Safety
+    /// `value` must point to a valid value of the component type this was created for.
+    #[inline]
+    pub(crate) unsafe fn clone_value(&self, value: Ptr<'_>) -> Box<dyn Any + Send + Sync> {
+        (self.clone)(value)
+    }
+}
+
+/// # Safety
+/// `value` must point to a valid `C`.
+unsafe fn clone_previous<C: Component + Clone>(value: Ptr<'_>) -> Box<dyn Any + Send + Sync> {
+    Box::new(value.deref::<C>().clone())
+}
+
 /// Stores metadata for a type of component or resource stored in a specific [`World`].
 #[derive(Debug)]
 pub struct ComponentInfo {
     id: ComponentId,
     descriptor: ComponentDescriptor,
+    hooks: ComponentHooks,
+    clone_handler: ComponentCloneHandler,
 }
 
 impl ComponentInfo {
@@ -261,7 +580,24 @@ impl ComponentInfo {
 
     /// Create a new [`ComponentInfo`].
     pub(crate) fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
-        ComponentInfo { id, descriptor }
+        ComponentInfo {
+            id,
+            descriptor,
+            hooks: ComponentHooks::default(),
+            clone_handler: ComponentCloneHandler::Default,
+        }
+    }
+
+    /// Returns the [`ComponentHooks`] registered for this component.
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    /// Returns how this component is copied when an entity is cloned.
+    #[inline]
+    pub fn clone_handler(&self) -> ComponentCloneHandler {
+        self.clone_handler
     }
 }
 
@@ -452,7 +788,19 @@ impl Components {
             ..
         } = self;
         let index = indices.entry(type_id).or_insert_with(|| {
-            Components::init_component_inner(components, storages, ComponentDescriptor::new::<T>())
+            let index = Components::init_component_inner(
+                components,
+                storages,
+                ComponentDescriptor::new::<T>(),
+            );
+            T::register_component_hooks(&mut components[index].hooks);
+            components[index].clone_handler = T::clone_handler();
+            if let Some(track_previous) = T::track_previous() {
+                storages
+                    .previous_values
+                    .insert(ComponentId(index), PreviousValues::new(track_previous));
+            }
+            index
         });
         ComponentId(*index)
     }
@@ -507,6 +855,19 @@ impl Components {
         self.components.get(id.0)
     }
 
+    /// Returns a mutable reference to the [`ComponentHooks`] of the given component, if it exists.
+    #[inline]
+    pub(crate) fn get_hooks_mut(&mut self, id: ComponentId) -> Option<&mut ComponentHooks> {
+        self.components.get_mut(id.0).map(|info| &mut info.hooks)
+    }
+
+    #[inline]
+    pub(crate) fn set_clone_handler(&mut self, id: ComponentId, handler: ComponentCloneHandler) {
+        if let Some(info) = self.components.get_mut(id.0) {
+            info.clone_handler = handler;
+        }
+    }
+
     /// Returns the name associated with the given component.
     ///
     /// This will return an incorrect result if `id` did not come from the same world as `self`. It may return `None` or a garbage value.
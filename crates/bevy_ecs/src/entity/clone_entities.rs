@@ -0,0 +1,433 @@
+use std::{any::TypeId, collections::VecDeque};
+
+use bevy_utils::HashSet;
+
+use crate::{
+    component::{Component, ComponentCloneHandler, ComponentId},
+    entity::{Entity, EntityMap},
+    world::World,
+};
+
+/// Configures how entities are cloned by [`World::clone_entity`] and
+/// [`EntityCommands::clone_and_spawn`](crate::system::EntityCommands::clone_and_spawn).
+///
+/// Each component is copied according to its [`ComponentCloneHandler`]:
+/// - Components with [`ComponentCloneHandler::Custom`] handlers, such as the ones created with
+///   [`ComponentCloneHandler::from_clone`], are copied by their handler.
+/// - Other components are copied through reflection, if they are registered with `ReflectComponent`
+///   in the `AppTypeRegistry` resource. Components that cannot be copied are skipped.
+///
+/// Entity references held by the cloned components are remapped to point at the clones of the entities
+/// that were cloned in the same operation. References to other entities are left unchanged.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::component::ComponentCloneHandler;
+/// # use bevy_ecs::entity::EntityCloneBuilder;
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// struct Health(u32);
+///
+/// #[derive(Component, Clone)]
+/// struct Player;
+///
+/// let mut world = World::new();
+/// world.set_component_clone_handler::<Health>(ComponentCloneHandler::from_clone::<Health>());
+/// world.set_component_clone_handler::<Player>(ComponentCloneHandler::from_clone::<Player>());
+///
+/// let source = world.spawn((Health(10), Player)).id();
+/// let clone = EntityCloneBuilder::new()
+///     .deny::<Player>()
+///     .clone_entity(&mut world, source);
+///
+/// assert_eq!(world.get::<Health>(clone), Some(&Health(10)));
+/// assert!(world.get::<Player>(clone).is_none());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct EntityCloneBuilder {
+    recursive: bool,
+    denied: HashSet<TypeId>,
+}
+
+impl EntityCloneBuilder {
+    /// Creates a builder that clones every component of a single entity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the entities related to the cloned entity through a
+    /// [`RelationshipTarget`](crate::relationship::RelationshipTarget), such as its children,
+    /// are cloned too, recursively.
+    ///
+    /// The cloned sources point at the clone instead of the original entity, in the same order.
+    pub fn recursive(&mut self, recursive: bool) -> &mut Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Prevents the component `C` from being cloned.
+    pub fn deny<C: Component>(&mut self) -> &mut Self {
+        self.denied.insert(TypeId::of::<C>());
+        self
+    }
+
+    /// Spawns a clone of the `source` entity and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` does not exist.
+    pub fn clone_entity(&self, world: &mut World, source: Entity) -> Entity {
+        let target = world.spawn_empty().id();
+        self.clone_entity_into(world, source, target);
+        target
+    }
+
+    /// Clones the components of the `source` entity into the existing `target` entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or `target` do not exist.
+    pub fn clone_entity_into(&self, world: &mut World, source: Entity, target: Entity) {
+        let mut entity_map = EntityMap::default();
+        entity_map.insert(source, target);
+        let mut clones = vec![(source, target)];
+
+        if self.recursive {
+            let mut queue = VecDeque::from([source]);
+            while let Some(entity) = queue.pop_front() {
+                for related in self.related_entities(world, entity) {
+                    if entity_map.get(related).is_none() {
+                        let clone = world.spawn_empty().id();
+                        entity_map.insert(related, clone);
+                        clones.push((related, clone));
+                        queue.push_back(related);
+                    }
+                }
+            }
+        }
+
+        for (source, target) in clones {
+            self.clone_components(world, &mut entity_map, source, target);
+        }
+    }
+
+    fn cloned_components(&self, world: &World, entity: Entity) -> Vec<ComponentId> {
+        let entity = world.entity(entity);
+        entity
+            .archetype()
+            .components()
+            .filter(|&id| {
+                let type_id = world
+                    .components()
+                    .get_info(id)
+                    .and_then(|info| info.type_id());
+                !matches!(type_id, Some(type_id) if self.denied.contains(&type_id))
+            })
+            .collect()
+    }
+
+    fn related_entities(&self, world: &World, entity: Entity) -> Vec<Entity> {
+        self.cloned_components(world, entity)
+            .into_iter()
+            .filter_map(
+                |id| match world.components().get_info(id)?.clone_handler() {
+                    ComponentCloneHandler::RelationshipTarget(sources) => {
+                        Some(sources(world, entity))
+                    }
+                    _ => None,
+                },
+            )
+            .flatten()
+            .collect()
+    }
+
+    fn clone_components(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        source: Entity,
+        target: Entity,
+    ) {
+        for id in self.cloned_components(world, source) {
+            let Some(info) = world.components().get_info(id) else {
+                continue;
+            };
+            match info.clone_handler() {
+                ComponentCloneHandler::Ignore | ComponentCloneHandler::RelationshipTarget(_) => {}
+                ComponentCloneHandler::Custom(clone) => {
+                    entity_map.same_world_scope(|mapper| clone(world, source, target, mapper));
+                }
+                #[cfg(feature = "bevy_reflect")]
+                ComponentCloneHandler::Default => {
+                    if let Some(type_id) = info.type_id() {
+                        clone_reflect(world, entity_map, type_id, source, target);
+                    }
+                }
+                #[cfg(not(feature = "bevy_reflect"))]
+                ComponentCloneHandler::Default => {}
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn clone_reflect(
+    world: &mut World,
+    entity_map: &mut EntityMap,
+    type_id: TypeId,
+    source: Entity,
+    target: Entity,
+) {
+    use crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities};
+
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return;
+    };
+    let registry = registry.read();
+    let Some(registration) = registry.get(type_id) else {
+        return;
+    };
+    let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+        return;
+    };
+    let Some(value) = reflect_component
+        .reflect(world.entity(source))
+        .map(|component| component.clone_value())
+    else {
+        return;
+    };
+
+    // `ReflectMapEntities` needs a value of the concrete component type.
+    let mut component = reflect_component.from_world(world);
+    component.apply(&*value);
+    if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+        entity_map.same_world_scope(|mapper| {
+            map_entities.map_reflect_entities_with_mapper(&mut *component, mapper);
+        });
+    }
+    reflect_component.insert(&mut world.entity_mut(target), &*component);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntityCloneBuilder;
+    use crate as bevy_ecs;
+    use crate::{
+        component::{Component, ComponentCloneHandler},
+        entity::{Entity, EntityMapper, MapEntities},
+        relationship::RelationshipTarget,
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct A(u32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct B;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct NotCloned;
+
+    #[derive(Component, Debug, PartialEq, Eq)]
+    #[relationship(relationship_target = Owns)]
+    struct OwnedBy(Entity);
+
+    #[derive(Component, Debug)]
+    #[relationship_target(relationship = OwnedBy, despawn_behavior = "Remove")]
+    struct Owns(Vec<Entity>);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            self.0 = entity_mapper.get_or_reserve(self.0);
+        }
+    }
+
+    fn world_with_clone_handlers() -> World {
+        let mut world = World::new();
+        world.set_component_clone_handler::<A>(ComponentCloneHandler::from_clone::<A>());
+        world.set_component_clone_handler::<B>(ComponentCloneHandler::from_clone::<B>());
+        let map_target = ComponentCloneHandler::from_clone_and_map::<Target>();
+        world.set_component_clone_handler::<Target>(map_target);
+        world
+    }
+
+    #[test]
+    fn clone_with_clone_handler() {
+        let mut world = world_with_clone_handlers();
+        let source = world.spawn((A(5), B, NotCloned)).id();
+
+        let clone = world.clone_entity(source);
+
+        assert_ne!(clone, source);
+        assert_eq!(world.get::<A>(clone), Some(&A(5)));
+        assert_eq!(world.get::<B>(clone), Some(&B));
+        assert!(world.get::<NotCloned>(clone).is_none());
+        assert_eq!(world.get::<A>(source), Some(&A(5)));
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn clone_with_reflection() {
+        use crate::reflect::{AppTypeRegistry, ReflectComponent};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Reflected {
+            value: u32,
+            name: String,
+        }
+
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Reflected>();
+        world.insert_resource(registry);
+
+        let source = world
+            .spawn((
+                Reflected {
+                    value: 3,
+                    name: "reflected".to_string(),
+                },
+                NotCloned,
+            ))
+            .id();
+        let clone = world.clone_entity(source);
+
+        assert_eq!(
+            world.get::<Reflected>(clone),
+            Some(&Reflected {
+                value: 3,
+                name: "reflected".to_string(),
+            })
+        );
+        assert!(world.get::<NotCloned>(clone).is_none());
+    }
+
+    #[test]
+    fn clone_with_denied_components() {
+        let mut world = world_with_clone_handlers();
+        let source = world.spawn((A(1), B)).id();
+
+        let clone = EntityCloneBuilder::new()
+            .deny::<B>()
+            .clone_entity(&mut world, source);
+
+        assert_eq!(world.get::<A>(clone), Some(&A(1)));
+        assert!(world.get::<B>(clone).is_none());
+    }
+
+    #[test]
+    fn clone_ignored_component() {
+        let mut world = world_with_clone_handlers();
+        world.set_component_clone_handler::<B>(ComponentCloneHandler::Ignore);
+        let source = world.spawn((A(1), B)).id();
+
+        let clone = world.clone_entity(source);
+
+        assert_eq!(world.get::<A>(clone), Some(&A(1)));
+        assert!(world.get::<B>(clone).is_none());
+    }
+
+    #[test]
+    fn clone_relationship_source() {
+        let mut world = world_with_clone_handlers();
+        let owner = world.spawn_empty().id();
+        let source = world.spawn((A(1), OwnedBy(owner))).id();
+
+        let clone = world.clone_entity(source);
+
+        assert_eq!(world.get::<OwnedBy>(clone), Some(&OwnedBy(owner)));
+        let owned: Vec<_> = world.get::<Owns>(owner).unwrap().iter().collect();
+        assert_eq!(owned, vec![source, clone]);
+    }
+
+    #[test]
+    fn clone_without_recursion_skips_relationship_target() {
+        let mut world = world_with_clone_handlers();
+        let owner = world.spawn(A(0)).id();
+        let owned = world.spawn(OwnedBy(owner)).id();
+
+        let clone = world.clone_entity(owner);
+
+        assert!(world.get::<Owns>(clone).is_none());
+        assert_eq!(world.get::<OwnedBy>(owned), Some(&OwnedBy(owner)));
+    }
+
+    #[test]
+    fn clone_recursive() {
+        let mut world = world_with_clone_handlers();
+        let root = world.spawn(A(0)).id();
+        let first = world.spawn((A(1), OwnedBy(root))).id();
+        let second = world.spawn((A(2), OwnedBy(root))).id();
+        let grandchild = world.spawn((A(3), OwnedBy(first))).id();
+
+        let clone = EntityCloneBuilder::new()
+            .recursive(true)
+            .clone_entity(&mut world, root);
+
+        let cloned_owned: Vec<_> = world.get::<Owns>(clone).unwrap().iter().collect();
+        assert_eq!(cloned_owned.len(), 2);
+        assert!(!cloned_owned.contains(&first) && !cloned_owned.contains(&second));
+        assert_eq!(world.get::<A>(cloned_owned[0]), Some(&A(1)));
+        assert_eq!(world.get::<A>(cloned_owned[1]), Some(&A(2)));
+
+        let cloned_grandchildren: Vec<_> =
+            world.get::<Owns>(cloned_owned[0]).unwrap().iter().collect();
+        assert_eq!(cloned_grandchildren.len(), 1);
+        assert_ne!(cloned_grandchildren[0], grandchild);
+        assert_eq!(world.get::<A>(cloned_grandchildren[0]), Some(&A(3)));
+
+        let owned: Vec<_> = world.get::<Owns>(root).unwrap().iter().collect();
+        assert_eq!(owned, vec![first, second]);
+    }
+
+    #[test]
+    fn clone_recursive_maps_entities() {
+        let mut world = world_with_clone_handlers();
+        let outside = world.spawn_empty().id();
+        let root = world.spawn_empty().id();
+        let first = world.spawn(OwnedBy(root)).id();
+        world.spawn((OwnedBy(root), Target(first)));
+        world.entity_mut(first).insert(Target(outside));
+
+        let clone = EntityCloneBuilder::new()
+            .recursive(true)
+            .clone_entity(&mut world, root);
+
+        let cloned_owned: Vec<_> = world.get::<Owns>(clone).unwrap().iter().collect();
+        assert_eq!(world.get::<Target>(cloned_owned[0]), Some(&Target(outside)));
+        assert_eq!(
+            world.get::<Target>(cloned_owned[1]),
+            Some(&Target(cloned_owned[0]))
+        );
+    }
+
+    #[test]
+    fn clone_with_commands() {
+        let mut world = world_with_clone_handlers();
+        let source = world.spawn((A(1), B)).id();
+        world.spawn(OwnedBy(source));
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let clone = commands.entity(source).clone_and_spawn().insert(A(2)).id();
+        let deep_clone = commands
+            .entity(source)
+            .clone_and_spawn_with(|builder| {
+                builder.recursive(true).deny::<B>();
+            })
+            .id();
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<A>(clone), Some(&A(2)));
+        assert_eq!(world.get::<B>(clone), Some(&B));
+        assert!(world.get::<Owns>(clone).is_none());
+        assert_eq!(world.get::<A>(deep_clone), Some(&A(1)));
+        assert!(world.get::<B>(deep_clone).is_none());
+        assert_eq!(world.get::<Owns>(deep_clone).unwrap().len(), 1);
+    }
+}
@@ -127,6 +127,18 @@ impl EntityMap {
         mapper.finish(world);
         result
     }
+
+    /// Creates an [`EntityMapper`] from this [`EntityMap`] for mapping references between entities of the same
+    /// [`World`], then calls the provided function with it. Entities that are absent from the map are not reserved
+    /// but left unchanged, since they still point at valid entities. This is used when cloning entities.
+    pub fn same_world_scope<R>(&mut self, f: impl FnOnce(&mut EntityMapper) -> R) -> R {
+        let mut mapper = EntityMapper {
+            map: self,
+            dead_start: None,
+            generations: 0,
+        };
+        f(&mut mapper)
+    }
 }
 
 /// A wrapper for [`EntityMap`], augmenting it with the ability to allocate new [`Entity`] references in a destination
@@ -138,23 +150,29 @@ impl EntityMap {
 pub struct EntityMapper<'m> {
     /// The wrapped [`EntityMap`].
     map: &'m mut EntityMap,
-    /// A base [`Entity`] used to allocate new references.
-    dead_start: Entity,
+    /// A base [`Entity`] used to allocate new references, or `None` when mapping within the same world.
+    dead_start: Option<Entity>,
     /// The number of generations this mapper has allocated thus far.
     generations: u32,
 }
 
 impl<'m> EntityMapper<'m> {
     /// Returns the corresponding mapped entity or reserves a new dead entity ID if it is absent.
+    ///
+    /// Absent entities are returned unchanged when mapping within the same world, see
+    /// [`EntityMap::same_world_scope`].
     pub fn get_or_reserve(&mut self, entity: Entity) -> Entity {
         if let Some(mapped) = self.map.get(entity) {
             return mapped;
         }
+        let Some(dead_start) = self.dead_start else {
+            return entity;
+        };
 
         // this new entity reference is specifically designed to never represent any living entity
         let new = Entity {
-            generation: self.dead_start.generation + self.generations,
-            index: self.dead_start.index,
+            generation: dead_start.generation + self.generations,
+            index: dead_start.index,
         };
         self.generations += 1;
 
@@ -178,7 +196,7 @@ impl<'m> EntityMapper<'m> {
         Self {
             map,
             // SAFETY: Entities data is kept in a valid state via `EntityMap::world_scope`
-            dead_start: unsafe { world.entities_mut().alloc() },
+            dead_start: Some(unsafe { world.entities_mut().alloc() }),
             generations: 0,
         }
     }
@@ -190,8 +208,10 @@ impl<'m> EntityMapper<'m> {
     fn finish(self, world: &mut World) {
         // SAFETY: Entities data is kept in a valid state via `EntityMap::world_scope`
         let entities = unsafe { world.entities_mut() };
-        assert!(entities.free(self.dead_start).is_some());
-        assert!(entities.reserve_generations(self.dead_start.index, self.generations));
+        if let Some(dead_start) = self.dead_start {
+            assert!(entities.free(dead_start).is_some());
+            assert!(entities.reserve_generations(dead_start.index, self.generations));
+        }
     }
 }
 
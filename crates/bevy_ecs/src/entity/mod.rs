@@ -35,8 +35,10 @@
 //! [`World::despawn`]: crate::world::World::despawn
 //! [`EntityMut::insert`]: crate::world::EntityMut::insert
 //! [`EntityMut::remove`]: crate::world::EntityMut::remove
+mod clone_entities;
 mod map_entities;
 
+pub use clone_entities::*;
 pub use map_entities::*;
 
 use crate::{
@@ -44,7 +46,7 @@ use crate::{
     storage::{SparseSetIndex, TableId, TableRow},
 };
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, fmt, mem, sync::atomic::Ordering};
+use std::{convert::TryFrom, fmt, mem, ops::Range, sync::atomic::Ordering};
 
 #[cfg(target_has_atomic = "64")]
 use std::sync::atomic::AtomicI64 as AtomicIdCursor;
@@ -127,6 +129,31 @@ pub(crate) enum AllocAtWithoutReplacement {
     ExistsWithWrongGeneration,
 }
 
+/// How [`Entities`] picks which freed index to reuse next.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EntityAllocationMode {
+    /// Reuse the most recently freed index first.
+    ///
+    /// This is the fastest mode, but the ids handed out depend on the order in which entities
+    /// were despawned.
+    #[default]
+    Lifo,
+    /// Reuse the lowest free index first.
+    ///
+    /// The ids handed out only depend on which indices are free, not on the order they were
+    /// freed in, so worlds that run the same spawns and despawns (in any order within a batch)
+    /// always agree on entity ids. Freeing an entity costs `O(n)` in the number of free indices.
+    Deterministic,
+}
+
+/// The generations and free list of [`Entities`], as captured by [`Entities::allocator_state`].
+#[derive(Clone, Debug)]
+pub(crate) struct EntitiesAllocatorState {
+    generations: Vec<u32>,
+    pending: Vec<u32>,
+    len: u32,
+}
+
 impl Entity {
     #[cfg(test)]
     pub(crate) const fn new(index: u32, generation: u32) -> Entity {
@@ -360,6 +387,9 @@ pub struct Entities {
     free_cursor: AtomicIdCursor,
     /// Stores the number of free entities for [`len`](Entities::len)
     len: u32,
+    /// In [`EntityAllocationMode::Deterministic`], the freelist part of `pending` is kept sorted
+    /// in descending order, so that the lowest free index is handed out first.
+    allocation_mode: EntityAllocationMode,
 }
 
 impl Entities {
@@ -369,7 +399,69 @@ impl Entities {
             pending: Vec::new(),
             free_cursor: AtomicIdCursor::new(0),
             len: 0,
+            allocation_mode: EntityAllocationMode::Lifo,
+        }
+    }
+
+    /// Returns the [`EntityAllocationMode`] used to reuse freed indices.
+    #[inline]
+    pub fn allocation_mode(&self) -> EntityAllocationMode {
+        self.allocation_mode
+    }
+
+    /// Sets the [`EntityAllocationMode`] used to reuse freed indices.
+    ///
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    pub fn set_allocation_mode(&mut self, mode: EntityAllocationMode) {
+        self.verify_flushed();
+        self.allocation_mode = mode;
+        if mode == EntityAllocationMode::Deterministic {
+            self.pending.sort_unstable_by(|a, b| b.cmp(a));
+        }
+    }
+
+    /// Reserves a contiguous block of `count` new entity indices, which this allocator will never
+    /// hand out on its own.
+    ///
+    /// The reserved indices are counted as allocated, but do not refer to live entities until they
+    /// are spawned explicitly, for example with [`World::get_or_spawn`] and
+    /// [`Entity::from_raw`]. This lets a server hand out blocks of ids to clients without
+    /// collisions. Once an entity from the block is despawned, its index is recycled as usual.
+    ///
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    ///
+    /// [`World::get_or_spawn`]: crate::world::World::get_or_spawn
+    pub fn reserve_range(&mut self, count: u32) -> Range<u32> {
+        self.verify_flushed();
+        let start = u32::try_from(self.meta.len()).expect("too many entities");
+        let end = start.checked_add(count).expect("too many entities");
+        self.meta.resize(end as usize, EntityMeta::EMPTY);
+        self.len += count;
+        start..end
+    }
+
+    /// Adds the indices `start..end`, which are all beyond the current freelist, to the freelist.
+    fn extend_pending(&mut self, start: u32, end: u32) {
+        match self.allocation_mode {
+            EntityAllocationMode::Lifo => self.pending.extend(start..end),
+            EntityAllocationMode::Deterministic => {
+                self.pending.splice(0..0, (start..end).rev());
+            }
+        }
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+    }
+
+    /// Removes the freelist entry at `position`.
+    fn remove_pending(&mut self, position: usize) {
+        match self.allocation_mode {
+            EntityAllocationMode::Lifo => {
+                self.pending.swap_remove(position);
+            }
+            EntityAllocationMode::Deterministic => {
+                self.pending.remove(position);
+            }
         }
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
     }
 
     /// Reserve entity IDs concurrently.
@@ -480,17 +572,13 @@ impl Entities {
         self.verify_flushed();
 
         let loc = if entity.index as usize >= self.meta.len() {
-            self.pending.extend((self.meta.len() as u32)..entity.index);
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            self.extend_pending(self.meta.len() as u32, entity.index);
             self.meta
                 .resize(entity.index as usize + 1, EntityMeta::EMPTY);
             self.len += 1;
             None
         } else if let Some(index) = self.pending.iter().position(|item| *item == entity.index) {
-            self.pending.swap_remove(index);
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            self.remove_pending(index);
             self.len += 1;
             None
         } else {
@@ -515,17 +603,13 @@ impl Entities {
         self.verify_flushed();
 
         let result = if entity.index as usize >= self.meta.len() {
-            self.pending.extend((self.meta.len() as u32)..entity.index);
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            self.extend_pending(self.meta.len() as u32, entity.index);
             self.meta
                 .resize(entity.index as usize + 1, EntityMeta::EMPTY);
             self.len += 1;
             AllocAtWithoutReplacement::DidNotExist
         } else if let Some(index) = self.pending.iter().position(|item| *item == entity.index) {
-            self.pending.swap_remove(index);
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            self.remove_pending(index);
             self.len += 1;
             AllocAtWithoutReplacement::DidNotExist
         } else {
@@ -557,7 +641,13 @@ impl Entities {
 
         let loc = mem::replace(&mut meta.location, EntityMeta::EMPTY.location);
 
-        self.pending.push(entity.index);
+        match self.allocation_mode {
+            EntityAllocationMode::Lifo => self.pending.push(entity.index),
+            EntityAllocationMode::Deterministic => {
+                let position = self.pending.partition_point(|&index| index > entity.index);
+                self.pending.insert(position, entity.index);
+            }
+        }
 
         let new_free_cursor = self.pending.len() as IdCursor;
         *self.free_cursor.get_mut() = new_free_cursor;
@@ -586,6 +676,38 @@ impl Entities {
             .map_or(false, |e| e.generation() == entity.generation)
     }
 
+    /// Captures the generations and free list of the allocator, so that later allocations can be
+    /// replayed exactly by [`restore_allocator_state`](Entities::restore_allocator_state).
+    ///
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    pub(crate) fn allocator_state(&mut self) -> EntitiesAllocatorState {
+        self.verify_flushed();
+        EntitiesAllocatorState {
+            generations: self.meta.iter().map(|meta| meta.generation).collect(),
+            pending: self.pending.clone(),
+            len: self.len,
+        }
+    }
+
+    /// Restores the generations and free list captured by
+    /// [`allocator_state`](Entities::allocator_state), keeping the locations of live entities.
+    ///
+    /// The caller must ensure that exactly the entities that were alive when `state` was captured
+    /// are alive now, with the same generations.
+    pub(crate) fn restore_allocator_state(&mut self, state: &EntitiesAllocatorState) {
+        self.verify_flushed();
+        debug_assert!(self.meta[state.generations.len().min(self.meta.len())..]
+            .iter()
+            .all(|meta| meta.location.archetype_id == ArchetypeId::INVALID));
+        self.meta.resize(state.generations.len(), EntityMeta::EMPTY);
+        for (meta, &generation) in self.meta.iter_mut().zip(&state.generations) {
+            meta.generation = generation;
+        }
+        self.pending.clone_from(&state.pending);
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+        self.len = state.len;
+    }
+
     /// Clears all [`Entity`] from the World.
     pub fn clear(&mut self) {
         self.meta.clear();
@@ -821,7 +943,7 @@ pub struct EntityLocation {
 
 impl EntityLocation {
     /// location for **pending entity** and **invalid entity**
-    const INVALID: EntityLocation = EntityLocation {
+    pub(crate) const INVALID: EntityLocation = EntityLocation {
         archetype_id: ArchetypeId::INVALID,
         archetype_row: ArchetypeRow::INVALID,
         table_id: TableId::INVALID,
@@ -910,4 +1032,45 @@ mod tests {
         assert_eq!(next_entity.index(), entity.index());
         assert!(next_entity.generation > entity.generation + GENERATIONS);
     }
+
+    #[test]
+    fn deterministic_allocation_ignores_free_order() {
+        let mut a = Entities::new();
+        let mut b = Entities::new();
+        a.set_allocation_mode(EntityAllocationMode::Deterministic);
+        b.set_allocation_mode(EntityAllocationMode::Deterministic);
+
+        let entities: Vec<Entity> = (0..5).map(|_| a.alloc()).collect();
+        for _ in 0..5 {
+            b.alloc();
+        }
+        for index in [3, 0, 4] {
+            a.free(entities[index]);
+        }
+        for index in [4, 3, 0] {
+            b.free(entities[index]);
+        }
+        // Grows the freelist past the end of `meta`.
+        a.alloc_at(Entity::from_raw(7));
+        b.alloc_at(Entity::from_raw(7));
+
+        let from_a: Vec<Entity> = (0..6).map(|_| a.alloc()).collect();
+        let from_b: Vec<Entity> = (0..6).map(|_| b.alloc()).collect();
+        assert_eq!(from_a, from_b);
+        let indices: Vec<u32> = from_a.iter().map(|entity| entity.index()).collect();
+        assert_eq!(indices, vec![0, 3, 4, 5, 6, 8]);
+    }
+
+    #[test]
+    fn reserve_range_is_not_allocated() {
+        let mut entities = Entities::new();
+        entities.alloc();
+        entities.alloc();
+
+        let range = entities.reserve_range(3);
+        assert_eq!(range, 2..5);
+        assert_eq!(entities.len(), 5);
+        assert!(entities.get(Entity::from_raw(2)).is_none());
+        assert_eq!(entities.alloc().index(), 5);
+    }
 }
@@ -1,23 +1,62 @@
 //! Event handling types.
 
 use crate as bevy_ecs;
-use crate::system::{Local, Res, ResMut, Resource, SystemParam};
+use crate::archetype::Archetype;
+use crate::component::Tick;
+use crate::observer::Traversal;
+use crate::schedule::SystemSet;
+use crate::system::{Local, ReadOnlySystemParam, Res, ResMut, Resource, SystemMeta, SystemParam};
+use crate::world::{unsafe_world_cell::UnsafeWorldCell, World};
 pub use bevy_ecs_macros::Event;
 use bevy_utils::detailed_trace;
 use std::ops::{Deref, DerefMut};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering as AtomicOrdering},
+    Arc, Mutex, Weak,
+};
 use std::{
     cmp::Ordering,
+    collections::{vec_deque::Iter, VecDeque},
     fmt,
     hash::{Hash, Hasher},
     iter::Chain,
     marker::PhantomData,
-    slice::Iter,
 };
 /// A type that can be stored in an [`Events<E>`] resource
 /// You can conveniently access events using the [`EventReader`] and [`EventWriter`] system parameter.
 ///
 /// Events must be thread-safe.
-pub trait Event: Send + Sync + 'static {}
+///
+/// Events can also be triggered for [observers](crate::observer) to react to immediately,
+/// optionally propagating from entity to entity along their [`Event::Traversal`].
+/// Both are configured when deriving `Event`:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::observer::Traversal;
+/// #[derive(Component)]
+/// struct Owner(Entity);
+///
+/// impl Traversal for Owner {
+///     fn traverse(world: &World, entity: Entity) -> Option<Entity> {
+///         world.get::<Owner>(entity).map(|owner| owner.0)
+///     }
+/// }
+///
+/// #[derive(Event)]
+/// #[event(traversal = Owner, auto_propagate)]
+/// struct Clicked;
+/// ```
+pub trait Event: Send + Sync + 'static {
+    /// How the event propagates between entities when triggered on them.
+    /// Use `()` for events that do not propagate.
+    type Traversal: Traversal;
+
+    /// Whether the event propagates along its [`Event::Traversal`] by default.
+    ///
+    /// Observers can override this with [`Trigger::propagate`](crate::observer::Trigger::propagate).
+    const AUTO_PROPAGATE: bool = false;
+}
 
 /// An `EventId` uniquely identifies an event stored in a specific [`World`].
 ///
@@ -157,6 +196,14 @@ struct EventInstance<E: Event> {
 /// but can be done by adding your event as a resource instead of using
 /// [`add_event`](https://docs.rs/bevy/*/bevy/app/struct.App.html#method.add_event).
 ///
+/// # Persistent events
+///
+/// Readers that do not run every frame, such as systems with run conditions, can miss events.
+/// [`Events::new_persistent`] creates a collection where [`update`](Events::update) only drops
+/// the events that every registered reader has read, up to a bounded capacity.
+/// [`EventReader`]s are registered when their system is initialized, and [`ManualEventReader`]s
+/// when they first read the events. Readers stop being registered once they are dropped.
+///
 /// [Example usage.](https://github.com/bevyengine/bevy/blob/latest/examples/ecs/event.rs)
 /// [Example usage standalone.](https://github.com/bevyengine/bevy/blob/latest/crates/bevy_ecs/examples/events.rs)
 ///
@@ -168,6 +215,7 @@ pub struct Events<E: Event> {
     /// Holds the newer events.
     events_b: EventSequence<E>,
     event_count: usize,
+    persistent: Option<PersistentEvents>,
 }
 
 // Derived Default impl would incorrectly require E: Default
@@ -177,10 +225,46 @@ impl<E: Event> Default for Events<E> {
             events_a: Default::default(),
             events_b: Default::default(),
             event_count: Default::default(),
+            persistent: None,
         }
     }
 }
 
+/// What persistent [`Events`] do when an event is sent while they store as many events as their capacity.
+///
+/// See [`Events::new_persistent`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EventOverflow {
+    /// Drops the oldest stored event to make room for the new one.
+    /// Readers that did not read it yet will count it in [`ManualEventReader::missed_events`].
+    #[default]
+    DropOldest,
+    /// Drops the new event.
+    DropNewest,
+}
+
+/// The configuration and registered readers of persistent [`Events`].
+#[derive(Debug)]
+struct PersistentEvents {
+    capacity: usize,
+    overflow: EventOverflow,
+    cursors: Mutex<Vec<Weak<AtomicUsize>>>,
+}
+
+impl PersistentEvents {
+    /// Returns the count of the oldest event that a registered reader has not read yet,
+    /// or `None` if no reader is registered.
+    fn oldest_unread_event_count(&self) -> Option<usize> {
+        let mut cursors = self.cursors.lock().unwrap_or_else(|e| e.into_inner());
+        cursors.retain(|cursor| cursor.strong_count() > 0);
+        cursors
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|cursor| cursor.load(AtomicOrdering::Relaxed))
+            .min()
+    }
+}
+
 impl<E: Event> Events<E> {
     /// Returns the index of the oldest event stored in the event buffer.
     pub fn oldest_event_count(&self) -> usize {
@@ -192,7 +276,8 @@ impl<E: Event> Events<E> {
 
 #[derive(Debug)]
 struct EventSequence<E: Event> {
-    events: Vec<EventInstance<E>>,
+    // a `VecDeque` so that full persistent events can drop their oldest event cheaply
+    events: VecDeque<EventInstance<E>>,
     start_event_count: usize,
 }
 
@@ -207,7 +292,7 @@ impl<E: Event> Default for EventSequence<E> {
 }
 
 impl<E: Event> Deref for EventSequence<E> {
-    type Target = Vec<EventInstance<E>>;
+    type Target = VecDeque<EventInstance<E>>;
 
     fn deref(&self) -> &Self::Target {
         &self.events
@@ -221,12 +306,57 @@ impl<E: Event> DerefMut for EventSequence<E> {
 }
 
 /// Reads events of type `T` in order and tracks which events have already been read.
-#[derive(SystemParam, Debug)]
+///
+/// The reader is registered to persistent [`Events`] when its system is initialized, so that
+/// events are kept until it reads them even if the system does not run every frame.
+#[derive(Debug)]
 pub struct EventReader<'w, 's, E: Event> {
     reader: Local<'s, ManualEventReader<E>>,
     events: Res<'w, Events<E>>,
 }
 
+type EventReaderParams<E> = (
+    Local<'static, ManualEventReader<E>>,
+    Res<'static, Events<E>>,
+);
+
+// SAFETY: Delegates to the implementation of `EventReaderParams`, which only reads the world.
+unsafe impl<'w, 's, E: Event> ReadOnlySystemParam for EventReader<'w, 's, E> {}
+
+// SAFETY: Delegates to the implementation of `EventReaderParams`.
+unsafe impl<E: Event> SystemParam for EventReader<'_, '_, E> {
+    type State = <EventReaderParams<E> as SystemParam>::State;
+    type Item<'w, 's> = EventReader<'w, 's, E>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let mut state = EventReaderParams::<E>::init_state(world, system_meta);
+        if let Some(events) = world.get_resource::<Events<E>>() {
+            events.register_reader(state.0.get());
+        }
+        state
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        EventReaderParams::<E>::new_archetype(state, archetype, system_meta);
+    }
+
+    fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
+        EventReaderParams::<E>::apply(state, system_meta, world);
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        let (reader, events) =
+            EventReaderParams::<E>::get_param(state, system_meta, world, change_tick);
+        EventReader { reader, events }
+    }
+}
+
 impl<'w, 's, E: Event> EventReader<'w, 's, E> {
     /// Iterates over the events this [`EventReader`] has not seen yet. This updates the
     /// [`EventReader`]'s event counter, which means subsequent event reads will not include events
@@ -367,6 +497,8 @@ impl<'w, E: Event> EventWriter<'w, E> {
 #[derive(Debug)]
 pub struct ManualEventReader<E: Event> {
     last_event_count: usize,
+    /// Shares `last_event_count` with the persistent [`Events`] this reader is registered to.
+    cursor: Option<Arc<AtomicUsize>>,
     _marker: PhantomData<E>,
 }
 
@@ -374,6 +506,7 @@ impl<E: Event> Default for ManualEventReader<E> {
     fn default() -> Self {
         ManualEventReader {
             last_event_count: 0,
+            cursor: None,
             _marker: Default::default(),
         }
     }
@@ -420,7 +553,24 @@ impl<E: Event> ManualEventReader<E> {
 
     /// See [`EventReader::clear()`]
     pub fn clear(&mut self, events: &Events<E>) {
+        events.register_reader(self);
         self.last_event_count = events.event_count;
+        self.publish_cursor();
+    }
+
+    /// Marks `count` more events as read.
+    #[inline]
+    fn advance(&mut self, count: usize) {
+        self.last_event_count += count;
+        self.publish_cursor();
+    }
+
+    /// Shares the count of the events read so far with the persistent [`Events`] this reader is registered to.
+    #[inline]
+    fn publish_cursor(&self) {
+        if let Some(cursor) = &self.cursor {
+            cursor.store(self.last_event_count, AtomicOrdering::Relaxed);
+        }
     }
 }
 
@@ -473,17 +623,19 @@ pub struct ManualEventIteratorWithId<'a, E: Event> {
 impl<'a, E: Event> ManualEventIteratorWithId<'a, E> {
     /// Creates a new iterator that yields any `events` that have not yet been seen by `reader`.
     pub fn new(reader: &'a mut ManualEventReader<E>, events: &'a Events<E>) -> Self {
+        events.register_reader(reader);
         let a_index = (reader.last_event_count).saturating_sub(events.events_a.start_event_count);
         let b_index = (reader.last_event_count).saturating_sub(events.events_b.start_event_count);
-        let a = events.events_a.get(a_index..).unwrap_or_default();
-        let b = events.events_b.get(b_index..).unwrap_or_default();
+        let a = events.events_a.range(a_index.min(events.events_a.len())..);
+        let b = events.events_b.range(b_index.min(events.events_b.len())..);
 
         let unread_count = a.len() + b.len();
         // Ensure `len` is implemented correctly
         debug_assert_eq!(unread_count, reader.len(events));
         reader.last_event_count = events.event_count - unread_count;
+        reader.publish_cursor();
         // Iterate the oldest first, then the newer events
-        let chain = a.iter().chain(b.iter());
+        let chain = a.chain(b);
 
         Self {
             reader,
@@ -508,7 +660,7 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
         {
             Some(item) => {
                 detailed_trace!("EventReader::iter() -> {}", item.1);
-                self.reader.last_event_count += 1;
+                self.reader.advance(1);
                 self.unread -= 1;
                 Some(item)
             }
@@ -518,11 +670,11 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
         if let Some(EventInstance { event_id, event }) = self.chain.nth(n) {
-            self.reader.last_event_count += n + 1;
+            self.reader.advance(n + 1);
             self.unread -= n + 1;
             Some((event, *event_id))
         } else {
-            self.reader.last_event_count += self.unread;
+            self.reader.advance(self.unread);
             self.unread = 0;
             None
         }
@@ -533,12 +685,12 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
         Self: Sized,
     {
         let EventInstance { event_id, event } = self.chain.last()?;
-        self.reader.last_event_count += self.unread;
+        self.reader.advance(self.unread);
         Some((event, *event_id))
     }
 
     fn count(self) -> usize {
-        self.reader.last_event_count += self.unread;
+        self.reader.advance(self.unread);
         self.unread
     }
 
@@ -554,9 +706,104 @@ impl<'a, E: Event> ExactSizeIterator for ManualEventIteratorWithId<'a, E> {
 }
 
 impl<E: Event> Events<E> {
+    /// Creates persistent events, which are kept until every registered reader has read them,
+    /// but at most `capacity` of them. See [`EventOverflow`] for what happens when the capacity is reached.
+    ///
+    /// Events are still dropped after two [`update`](Self::update) calls when no reader is registered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new_persistent(capacity: usize, overflow: EventOverflow) -> Self {
+        let mut events = Self::default();
+        events.set_persistent(capacity, overflow);
+        events
+    }
+
+    /// Makes these events persistent, or changes their capacity and overflow policy.
+    /// See [`Events::new_persistent`].
+    ///
+    /// Readers must read these events again to be registered, unless they were registered before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn set_persistent(&mut self, capacity: usize, overflow: EventOverflow) {
+        assert!(capacity > 0, "Persistent events need a non-zero capacity.");
+        let cursors = self
+            .persistent
+            .take()
+            .map(|persistent| persistent.cursors)
+            .unwrap_or_default();
+        self.persistent = Some(PersistentEvents {
+            capacity,
+            overflow,
+            cursors,
+        });
+        while self.len() > capacity {
+            self.drop_oldest();
+        }
+    }
+
+    /// Returns `true` if these events are kept until every registered reader has read them.
+    pub fn is_persistent(&self) -> bool {
+        self.persistent.is_some()
+    }
+
+    /// Registers the `reader` so that persistent events are kept until it reads them.
+    /// Does nothing if these events are not persistent or the reader is already registered.
+    fn register_reader(&self, reader: &mut ManualEventReader<E>) {
+        let Some(persistent) = &self.persistent else {
+            return;
+        };
+        if reader.cursor.is_some() {
+            return;
+        }
+        let cursor = Arc::new(AtomicUsize::new(reader.last_event_count));
+        persistent
+            .cursors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::downgrade(&cursor));
+        reader.cursor = Some(cursor);
+    }
+
+    /// Makes room for a new event in persistent events, returning `false` if it should be dropped instead.
+    fn reserve_persistent_event(&mut self) -> bool {
+        let Some(persistent) = &self.persistent else {
+            return true;
+        };
+        if self.len() < persistent.capacity {
+            return true;
+        }
+        match persistent.overflow {
+            EventOverflow::DropOldest => {
+                self.drop_oldest();
+                true
+            }
+            EventOverflow::DropNewest => {
+                detailed_trace!("Events::send() -> dropped, persistent events are full");
+                false
+            }
+        }
+    }
+
+    /// Drops the oldest stored event.
+    fn drop_oldest(&mut self) {
+        if self.events_a.pop_front().is_some() {
+            self.events_a.start_event_count += 1;
+        } else if self.events_b.pop_front().is_some() {
+            self.events_b.start_event_count += 1;
+            self.events_a.start_event_count = self.events_b.start_event_count;
+        }
+    }
+
     /// "Sends" an `event` by writing it to the current event buffer. [`EventReader`]s can then read
     /// the event.
     pub fn send(&mut self, event: E) {
+        if !self.reserve_persistent_event() {
+            return;
+        }
         let event_id = EventId {
             id: self.event_count,
             _marker: PhantomData,
@@ -565,7 +812,7 @@ impl<E: Event> Events<E> {
 
         let event_instance = EventInstance { event_id, event };
 
-        self.events_b.push(event_instance);
+        self.events_b.push_back(event_instance);
         self.event_count += 1;
     }
 
@@ -593,7 +840,17 @@ impl<E: Event> Events<E> {
 
     /// Swaps the event buffers and clears the oldest event buffer. In general, this should be
     /// called once per frame/update.
+    ///
+    /// Persistent events only drop the events of the oldest buffer that every registered reader has read.
     pub fn update(&mut self) {
+        if let Some(oldest_unread) = self
+            .persistent
+            .as_ref()
+            .and_then(PersistentEvents::oldest_unread_event_count)
+        {
+            self.update_persistent(oldest_unread);
+            return;
+        }
         std::mem::swap(&mut self.events_a, &mut self.events_b);
         self.events_b.clear();
         self.events_b.start_event_count = self.event_count;
@@ -603,6 +860,26 @@ impl<E: Event> Events<E> {
         );
     }
 
+    /// Drops the events older than `oldest_unread` from the oldest buffer, then moves the newer
+    /// events to it.
+    fn update_persistent(&mut self, oldest_unread: usize) {
+        // Events sent since the last update are always kept, like non-persistent events.
+        let oldest_kept = oldest_unread.min(self.events_b.start_event_count);
+        let dropped = oldest_kept
+            .saturating_sub(self.events_a.start_event_count)
+            .min(self.events_a.len());
+        self.events_a.drain(..dropped);
+        self.events_a.start_event_count += dropped;
+
+        let newer_events = std::mem::take(&mut self.events_b.events);
+        self.events_a.extend(newer_events);
+        self.events_b.start_event_count = self.event_count;
+        debug_assert_eq!(
+            self.events_a.start_event_count + self.events_a.len(),
+            self.events_b.start_event_count
+        );
+    }
+
     /// A system that calls [`Events::update`] once per frame.
     pub fn update_system(mut events: ResMut<Self>) {
         events.update();
@@ -689,6 +966,13 @@ impl<E: Event> std::iter::Extend<E> for Events<E> {
     where
         I: IntoIterator<Item = E>,
     {
+        if self.persistent.is_some() {
+            // Each event may need to make room for itself.
+            for event in iter {
+                self.send(event);
+            }
+            return;
+        }
         let old_count = self.event_count;
         let mut event_count = self.event_count;
         let events = iter.into_iter().map(|event| {
@@ -714,6 +998,53 @@ impl<E: Event> std::iter::Extend<E> for Events<E> {
     }
 }
 
+/// The [`SystemSet`] of the [`event_update_system`]s added for each event type by `App::add_event`.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EventUpdates;
+
+/// Tells [`event_update_system`] whether events can be updated.
+///
+/// When this resource exists, events are only updated after the [`signal_event_update_system`] ran,
+/// typically in a fixed timestep schedule. This ensures that fixed timestep systems see every event,
+/// even in frames where the fixed timestep schedule does not run.
+#[derive(Resource, Default, Debug)]
+pub struct EventUpdateSignal(bool);
+
+impl EventUpdateSignal {
+    /// Allows the next [`event_update_system`]s to update events.
+    pub fn signal(&mut self) {
+        self.0 = true;
+    }
+}
+
+/// Allows the next [`event_update_system`]s to update events, if the [`EventUpdateSignal`] resource exists.
+pub fn signal_event_update_system(signal: Option<ResMut<EventUpdateSignal>>) {
+    if let Some(mut signal) = signal {
+        signal.signal();
+    }
+}
+
+/// Resets the [`EventUpdateSignal`] after the [`event_update_system`]s ran, if the resource exists.
+pub fn reset_event_update_signal_system(signal: Option<ResMut<EventUpdateSignal>>) {
+    if let Some(mut signal) = signal {
+        signal.0 = false;
+    }
+}
+
+/// A system that calls [`Events::update`], unless the [`EventUpdateSignal`] resource exists
+/// and was not signaled since the last update.
+pub fn event_update_system<E: Event>(
+    signal: Option<Res<EventUpdateSignal>>,
+    mut events: ResMut<Events<E>>,
+) {
+    if let Some(signal) = signal {
+        if !signal.0 {
+            return;
+        }
+    }
+    events.update();
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{prelude::World, system::SystemState};
@@ -1053,4 +1384,128 @@ mod tests {
         }
         read_for::<EmptyTestEvent>();
     }
+
+    #[test]
+    fn persistent_events_wait_for_registered_readers() {
+        let mut events = Events::<TestEvent>::new_persistent(16, EventOverflow::DropOldest);
+        let mut reader = events.get_reader();
+        events.send(TestEvent { i: 0 });
+        // Registers the reader.
+        assert_eq!(reader.len(&events), 1);
+        assert_eq!(reader.iter(&events).next(), Some(&TestEvent { i: 0 }));
+
+        events.send(TestEvent { i: 1 });
+        events.update();
+        events.update();
+        events.update();
+        assert_eq!(get_events(&events, &mut reader), vec![TestEvent { i: 1 }]);
+        assert_eq!(reader.missed_events(&events), 0);
+
+        events.update();
+        events.update();
+        assert!(events.is_empty());
+
+        // Dropped readers do not keep events anymore.
+        events.send(TestEvent { i: 2 });
+        drop(reader);
+        events.update();
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn persistent_events_drop_oldest() {
+        let mut events = Events::<TestEvent>::new_persistent(2, EventOverflow::DropOldest);
+        let mut reader = events.get_reader();
+        reader.clear(&events);
+        events.extend((0..3).map(|i| TestEvent { i }));
+        events.update();
+        events.send(TestEvent { i: 3 });
+
+        assert_eq!(reader.missed_events(&events), 2);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 2 }, TestEvent { i: 3 }]
+        );
+    }
+
+    #[test]
+    fn persistent_events_drop_newest() {
+        let mut events = Events::<TestEvent>::new_persistent(2, EventOverflow::DropNewest);
+        let mut reader = events.get_reader();
+        reader.clear(&events);
+        events.extend((0..3).map(|i| TestEvent { i }));
+
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+        events.update();
+        events.update();
+        events.send(TestEvent { i: 3 });
+        assert_eq!(get_events(&events, &mut reader), vec![TestEvent { i: 3 }]);
+    }
+
+    #[test]
+    fn persistent_events_reach_systems_with_run_conditions() {
+        use crate::schedule::{IntoSystemConfigs, Schedule};
+
+        #[derive(Resource, Default)]
+        struct Enabled(bool);
+
+        #[derive(Resource, Default)]
+        struct Received(Vec<usize>);
+
+        let mut world = World::new();
+        world.insert_resource(Events::<TestEvent>::new_persistent(
+            16,
+            EventOverflow::DropOldest,
+        ));
+        world.init_resource::<Enabled>();
+        world.init_resource::<Received>();
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems(
+            (|mut reader: EventReader<TestEvent>, mut received: ResMut<Received>| {
+                received.0.extend(reader.iter().map(|event| event.i));
+            })
+            .run_if(|enabled: Res<Enabled>| enabled.0),
+        );
+
+        for i in 0..3 {
+            world.send_event(TestEvent { i });
+            schedule.run(&mut world);
+            world.resource_mut::<Events<TestEvent>>().update();
+        }
+        world.resource_mut::<Enabled>().0 = true;
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Received>().0, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn event_update_waits_for_signal() {
+        let mut world = World::new();
+        world.init_resource::<Events<TestEvent>>();
+        world.init_resource::<EventUpdateSignal>();
+        let mut update =
+            SystemState::<(Option<Res<EventUpdateSignal>>, ResMut<Events<TestEvent>>)>::new(
+                &mut world,
+            );
+        let mut run_update = |world: &mut World| {
+            let (signal, events) = update.get_mut(world);
+            event_update_system(signal, events);
+        };
+
+        world.send_event(TestEvent { i: 0 });
+        run_update(&mut world);
+        run_update(&mut world);
+        assert_eq!(world.resource::<Events<TestEvent>>().len(), 1);
+
+        world.resource_mut::<EventUpdateSignal>().0 = true;
+        run_update(&mut world);
+        run_update(&mut world);
+        assert!(world.resource::<Events<TestEvent>>().is_empty());
+    }
 }
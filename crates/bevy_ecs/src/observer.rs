@@ -0,0 +1,1054 @@
+//! Types for creating and running observers: systems that run immediately in response to a
+//! triggered [`Event`].
+//!
+//! Unlike [`Events`](crate::event::Events), which are buffered and read by polling systems,
+//! observers are run as soon as an event is triggered with [`World::trigger`] or
+//! [`World::trigger_targets`] (or the equivalent [`Commands`] methods when those are applied).
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! #[derive(Event)]
+//! struct Hit {
+//!     damage: u32,
+//! }
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! let target = world.spawn(Health(10)).id();
+//!
+//! world.entity_mut(target).observe(|trigger: Trigger<Hit>, mut query: Query<&mut Health>| {
+//!     let mut health = query.get_mut(trigger.entity()).unwrap();
+//!     health.0 -= trigger.event().damage;
+//! });
+//!
+//! world.trigger_targets(Hit { damage: 3 }, target);
+//! assert_eq!(world.get::<Health>(target).unwrap().0, 7);
+//! ```
+//!
+//! Observers also run for the built-in [`OnAdd`], [`OnInsert`], [`OnReplace`] and [`OnRemove`]
+//! events, which are triggered for every component added to, inserted on, replaced on, or removed
+//! from an entity. The components to observe are given by the second type parameter of [`Trigger`].
+
+use std::{any::TypeId, marker::PhantomData};
+
+use bevy_utils::HashMap;
+
+use crate::{
+    self as bevy_ecs,
+    bundle::Bundle,
+    component::{Component, ComponentId, Tick},
+    entity::Entity,
+    event::Event,
+    system::{
+        BoxedSystem, Command, Commands, IntoSystem, SystemMeta, SystemParam, SystemParamFunction,
+    },
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, EntityMut, World},
+};
+
+/// Describes how an [`Event`] propagates from one entity to the next when it is triggered on
+/// entities with [`World::trigger_targets`].
+///
+/// An event's traversal is set with `#[event(traversal = T)]` when deriving [`Event`].
+/// Propagation is then enabled per event with `#[event(auto_propagate)]`, or by observers
+/// calling [`Trigger::propagate`].
+pub trait Traversal: 'static {
+    /// Returns the entity the event should propagate to after `entity`, if any.
+    fn traverse(world: &World, entity: Entity) -> Option<Entity>;
+}
+
+impl Traversal for () {
+    fn traverse(_world: &World, _entity: Entity) -> Option<Entity> {
+        None
+    }
+}
+
+/// Triggered for each component that is added to an entity which did not already have it.
+///
+/// Observe with `Trigger<OnAdd, MyComponent>`. Runs after any `on_add` [hook](crate::component::ComponentHooks).
+#[derive(Event, Debug, Default)]
+pub struct OnAdd;
+
+/// Triggered for each component inserted on an entity, whether or not it was already present.
+///
+/// Observe with `Trigger<OnInsert, MyComponent>`. Runs after any `on_insert` [hook](crate::component::ComponentHooks).
+#[derive(Event, Debug, Default)]
+pub struct OnInsert;
+
+/// Triggered for each component whose value is about to be dropped, either because a new value is
+/// inserted over it or because it is removed.
+///
+/// Observe with `Trigger<OnReplace, MyComponent>`. Runs after any `on_replace` [hook](crate::component::ComponentHooks),
+/// while the old value can still be read.
+#[derive(Event, Debug, Default)]
+pub struct OnReplace;
+
+/// Triggered for each component removed from an entity, including when the entity is despawned.
+///
+/// Observe with `Trigger<OnRemove, MyComponent>`. Runs after any `on_remove` [hook](crate::component::ComponentHooks),
+/// while the component can still be read.
+#[derive(Event, Debug, Default)]
+pub struct OnRemove;
+
+/// The [`SystemParam`] that gives an observer access to the event that triggered it.
+///
+/// `E` is the observed [`Event`]. For the built-in [`OnAdd`], [`OnInsert`], [`OnReplace`] and
+/// [`OnRemove`] events, `B` is the [`Bundle`] of components to observe. Other events are not tied to
+/// components, so `B` should be left as `()` for them.
+///
+/// A `Trigger` must be the first parameter of an observer system, and can only be used in
+/// systems registered with [`World::observe`], [`EntityMut::observe`] or their [`Commands`]
+/// counterparts.
+pub struct Trigger<'w, E, B: Bundle = ()> {
+    event: &'w mut E,
+    propagate: &'w mut bool,
+    observer: Entity,
+    entity: Entity,
+    _marker: PhantomData<B>,
+}
+
+impl<'w, E, B: Bundle> Trigger<'w, E, B> {
+    /// Returns the event that triggered the observer.
+    pub fn event(&self) -> &E {
+        self.event
+    }
+
+    /// Returns a mutable reference to the event that triggered the observer.
+    ///
+    /// Changes are visible to observers that run afterwards, including when the event propagates.
+    pub fn event_mut(&mut self) -> &mut E {
+        self.event
+    }
+
+    /// Returns the entity that the event targets, or [`Entity::PLACEHOLDER`] if the event was
+    /// triggered without a target.
+    ///
+    /// When an event propagates, this is the entity the event has currently reached.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns the entity of the observer that is currently running.
+    pub fn observer(&self) -> Entity {
+        self.observer
+    }
+
+    /// Enables or disables propagation of the event to the next entity given by its
+    /// [`Event::Traversal`], once all observers of the current entity have run.
+    ///
+    /// Events start out propagating if they were derived with `#[event(auto_propagate)]`.
+    pub fn propagate(&mut self, should_propagate: bool) {
+        *self.propagate = should_propagate;
+    }
+
+    /// Returns `true` if the event will propagate after the current entity's observers have run.
+    pub fn get_propagate(&self) -> bool {
+        *self.propagate
+    }
+}
+
+/// Marker used to make sure a system has at most one [`Trigger`] parameter.
+#[derive(Component)]
+struct TriggerAccess;
+
+// SAFETY: `Trigger` only accesses the event being triggered, which is not stored in the world.
+// A fake write access is registered so that a system cannot hold two `Trigger`s to the same event.
+unsafe impl<'a, E: Event, B: Bundle> SystemParam for Trigger<'a, E, B> {
+    type State = ();
+    type Item<'w, 's> = Trigger<'w, E, B>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let access_id = world.init_component::<TriggerAccess>();
+        if system_meta
+            .component_access_set
+            .combined_access()
+            .has_write(access_id)
+        {
+            panic!(
+                "Trigger<{}> in system {} conflicts with a previous Trigger. An observer can only have one Trigger parameter.",
+                std::any::type_name::<E>(),
+                system_meta.name
+            );
+        }
+        system_meta
+            .component_access_set
+            .add_unfiltered_write(access_id);
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: only the observer metadata is read.
+        let current = unsafe { world.world_metadata() }
+            .observers
+            .current
+            .as_ref()
+            .filter(|current| current.event_type == TypeId::of::<E>())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Trigger<{}> can only be used in observers of that event, but was used by {}",
+                    std::any::type_name::<E>(),
+                    system_meta.name
+                )
+            });
+        Trigger {
+            // SAFETY: the event type was checked above, the event outlives the observer run and
+            // the system holds no other reference to it.
+            event: unsafe { &mut *current.event.cast::<E>() },
+            // SAFETY: the flag outlives the observer run and is only accessed through this trigger.
+            propagate: unsafe { &mut *current.propagate },
+            observer: current.observer,
+            entity: current.entity,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Implemented for the parameter tuples of functions that can be used as observer systems:
+/// those whose first parameter is a [`Trigger`].
+pub trait ObserverSystemParam<E: Event, B: Bundle>: SystemParam {}
+
+macro_rules! impl_observer_system_param {
+    ($($param: ident),*) => {
+        impl<E: Event, B: Bundle, $($param: SystemParam),*> ObserverSystemParam<E, B>
+            for (Trigger<'static, E, B>, $($param,)*)
+        {
+        }
+    };
+}
+
+bevy_utils::all_tuples!(impl_observer_system_param, 0, 15, P);
+
+/// Conversion trait to turn a function whose first parameter is a [`Trigger<E, B>`] into an
+/// observer system.
+///
+/// Exclusive systems cannot be used as observers, as observers may run in the middle of
+/// structural changes to the [`World`].
+pub trait IntoObserverSystem<E: Event, B: Bundle, Marker>: Send + 'static {
+    /// Turns this value into a boxed system that can be run as an observer.
+    fn into_observer_system(self) -> BoxedSystem;
+}
+
+impl<E: Event, B: Bundle, Marker, F> IntoObserverSystem<E, B, Marker> for F
+where
+    Marker: 'static,
+    F: SystemParamFunction<Marker, In = (), Out = ()>,
+    F::Param: ObserverSystemParam<E, B>,
+{
+    fn into_observer_system(self) -> BoxedSystem {
+        Box::new(IntoSystem::into_system(self))
+    }
+}
+
+/// Information about the trigger that is currently being observed, read by [`Trigger`].
+pub(crate) struct CurrentTrigger {
+    event_type: TypeId,
+    event: *mut u8,
+    propagate: *mut bool,
+    observer: Entity,
+    entity: Entity,
+}
+
+// SAFETY: the pointers are only dereferenced while the trigger is running on the thread that
+// owns the world exclusively.
+unsafe impl Send for CurrentTrigger {}
+// SAFETY: see above.
+unsafe impl Sync for CurrentTrigger {}
+
+/// Describes what an observer watches.
+#[derive(Debug, Clone)]
+pub struct ObserverDescriptor {
+    event: TypeId,
+    components: Vec<ComponentId>,
+    entities: Vec<Entity>,
+}
+
+impl ObserverDescriptor {
+    /// Returns the [`TypeId`] of the [`Event`] that is observed.
+    pub fn event(&self) -> TypeId {
+        self.event
+    }
+
+    /// Returns the components the observer is restricted to, or an empty slice if it observes
+    /// triggers regardless of their components.
+    pub fn components(&self) -> &[ComponentId] {
+        &self.components
+    }
+
+    /// Returns the entities the observer is restricted to, or an empty slice if it observes
+    /// triggers on every entity.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+}
+
+/// Component stored on observer entities. Despawning the entity unregisters the observer.
+#[derive(Component)]
+pub struct ObserverState {
+    descriptor: ObserverDescriptor,
+}
+
+impl ObserverState {
+    /// Returns the [`ObserverDescriptor`] of this observer.
+    pub fn descriptor(&self) -> &ObserverDescriptor {
+        &self.descriptor
+    }
+}
+
+/// Component stored on entities that are watched by entity-specific observers.
+///
+/// When it is removed, for instance because the entity is despawned, its observers are despawned too.
+#[derive(Component, Default)]
+pub struct ObservedBy(Vec<Entity>);
+
+impl ObservedBy {
+    /// Returns the observers that watch this entity.
+    pub fn get(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
+struct ObserverSlot {
+    /// Taken out while the observer runs, so that it cannot run recursively.
+    system: Option<BoxedSystem>,
+    descriptor: ObserverDescriptor,
+}
+
+/// The observers registered for a single event type.
+#[derive(Default)]
+struct CachedObservers {
+    /// Observers not restricted to specific entities.
+    global: Vec<Entity>,
+    /// Observers restricted to specific entities, keyed by the watched entity.
+    entities: HashMap<Entity, Vec<Entity>>,
+}
+
+impl CachedObservers {
+    fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.entities.is_empty()
+    }
+}
+
+/// Stores every observer registered in a [`World`].
+#[derive(Default)]
+pub struct Observers {
+    on_add: CachedObservers,
+    on_insert: CachedObservers,
+    on_replace: CachedObservers,
+    on_remove: CachedObservers,
+    cache: HashMap<TypeId, CachedObservers>,
+    observers: HashMap<Entity, ObserverSlot>,
+    pub(crate) current: Option<CurrentTrigger>,
+}
+
+impl Observers {
+    fn get(&self, event_type: TypeId) -> Option<&CachedObservers> {
+        if event_type == TypeId::of::<OnAdd>() {
+            Some(&self.on_add)
+        } else if event_type == TypeId::of::<OnInsert>() {
+            Some(&self.on_insert)
+        } else if event_type == TypeId::of::<OnReplace>() {
+            Some(&self.on_replace)
+        } else if event_type == TypeId::of::<OnRemove>() {
+            Some(&self.on_remove)
+        } else {
+            self.cache.get(&event_type)
+        }
+    }
+
+    fn get_or_insert_mut(&mut self, event_type: TypeId) -> &mut CachedObservers {
+        if event_type == TypeId::of::<OnAdd>() {
+            &mut self.on_add
+        } else if event_type == TypeId::of::<OnInsert>() {
+            &mut self.on_insert
+        } else if event_type == TypeId::of::<OnReplace>() {
+            &mut self.on_replace
+        } else if event_type == TypeId::of::<OnRemove>() {
+            &mut self.on_remove
+        } else {
+            self.cache.entry(event_type).or_default()
+        }
+    }
+
+    /// Returns `true` if there are any observers for the [`OnAdd`] event.
+    #[inline]
+    pub(crate) fn has_on_add(&self) -> bool {
+        !self.on_add.is_empty()
+    }
+
+    /// Returns `true` if there are any observers for the [`OnInsert`] event.
+    #[inline]
+    pub(crate) fn has_on_insert(&self) -> bool {
+        !self.on_insert.is_empty()
+    }
+
+    /// Returns `true` if there are any observers for the [`OnReplace`] event.
+    #[inline]
+    pub(crate) fn has_on_replace(&self) -> bool {
+        !self.on_replace.is_empty()
+    }
+
+    /// Returns `true` if there are any observers for the [`OnRemove`] event.
+    #[inline]
+    pub(crate) fn has_on_remove(&self) -> bool {
+        !self.on_remove.is_empty()
+    }
+
+    /// Returns the number of registered observers.
+    pub fn len(&self) -> usize {
+        self.observers.len()
+    }
+
+    /// Returns `true` if no observers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    fn register(&mut self, observer: Entity, system: BoxedSystem, descriptor: ObserverDescriptor) {
+        let cache = self.get_or_insert_mut(descriptor.event);
+        if descriptor.entities.is_empty() {
+            cache.global.push(observer);
+        } else {
+            for &entity in &descriptor.entities {
+                cache.entities.entry(entity).or_default().push(observer);
+            }
+        }
+        self.observers.insert(
+            observer,
+            ObserverSlot {
+                system: Some(system),
+                descriptor,
+            },
+        );
+    }
+
+    fn unregister(&mut self, observer: Entity) {
+        let Some(slot) = self.observers.remove(&observer) else {
+            return;
+        };
+        let cache = self.get_or_insert_mut(slot.descriptor.event);
+        if slot.descriptor.entities.is_empty() {
+            cache.global.retain(|&e| e != observer);
+        } else {
+            for entity in &slot.descriptor.entities {
+                if let Some(observers) = cache.entities.get_mut(entity) {
+                    observers.retain(|&e| e != observer);
+                    if observers.is_empty() {
+                        cache.entities.remove(entity);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collects the observers of `event_type` that should run for a trigger on `entity`
+    /// involving `components`.
+    fn collect(
+        &self,
+        event_type: TypeId,
+        entity: Entity,
+        components: &[ComponentId],
+        observers: &mut Vec<Entity>,
+    ) {
+        let Some(cache) = self.get(event_type) else {
+            return;
+        };
+        let entity_observers = cache.entities.get(&entity).into_iter().flatten();
+        observers.extend(
+            cache
+                .global
+                .iter()
+                .chain(entity_observers)
+                .filter(|observer| {
+                    self.observers.get(*observer).map_or(false, |slot| {
+                        slot.descriptor.components.is_empty()
+                            || slot
+                                .descriptor
+                                .components
+                                .iter()
+                                .any(|id| components.contains(id))
+                    })
+                }),
+        );
+    }
+}
+
+/// Runs `observer` with the given trigger data, if it is registered and not already running.
+fn run_observer(
+    world: &mut World,
+    observer: Entity,
+    event_type: TypeId,
+    entity: Entity,
+    event: *mut u8,
+    propagate: &mut bool,
+) {
+    let Some(mut system) = world
+        .observers
+        .observers
+        .get_mut(&observer)
+        .and_then(|slot| slot.system.take())
+    else {
+        return;
+    };
+    let previous = world.observers.current.replace(CurrentTrigger {
+        event_type,
+        event,
+        propagate,
+        observer,
+        entity,
+    });
+    // Observer systems are never exclusive, so running them cannot change the structure of the world.
+    system.run((), world);
+    world.observers.current = previous;
+    if let Some(slot) = world.observers.observers.get_mut(&observer) {
+        slot.system = Some(system);
+    }
+    // The observer may be running in the middle of a structural change, so its commands are
+    // applied the next time the world's command queue is flushed.
+    world.command_queue.push(ApplyObserverDeferred(observer));
+}
+
+/// Applies the deferred system parameters (such as [`Commands`]) of an observer.
+struct ApplyObserverDeferred(Entity);
+
+impl Command for ApplyObserverDeferred {
+    fn apply(self, world: &mut World) {
+        let Some(mut system) = world
+            .observers
+            .observers
+            .get_mut(&self.0)
+            .and_then(|slot| slot.system.take())
+        else {
+            return;
+        };
+        system.apply_deferred(world);
+        if let Some(slot) = world.observers.observers.get_mut(&self.0) {
+            slot.system = Some(system);
+        }
+    }
+}
+
+impl<'w> DeferredWorld<'w> {
+    /// Runs the observers of `event_type` for a trigger on `entity` involving `components`.
+    ///
+    /// # Safety
+    /// `event` must point to a valid value of the event type identified by `event_type`.
+    pub(crate) unsafe fn trigger_observers_with_data(
+        &mut self,
+        event_type: TypeId,
+        entity: Entity,
+        components: &[ComponentId],
+        event: *mut u8,
+        propagate: &mut bool,
+    ) {
+        let world = self.as_world_mut();
+        let mut observers = Vec::new();
+        world
+            .observers
+            .collect(event_type, entity, components, &mut observers);
+        for observer in observers {
+            run_observer(world, observer, event_type, entity, event, propagate);
+        }
+    }
+
+    /// Runs the observers of the built-in lifecycle event `E` for each of the `components` of `entity`.
+    pub(crate) fn trigger_lifecycle_observers<E: Event + Default>(
+        &mut self,
+        entity: Entity,
+        components: &[ComponentId],
+    ) {
+        for &component_id in components {
+            let mut event = E::default();
+            let mut propagate = false;
+            // SAFETY: `event` is a valid `E`.
+            unsafe {
+                self.trigger_observers_with_data(
+                    TypeId::of::<E>(),
+                    entity,
+                    &[component_id],
+                    (&mut event as *mut E).cast(),
+                    &mut propagate,
+                );
+            }
+        }
+    }
+}
+
+/// The targets of an event triggered with [`World::trigger_targets`].
+pub trait TriggerTargets: Send + Sync + 'static {
+    /// The entities the event targets.
+    fn entities(&self) -> &[Entity];
+}
+
+impl TriggerTargets for Entity {
+    fn entities(&self) -> &[Entity] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl TriggerTargets for Vec<Entity> {
+    fn entities(&self) -> &[Entity] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> TriggerTargets for [Entity; N] {
+    fn entities(&self) -> &[Entity] {
+        self.as_slice()
+    }
+}
+
+impl World {
+    /// Spawns a global observer that runs `system` whenever the event `E` is triggered,
+    /// on any entity or without a target.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Event)]
+    /// struct Explode;
+    ///
+    /// #[derive(Resource, Default)]
+    /// struct Explosions(usize);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Explosions>();
+    /// world.observe(|_trigger: Trigger<Explode>, mut explosions: ResMut<Explosions>| {
+    ///     explosions.0 += 1;
+    /// });
+    ///
+    /// world.trigger(Explode);
+    /// assert_eq!(world.resource::<Explosions>().0, 1);
+    /// ```
+    pub fn observe<E: Event, B: Bundle, M>(
+        &mut self,
+        system: impl IntoObserverSystem<E, B, M>,
+    ) -> EntityMut {
+        let entity = self.spawn_empty().id();
+        self.register_observer::<E, B>(entity, system.into_observer_system(), Vec::new());
+        self.entity_mut(entity)
+    }
+
+    /// Makes `observer` an observer of `E` restricted to `entities`, running `system`.
+    fn register_observer<E: Event, B: Bundle>(
+        &mut self,
+        observer: Entity,
+        mut system: BoxedSystem,
+        entities: Vec<Entity>,
+    ) {
+        self.init_observer_components();
+        let mut components = Vec::new();
+        B::component_ids(&mut self.components, &mut self.storages, &mut |id| {
+            components.push(id);
+        });
+        system.initialize(self);
+        let descriptor = ObserverDescriptor {
+            event: TypeId::of::<E>(),
+            components,
+            entities,
+        };
+        for &entity in &descriptor.entities {
+            if let Some(mut entity) = self.get_entity_mut(entity) {
+                if let Some(mut observed_by) = entity.get_mut::<ObservedBy>() {
+                    observed_by.0.push(observer);
+                } else {
+                    entity.insert(ObservedBy(vec![observer]));
+                }
+            }
+        }
+        self.observers
+            .register(observer, system, descriptor.clone());
+        self.entity_mut(observer)
+            .insert(ObserverState { descriptor });
+    }
+
+    /// Initializes the components used to store observers, along with the hooks that keep
+    /// [`Observers`] in sync with them.
+    fn init_observer_components(&mut self) {
+        if self.components.component_id::<ObserverState>().is_some() {
+            return;
+        }
+        self.register_component_hooks::<ObserverState>()
+            .on_remove(|mut world, entity, _| {
+                world.as_world_mut().observers.unregister(entity);
+            });
+        self.register_component_hooks::<ObservedBy>()
+            .on_remove(|mut world, entity, _| {
+                let observers = world
+                    .get::<ObservedBy>(entity)
+                    .map(|observed_by| observed_by.0.clone())
+                    .unwrap_or_default();
+                let mut commands = world.commands();
+                for observer in observers {
+                    commands.add(move |world: &mut World| {
+                        if let Some(entity) = world.get_entity_mut(observer) {
+                            entity.despawn();
+                        }
+                    });
+                }
+            });
+    }
+
+    /// Triggers the event `E` without a target, running its global observers immediately.
+    pub fn trigger<E: Event>(&mut self, mut event: E) {
+        let mut propagate = false;
+        // SAFETY: `event` is a valid `E`.
+        unsafe {
+            DeferredWorld::from(&mut *self).trigger_observers_with_data(
+                TypeId::of::<E>(),
+                Entity::PLACEHOLDER,
+                &[],
+                (&mut event as *mut E).cast(),
+                &mut propagate,
+            );
+        }
+        self.flush_commands();
+    }
+
+    /// Triggers the event `E` for each of the `targets`, running the global observers and the
+    /// observers of the targeted entities immediately.
+    ///
+    /// If the event propagates (see [`Trigger::propagate`]), it is then triggered on the next
+    /// entity given by its [`Event::Traversal`], until propagation stops or there is no next entity.
+    pub fn trigger_targets<E: Event>(&mut self, mut event: E, targets: impl TriggerTargets) {
+        for &target in targets.entities() {
+            let mut entity = target;
+            loop {
+                let mut propagate = E::AUTO_PROPAGATE;
+                // SAFETY: `event` is a valid `E`.
+                unsafe {
+                    DeferredWorld::from(&mut *self).trigger_observers_with_data(
+                        TypeId::of::<E>(),
+                        entity,
+                        &[],
+                        (&mut event as *mut E).cast(),
+                        &mut propagate,
+                    );
+                }
+                if !propagate {
+                    break;
+                }
+                match E::Traversal::traverse(self, entity) {
+                    Some(next) => entity = next,
+                    None => break,
+                }
+            }
+        }
+        self.flush_commands();
+    }
+}
+
+impl<'w> EntityMut<'w> {
+    /// Spawns an observer that runs `system` whenever the event `E` is triggered on this entity.
+    ///
+    /// The observer is despawned when this entity is despawned.
+    pub fn observe<E: Event, B: Bundle, M>(
+        &mut self,
+        system: impl IntoObserverSystem<E, B, M>,
+    ) -> &mut Self {
+        let target = self.id();
+        self.world_scope(|world| {
+            let observer = world.spawn_empty().id();
+            world.register_observer::<E, B>(observer, system.into_observer_system(), vec![target]);
+        });
+        self
+    }
+}
+
+impl<'w, 's> Commands<'w, 's> {
+    /// Spawns a global observer that runs `system` whenever the event `E` is triggered.
+    ///
+    /// See [`World::observe`].
+    pub fn observe<E: Event, B: Bundle, M>(
+        &mut self,
+        system: impl IntoObserverSystem<E, B, M>,
+    ) -> Entity {
+        let observer = self.spawn_empty().id();
+        let system = system.into_observer_system();
+        self.add(move |world: &mut World| {
+            world.register_observer::<E, B>(observer, system, Vec::new());
+        });
+        observer
+    }
+
+    /// Triggers the event `E` without a target when the command is applied.
+    ///
+    /// See [`World::trigger`].
+    pub fn trigger<E: Event>(&mut self, event: E) {
+        self.add(move |world: &mut World| world.trigger(event));
+    }
+
+    /// Triggers the event `E` for each of the `targets` when the command is applied.
+    ///
+    /// See [`World::trigger_targets`].
+    pub fn trigger_targets<E: Event>(&mut self, event: E, targets: impl TriggerTargets) {
+        self.add(move |world: &mut World| world.trigger_targets(event, targets));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::observer::{ObservedBy, Traversal, Trigger};
+    use crate::prelude::*;
+    use crate::system::CommandQueue;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Resource, Default)]
+    struct Order(Vec<&'static str>);
+
+    impl Order {
+        fn observed(&mut self, name: &'static str) {
+            self.0.push(name);
+        }
+    }
+
+    #[derive(Event)]
+    struct EventA;
+
+    #[derive(Component)]
+    struct Up(Entity);
+
+    impl Traversal for Up {
+        fn traverse(world: &World, entity: Entity) -> Option<Entity> {
+            world.get::<Up>(entity).map(|up| up.0)
+        }
+    }
+
+    #[derive(Event)]
+    #[event(traversal = Up, auto_propagate)]
+    struct Bubbling;
+
+    #[test]
+    fn observer_order_spawn_despawn() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.observe(|_: Trigger<OnAdd, A>, mut res: ResMut<Order>| res.observed("add"));
+        world.observe(|_: Trigger<OnInsert, A>, mut res: ResMut<Order>| res.observed("insert"));
+        world.observe(|_: Trigger<OnRemove, A>, mut res: ResMut<Order>| res.observed("remove"));
+
+        let entity = world.spawn(A).id();
+        world.despawn(entity);
+        assert_eq!(vec!["add", "insert", "remove"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_order_insert_remove() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.observe(|_: Trigger<OnAdd, A>, mut res: ResMut<Order>| res.observed("add"));
+        world.observe(|_: Trigger<OnInsert, A>, mut res: ResMut<Order>| res.observed("insert"));
+        world.observe(|_: Trigger<OnRemove, A>, mut res: ResMut<Order>| res.observed("remove"));
+
+        let mut entity = world.spawn_empty();
+        entity.insert(A);
+        entity.insert(A);
+        entity.remove::<A>();
+        assert_eq!(
+            vec!["add", "insert", "insert", "remove"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_filters_components() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.observe(|_: Trigger<OnAdd, B>, mut res: ResMut<Order>| res.observed("add_b"));
+        world.observe(|_: Trigger<OnAdd, (A, B)>, mut res: ResMut<Order>| {
+            res.observed("add_ab");
+        });
+
+        world.spawn(A);
+        world.spawn(B);
+        assert_eq!(
+            vec!["add_ab", "add_b", "add_ab"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_runs_after_hooks() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<Order>().observed("hook"));
+        world.observe(|_: Trigger<OnAdd, A>, mut res: ResMut<Order>| res.observed("observer"));
+
+        world.spawn(A);
+        assert_eq!(vec!["hook", "observer"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_reads_removed_component() {
+        #[derive(Component)]
+        struct Value(u32);
+
+        #[derive(Resource, Default)]
+        struct Removed(u32);
+
+        let mut world = World::new();
+        world.init_resource::<Removed>();
+        world.observe(
+            |trigger: Trigger<OnRemove, Value>, query: Query<&Value>, mut res: ResMut<Removed>| {
+                res.0 = query.get(trigger.entity()).unwrap().0;
+            },
+        );
+
+        let entity = world.spawn(Value(7)).id();
+        world.despawn(entity);
+        assert_eq!(7, world.resource::<Removed>().0);
+    }
+
+    #[test]
+    fn observer_commands_are_applied() {
+        let mut world = World::new();
+        world.observe(|trigger: Trigger<OnAdd, A>, mut commands: Commands| {
+            commands.entity(trigger.entity()).insert(B);
+        });
+
+        let entity = world.spawn(A).id();
+        world.flush_commands();
+        assert!(world.entity(entity).contains::<B>());
+    }
+
+    #[test]
+    fn observer_trigger() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+        world.observe(|trigger: Trigger<EventA>, mut res: ResMut<Order>| {
+            assert_eq!(Entity::PLACEHOLDER, trigger.entity());
+            res.observed("event_a");
+        });
+
+        world.trigger(EventA);
+        assert_eq!(vec!["event_a"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_entity_targets() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let watched = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        world
+            .entity_mut(watched)
+            .observe(|_: Trigger<EventA>, mut res: ResMut<Order>| res.observed("entity"));
+        world.observe(|_: Trigger<EventA>, mut res: ResMut<Order>| res.observed("global"));
+
+        world.trigger_targets(EventA, other);
+        world.trigger_targets(EventA, watched);
+        assert_eq!(
+            vec!["global", "global", "entity"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_despawned_with_entity() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let watched = world.spawn_empty().id();
+        world
+            .entity_mut(watched)
+            .observe(|_: Trigger<EventA>, mut res: ResMut<Order>| res.observed("entity"));
+        let observer = world.get::<ObservedBy>(watched).unwrap().get()[0];
+
+        world.despawn(watched);
+        assert!(world.get_entity(observer).is_none());
+        assert!(world.observers.is_empty());
+
+        world.trigger_targets(EventA, watched);
+        assert!(world.resource::<Order>().0.is_empty());
+    }
+
+    #[test]
+    fn observer_despawn_unregisters() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let observer = world
+            .observe(|_: Trigger<EventA>, mut res: ResMut<Order>| res.observed("global"))
+            .id();
+        world.despawn(observer);
+
+        world.trigger(EventA);
+        assert!(world.resource::<Order>().0.is_empty());
+    }
+
+    #[test]
+    fn observer_commands_trigger() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.observe(|_: Trigger<EventA>, mut res: ResMut<Order>| res.observed("event_a"));
+        commands.trigger(EventA);
+        queue.apply(&mut world);
+
+        assert_eq!(vec!["event_a"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_propagation() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let parent = world
+            .spawn_empty()
+            .observe(|_: Trigger<Bubbling>, mut res: ResMut<Order>| res.observed("parent"))
+            .id();
+        let child = world
+            .spawn(Up(parent))
+            .observe(|_: Trigger<Bubbling>, mut res: ResMut<Order>| res.observed("child"))
+            .id();
+
+        world.trigger_targets(Bubbling, child);
+        assert_eq!(vec!["child", "parent"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_propagation_halt() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let parent = world
+            .spawn_empty()
+            .observe(|_: Trigger<Bubbling>, mut res: ResMut<Order>| res.observed("parent"))
+            .id();
+        let child = world
+            .spawn(Up(parent))
+            .observe(|mut trigger: Trigger<Bubbling>, mut res: ResMut<Order>| {
+                res.observed("child");
+                trigger.propagate(false);
+            })
+            .id();
+
+        world.trigger_targets(Bubbling, child);
+        assert_eq!(vec!["child"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn observer_multiple_triggers() {
+        let mut world = World::new();
+        world.observe(|_: Trigger<EventA>, _: Trigger<EventA>| {});
+    }
+}
@@ -202,6 +202,21 @@ impl<T: SparseSetIndex> Access<T> {
     pub fn writes(&self) -> impl Iterator<Item = T> + '_ {
         self.writes.ones().map(T::get_sparse_set_index)
     }
+
+    /// Returns `true` if the set is a subset of another, i.e. `other` contains
+    /// at least all the values in `self`.
+    pub fn is_subset(&self, other: &Access<T>) -> bool {
+        if self.reads_all {
+            return other.reads_all && self.writes.is_subset(&other.writes);
+        }
+
+        if other.reads_all {
+            return self.writes.is_subset(&other.writes);
+        }
+
+        self.reads_and_writes.is_subset(&other.reads_and_writes)
+            && self.writes.is_subset(&other.writes)
+    }
 }
 
 /// An [`Access`] that has been filtered to include and exclude certain combinations of elements.
@@ -227,6 +242,9 @@ impl<T: SparseSetIndex> Access<T> {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FilteredAccess<T: SparseSetIndex> {
     access: Access<T>,
+    // The elements that must be present on every entity matched by this access, used to check
+    // that a query can be transmuted into another one.
+    required: FixedBitSet,
     // An array of filter sets to express `With` or `Without` clauses in disjunctive normal form, for example: `Or<(With<A>, With<B>)>`.
     // Filters like `(With<A>, Or<(With<B>, Without<C>)>` are expanded into `Or<((With<A>, With<B>), (With<A>, Without<C>))>`.
     filter_sets: Vec<AccessFilters<T>>,
@@ -236,6 +254,7 @@ impl<T: SparseSetIndex> Default for FilteredAccess<T> {
     fn default() -> Self {
         Self {
             access: Access::default(),
+            required: FixedBitSet::default(),
             filter_sets: vec![AccessFilters::default()],
         }
     }
@@ -265,15 +284,33 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     /// Adds access to the element given by `index`.
     pub fn add_read(&mut self, index: T) {
         self.access.add_read(index.clone());
+        self.add_required(index.clone());
         self.and_with(index);
     }
 
     /// Adds exclusive access to the element given by `index`.
     pub fn add_write(&mut self, index: T) {
         self.access.add_write(index.clone());
+        self.add_required(index.clone());
         self.and_with(index);
     }
 
+    fn add_required(&mut self, index: T) {
+        let index = index.sparse_set_index();
+        self.required.grow(index + 1);
+        self.required.insert(index);
+    }
+
+    /// Removes every required element, leaving accesses and filters untouched.
+    pub(crate) fn clear_required(&mut self) {
+        self.required.clear();
+    }
+
+    /// Replaces the required elements with the ones of `other`, leaving accesses and filters untouched.
+    pub(crate) fn copy_required(&mut self, other: &FilteredAccess<T>) {
+        self.required.clone_from(&other.required);
+    }
+
     /// Adds a `With` filter: corresponds to a conjunction (AND) operation.
     ///
     /// Suppose we begin with `Or<(With<A>, With<B>)>`, which is represented by an array of two `AccessFilter` instances.
@@ -350,6 +387,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     /// `Or<((With<A>, With<C>), (With<A>, Without<D>), (Without<B>, With<C>), (Without<B>, Without<D>))>`.
     pub fn extend(&mut self, other: &FilteredAccess<T>) {
         self.access.extend(&other.access);
+        self.required.union_with(&other.required);
 
         // We can avoid allocating a new array of bitsets if `other` contains just a single set of filters:
         // in this case we can short-circuit by performing an in-place union for each bitset.
@@ -377,6 +415,29 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     pub fn read_all(&mut self) {
         self.access.read_all();
     }
+
+    /// Returns `true` if the set is a subset of another, i.e. `other` contains
+    /// at least all the values in `self`.
+    ///
+    /// Elements required by `self` must be required by `other` too.
+    pub fn is_subset(&self, other: &FilteredAccess<T>) -> bool {
+        self.required.is_subset(&other.required) && self.access().is_subset(other.access())
+    }
+
+    /// Returns `true` if a set of elements, described by `set_contains`, satisfies at least one of
+    /// the `With`/`Without` filter sets.
+    pub fn matches_filters(&self, set_contains: impl Fn(T) -> bool) -> bool {
+        self.filter_sets.iter().any(|filter| {
+            filter
+                .with
+                .ones()
+                .all(|index| set_contains(T::get_sparse_set_index(index)))
+                && filter
+                    .without
+                    .ones()
+                    .all(|index| !set_contains(T::get_sparse_set_index(index)))
+        })
+    }
 }
 
 #[derive(Clone, Eq, PartialEq)]
@@ -1,11 +1,14 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
-    change_detection::{Ticks, TicksMut},
+    change_detection::{PreviousSlot, Ticks, TicksMut},
     component::{Component, ComponentId, ComponentStorage, StorageType, Tick},
     entity::Entity,
     query::{Access, DebugCheckedUnwrap, FilteredAccess},
-    storage::{ComponentSparseSet, Table, TableRow},
-    world::{unsafe_world_cell::UnsafeWorldCell, EntityRef, Mut, Ref, World},
+    storage::{ComponentSparseSet, PreviousValues, Table, TableRow},
+    world::{
+        unsafe_world_cell::UnsafeWorldCell, EntityRef, FilteredEntityMut, FilteredEntityRef, Mut,
+        Ref, World,
+    },
 };
 pub use bevy_ecs_macros::WorldQuery;
 use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
@@ -437,6 +440,20 @@ pub unsafe trait WorldQuery {
     /// Creates and initializes a [`State`](WorldQuery::State) for this [`WorldQuery`] type.
     fn init_state(world: &mut World) -> Self::State;
 
+    /// Attempts to initialize a [`State`](WorldQuery::State) for this [`WorldQuery`] type using read-only
+    /// access to the [`World`]. Returns `None` if a component it needs has not been initialized yet.
+    fn get_state(world: &World) -> Option<Self::State>;
+
+    /// Sets the access this [`WorldQuery`] may use when it is part of a query built with a
+    /// [`QueryBuilder`](crate::query::QueryBuilder).
+    ///
+    /// Called once with the builder's final access, before any of the other methods use `state`.
+    /// Only queries whose access depends on the builder, such as [`FilteredEntityRef`], need to
+    /// override this.
+    #[allow(unused_variables)]
+    #[inline]
+    fn set_access(state: &mut Self::State, access: &FilteredAccess<ComponentId>) {}
+
     /// Returns `true` if this query matches a set of components. Otherwise, returns `false`.
     fn matches_component_set(
         state: &Self::State,
@@ -512,6 +529,10 @@ unsafe impl WorldQuery for Entity {
 
     fn init_state(_world: &mut World) {}
 
+    fn get_state(_world: &World) -> Option<()> {
+        Some(())
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -591,6 +612,10 @@ unsafe impl<'a> WorldQuery for EntityRef<'a> {
 
     fn init_state(_world: &mut World) {}
 
+    fn get_state(_world: &World) -> Option<()> {
+        Some(())
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -602,6 +627,204 @@ unsafe impl<'a> WorldQuery for EntityRef<'a> {
 /// SAFETY: access is read only
 unsafe impl<'a> ReadOnlyWorldQuery for EntityRef<'a> {}
 
+/// SAFETY: The accesses of `Self::ReadOnly` are a subset of the accesses of `Self`
+unsafe impl<'a> WorldQuery for FilteredEntityRef<'a> {
+    type Fetch<'w> = (UnsafeWorldCell<'w>, Access<ComponentId>);
+    type Item<'w> = FilteredEntityRef<'w>;
+    type ReadOnly = Self;
+    type State = FilteredAccess<ComponentId>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    const IS_DENSE: bool = true;
+
+    const IS_ARCHETYPAL: bool = true;
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        (world, read_only_access(state.access()))
+    }
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _state: &Self::State,
+        _archetype: &'w Archetype,
+        _table: &Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(_fetch: &mut Self::Fetch<'w>, _state: &Self::State, _table: &'w Table) {
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        (world, access): &mut Self::Fetch<'w>,
+        entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+        // SAFETY: `fetch` must be called with an entity that exists in the world
+        let cell = world.get_entity(entity).debug_checked_unwrap();
+        // SAFETY: read access to every component in `access` has been registered
+        FilteredEntityRef::new(cell, access.clone())
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        let read_access = read_only_access(state.access());
+        assert!(
+            access.access().is_compatible(&read_access),
+            "FilteredEntityRef conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+        );
+        access.access_mut().extend(&read_access);
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        for component_id in archetype.components() {
+            if state.access().has_read(component_id) {
+                access.add_read(archetype.get_archetype_component_id(component_id).unwrap());
+            }
+        }
+    }
+
+    fn init_state(_world: &mut World) -> Self::State {
+        FilteredAccess::default()
+    }
+
+    fn get_state(_world: &World) -> Option<Self::State> {
+        Some(FilteredAccess::default())
+    }
+
+    fn set_access(state: &mut Self::State, access: &FilteredAccess<ComponentId>) {
+        *state = access.clone();
+    }
+
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+/// SAFETY: access is read only
+unsafe impl<'a> ReadOnlyWorldQuery for FilteredEntityRef<'a> {}
+
+/// SAFETY: The accesses of `Self::ReadOnly` are a subset of the accesses of `Self`
+unsafe impl<'a> WorldQuery for FilteredEntityMut<'a> {
+    type Fetch<'w> = (UnsafeWorldCell<'w>, Access<ComponentId>);
+    type Item<'w> = FilteredEntityMut<'w>;
+    type ReadOnly = FilteredEntityRef<'a>;
+    type State = FilteredAccess<ComponentId>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    const IS_DENSE: bool = true;
+
+    const IS_ARCHETYPAL: bool = true;
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        (world, state.access().clone())
+    }
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _state: &Self::State,
+        _archetype: &'w Archetype,
+        _table: &Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(_fetch: &mut Self::Fetch<'w>, _state: &Self::State, _table: &'w Table) {
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        (world, access): &mut Self::Fetch<'w>,
+        entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+        // SAFETY: `fetch` must be called with an entity that exists in the world
+        let cell = world.get_entity(entity).debug_checked_unwrap();
+        // SAFETY: the accesses in `access` have been registered
+        FilteredEntityMut::new(cell, access.clone())
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        assert!(
+            access.access().is_compatible(state.access()),
+            "FilteredEntityMut conflicts with a previous access in this query. Exclusive access cannot coincide with any other accesses.",
+        );
+        access.access_mut().extend(state.access());
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        for component_id in archetype.components() {
+            let archetype_component_id =
+                archetype.get_archetype_component_id(component_id).unwrap();
+            if state.access().has_write(component_id) {
+                access.add_write(archetype_component_id);
+            } else if state.access().has_read(component_id) {
+                access.add_read(archetype_component_id);
+            }
+        }
+    }
+
+    fn init_state(_world: &mut World) -> Self::State {
+        FilteredAccess::default()
+    }
+
+    fn get_state(_world: &World) -> Option<Self::State> {
+        Some(FilteredAccess::default())
+    }
+
+    fn set_access(state: &mut Self::State, access: &FilteredAccess<ComponentId>) {
+        *state = access.clone();
+    }
+
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+/// Returns a copy of `access` with every write downgraded to a read.
+fn read_only_access(access: &Access<ComponentId>) -> Access<ComponentId> {
+    let mut read_access = Access::default();
+    if access.has_read_all() {
+        read_access.read_all();
+    }
+    for component_id in access.reads_and_writes() {
+        read_access.add_read(component_id);
+    }
+    read_access
+}
+
 #[doc(hidden)]
 pub struct ReadFetch<'w, T> {
     // T::Storage = TableStorage
@@ -734,6 +957,10 @@ unsafe impl<T: Component> WorldQuery for &T {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -755,6 +982,8 @@ pub struct RefFetch<'w, T> {
     )>,
     // T::Storage = SparseStorage
     sparse_set: Option<&'w ComponentSparseSet>,
+    // T tracks previous values
+    previous: Option<&'w PreviousValues>,
 
     last_run: Tick,
     this_run: Tick,
@@ -804,6 +1033,8 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
                     .get(component_id)
                     .debug_checked_unwrap()
             }),
+            // SAFETY: previous values are only accessed for the entities this query matches
+            previous: world.storages().previous_values.get(component_id),
             last_run,
             this_run,
         }
@@ -853,6 +1084,7 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
                         this_run: fetch.this_run,
                         last_run: fetch.last_run,
                     },
+                    previous: PreviousSlot::new(fetch.previous, entity),
                 }
             }
             StorageType::SparseSet => {
@@ -864,6 +1096,7 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
                 Ref {
                     value: component.deref(),
                     ticks: Ticks::from_tick_cells(ticks, fetch.last_run, fetch.this_run),
+                    previous: PreviousSlot::new(fetch.previous, entity),
                 }
             }
         }
@@ -895,6 +1128,10 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -916,6 +1153,8 @@ pub struct WriteFetch<'w, T> {
     )>,
     // T::Storage = SparseStorage
     sparse_set: Option<&'w ComponentSparseSet>,
+    // T tracks previous values
+    previous: Option<&'w PreviousValues>,
 
     last_run: Tick,
     this_run: Tick,
@@ -965,6 +1204,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                     .get(component_id)
                     .debug_checked_unwrap()
             }),
+            // SAFETY: previous values are only accessed for the entities this query matches
+            previous: world.storages().previous_values.get(component_id),
             last_run,
             this_run,
         }
@@ -1013,6 +1254,7 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                         changed: changed_ticks.get(table_row.index()).deref_mut(),
                         this_run: fetch.this_run,
                         last_run: fetch.last_run,
+                        previous: PreviousSlot::new(fetch.previous, entity),
                     },
                 }
             }
@@ -1022,9 +1264,11 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                     .debug_checked_unwrap()
                     .get_with_ticks(entity)
                     .debug_checked_unwrap();
+                let mut ticks = TicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run);
+                ticks.previous = PreviousSlot::new(fetch.previous, entity);
                 Mut {
                     value: component.assert_unique().deref_mut(),
-                    ticks: TicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run),
+                    ticks,
                 }
             }
         }
@@ -1056,6 +1300,10 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1163,6 +1411,14 @@ unsafe impl<T: WorldQuery> WorldQuery for Option<T> {
         T::init_state(world)
     }
 
+    fn get_state(world: &World) -> Option<T::State> {
+        T::get_state(world)
+    }
+
+    fn set_access(state: &mut T::State, access: &FilteredAccess<ComponentId>) {
+        T::set_access(state, access);
+    }
+
     fn matches_component_set(
         _state: &T::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1298,6 +1554,10 @@ unsafe impl<T: Component> WorldQuery for Has<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.component_id::<T>()
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1310,6 +1570,141 @@ unsafe impl<T: Component> WorldQuery for Has<T> {
 /// SAFETY: [`Has`] is read only
 unsafe impl<T: Component> ReadOnlyWorldQuery for Has<T> {}
 
+/// Fetches the value the component `T` had before its first mutation in the current frame, or its
+/// current value if it has not been mutated since.
+///
+/// Previous values are only kept for components with
+/// [`TrackPrevious`](crate::component::TrackPrevious) enabled. For other components, this always
+/// fetches the current value.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::Previous;
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// #[component(track_previous)]
+/// struct Position(f32);
+///
+/// fn report_movement(query: Query<(&Position, Previous<Position>), Changed<Position>>) {
+///     for (position, previous) in &query {
+///         println!("moved by {}", position.0 - previous.0);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(report_movement);
+/// ```
+pub struct Previous<T>(PhantomData<T>);
+
+#[doc(hidden)]
+pub struct PreviousFetch<'w, T> {
+    current: ReadFetch<'w, T>,
+    previous: Option<&'w PreviousValues>,
+}
+
+impl<T> Clone for PreviousFetch<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for PreviousFetch<'_, T> {}
+
+/// SAFETY: `Self` is the same as `Self::ReadOnly`, and the access is the same as `&T`
+unsafe impl<T: Component> WorldQuery for Previous<T> {
+    type Fetch<'w> = PreviousFetch<'w, T>;
+    type Item<'w> = &'w T;
+    type ReadOnly = Self;
+    type State = ComponentId;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: &'wlong T) -> &'wshort T {
+        item
+    }
+
+    const IS_DENSE: bool = <&T as WorldQuery>::IS_DENSE;
+
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        &component_id: &ComponentId,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> PreviousFetch<'w, T> {
+        PreviousFetch {
+            current: <&T as WorldQuery>::init_fetch(world, &component_id, last_run, this_run),
+            // SAFETY: previous values are only accessed for the entities this query matches,
+            // which we have shared access to.
+            previous: world.storages().previous_values.get(component_id),
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut PreviousFetch<'w, T>,
+        component_id: &ComponentId,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        <&T as WorldQuery>::set_archetype(&mut fetch.current, component_id, archetype, table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(
+        fetch: &mut PreviousFetch<'w, T>,
+        component_id: &ComponentId,
+        table: &'w Table,
+    ) {
+        <&T as WorldQuery>::set_table(&mut fetch.current, component_id, table);
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        let current = <&T as WorldQuery>::fetch(&mut fetch.current, entity, table_row);
+        fetch
+            .previous
+            // SAFETY: we have shared access to the component of `entity` for `'w`
+            .and_then(|previous| unsafe { previous.get::<T>(entity) })
+            .unwrap_or(current)
+    }
+
+    fn update_component_access(
+        component_id: &ComponentId,
+        access: &mut FilteredAccess<ComponentId>,
+    ) {
+        <&T as WorldQuery>::update_component_access(component_id, access);
+    }
+
+    fn update_archetype_component_access(
+        component_id: &ComponentId,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        <&T as WorldQuery>::update_archetype_component_access(component_id, archetype, access);
+    }
+
+    fn init_state(world: &mut World) -> ComponentId {
+        world.init_component::<T>()
+    }
+
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.component_id::<T>()
+    }
+
+    fn matches_component_set(
+        &state: &ComponentId,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        set_contains_id(state)
+    }
+}
+
+/// SAFETY: access is read only
+unsafe impl<T: Component> ReadOnlyWorldQuery for Previous<T> {}
+
 macro_rules! impl_tuple_fetch {
     ($(($name: ident, $state: ident)),*) => {
         #[allow(non_snake_case)]
@@ -1394,6 +1789,15 @@ macro_rules! impl_tuple_fetch {
                 ($($name::init_state(_world),)*)
             }
 
+            fn get_state(_world: &World) -> Option<Self::State> {
+                Some(($($name::get_state(_world)?,)*))
+            }
+
+            fn set_access(state: &mut Self::State, _access: &FilteredAccess<ComponentId>) {
+                let ($($name,)*) = state;
+                $($name::set_access($name, _access);)*
+            }
+
             fn matches_component_set(state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($name,)*) = state;
                 true $(&& $name::matches_component_set($name, _set_contains_id))*
@@ -1501,6 +1905,8 @@ macro_rules! impl_anytuple_fetch {
                     }
                 )*
 
+                // None of the terms is required to be present.
+                _new_access.copy_required(_access);
                 *_access = _new_access;
             }
 
@@ -1517,6 +1923,10 @@ macro_rules! impl_anytuple_fetch {
                 ($($name::init_state(_world),)*)
             }
 
+            fn get_state(_world: &World) -> Option<Self::State> {
+                Some(($($name::get_state(_world)?,)*))
+            }
+
             fn matches_component_set(_state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($name,)*) = _state;
                 false $(|| $name::matches_component_set($name, _set_contains_id))*
@@ -1591,6 +2001,10 @@ unsafe impl<Q: WorldQuery> WorldQuery for NopWorldQuery<Q> {
         Q::init_state(world)
     }
 
+    fn get_state(world: &World) -> Option<Self::State> {
+        Q::get_state(world)
+    }
+
     fn matches_component_set(
         state: &Self::State,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1654,6 +2068,10 @@ unsafe impl<T: ?Sized> WorldQuery for PhantomData<T> {
 
     fn init_state(_world: &mut World) -> Self::State {}
 
+    fn get_state(_world: &World) -> Option<Self::State> {
+        Some(())
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -105,6 +105,10 @@ unsafe impl<T: Component> WorldQuery for With<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.component_id::<T>()
+    }
+
     fn matches_component_set(
         &id: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -206,6 +210,10 @@ unsafe impl<T: Component> WorldQuery for Without<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.component_id::<T>()
+    }
+
     fn matches_component_set(
         &id: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -358,6 +366,8 @@ macro_rules! impl_query_filter_tuple {
                     }
                 )*
 
+                // None of the terms is required to be present.
+                _new_access.copy_required(access);
                 *access = _new_access;
             }
 
@@ -370,6 +380,10 @@ macro_rules! impl_query_filter_tuple {
                 ($($filter::init_state(world),)*)
             }
 
+            fn get_state(world: &World) -> Option<Self::State> {
+                Some(($($filter::get_state(world)?,)*))
+            }
+
             fn matches_component_set(_state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($filter,)*) = _state;
                 false $(|| $filter::matches_component_set($filter, _set_contains_id))*
@@ -534,6 +548,10 @@ macro_rules! impl_tick_filter {
                 world.init_component::<T>()
             }
 
+            fn get_state(world: &World) -> Option<ComponentId> {
+                world.component_id::<T>()
+            }
+
             fn matches_component_set(&id: &ComponentId, set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 set_contains_id(id)
             }
@@ -4,12 +4,13 @@ use crate::{
     entity::Entity,
     prelude::FromWorld,
     query::{
-        Access, BatchingStrategy, DebugCheckedUnwrap, FilteredAccess, QueryCombinationIter,
-        QueryIter, QueryParIter, WorldQuery,
+        Access, BatchingStrategy, DebugCheckedUnwrap, FilteredAccess, QueryBuilder,
+        QueryCombinationIter, QueryIter, QueryParIter, WorldQuery,
     },
     storage::{TableId, TableRow},
     world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
 };
+use bevy_utils::tracing::warn;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::Instrument;
 use fixedbitset::FixedBitSet;
@@ -33,6 +34,9 @@ pub struct QueryState<Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
     pub(crate) matched_table_ids: Vec<TableId>,
     // NOTE: we maintain both a ArchetypeId bitset and a vec because iterating the vec is faster
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
+    // Whether the query iterates over matched tables rather than matched archetypes. This is
+    // `Q::IS_DENSE && F::IS_DENSE`, unless the state was transmuted from or joined with another one.
+    pub(crate) is_dense: bool,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
 }
@@ -116,6 +120,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
             archetype_generation: ArchetypeGeneration::initial(),
             matched_table_ids: Vec::new(),
             matched_archetype_ids: Vec::new(),
+            is_dense: Q::IS_DENSE && F::IS_DENSE,
             fetch_state,
             filter_state,
             component_access,
@@ -127,6 +132,204 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         state
     }
 
+    /// Creates a new [`QueryState`] from a [`QueryBuilder`], using the access it was built with.
+    ///
+    /// Prefer [`QueryBuilder::build`], which calls this.
+    pub fn from_builder(builder: &mut QueryBuilder<Q, F>) -> Self {
+        let mut fetch_state = Q::init_state(builder.world_mut());
+        let filter_state = F::init_state(builder.world_mut());
+        Q::set_access(&mut fetch_state, builder.access());
+        // `set_access` gives terms like `FilteredEntityMut` the access of the whole builder, which
+        // also holds the access of their sibling terms, so check that the terms do not conflict.
+        Q::update_component_access(&fetch_state, &mut FilteredAccess::default());
+
+        let mut state = Self {
+            world_id: builder.world().id(),
+            archetype_generation: ArchetypeGeneration::initial(),
+            matched_table_ids: Vec::new(),
+            matched_archetype_ids: Vec::new(),
+            is_dense: Q::IS_DENSE && F::IS_DENSE,
+            fetch_state,
+            filter_state,
+            component_access: builder.access().clone(),
+            matched_tables: Default::default(),
+            matched_archetypes: Default::default(),
+            archetype_component_access: Default::default(),
+        };
+        state.update_archetypes(builder.world());
+        state
+    }
+
+    /// Returns the [`FilteredAccess`] of the components accessed by this query.
+    ///
+    /// This is what [`System`](crate::system::System)s use to check for conflicts between queries.
+    pub fn component_access(&self) -> &FilteredAccess<ComponentId> {
+        &self.component_access
+    }
+
+    /// Use this to transform a [`QueryState`] into a more generic [`QueryState`].
+    /// This can be useful for passing to another function that might take the more generalized query.
+    /// See [`Query::transmute_lens`](crate::system::Query::transmute_lens) for more details.
+    ///
+    /// You should not call [`update_archetypes`](Self::update_archetypes) on the returned [`QueryState`] as the result will be unpredictable.
+    /// You might end up with a mix of archetypes that only matched the original query + archetypes that only match
+    /// the new [`QueryState`]. Most of the safe methods on [`QueryState`] call [`QueryState::update_archetypes`] internally, so this
+    /// is best used through a [`Query`](crate::system::Query).
+    pub fn transmute<NewQ: WorldQuery>(&self, world: &World) -> QueryState<NewQ> {
+        self.transmute_filtered::<NewQ, ()>(world)
+    }
+
+    /// Creates a new [`QueryState`] with the same underlying [`FilteredAccess`], matched tables and archetypes
+    /// as self but with a new type signature.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `NewQ` or `NewF` requires accesses or components that `Q` and `F` do not.
+    pub fn transmute_filtered<NewQ: WorldQuery, NewF: ReadOnlyWorldQuery>(
+        &self,
+        world: &World,
+    ) -> QueryState<NewQ, NewF> {
+        let mut component_access = FilteredAccess::default();
+        let mut fetch_state = NewQ::get_state(world)
+            .expect("Could not create fetch_state, Please initialize all referenced components before transmuting.");
+        let filter_state = NewF::get_state(world)
+            .expect("Could not create filter_state, Please initialize all referenced components before transmuting.");
+
+        NewQ::set_access(&mut fetch_state, &self.component_access);
+        NewQ::update_component_access(&fetch_state, &mut component_access);
+
+        let mut filter_component_access = FilteredAccess::default();
+        NewF::update_component_access(&filter_state, &mut filter_component_access);
+
+        component_access.extend(&filter_component_access);
+        assert!(
+            component_access.is_subset(&self.component_access),
+            "Transmuted state for {} attempts to access terms that are not allowed by original state {}.",
+            std::any::type_name::<(NewQ, NewF)>(),
+            std::any::type_name::<(Q, F)>()
+        );
+
+        QueryState {
+            world_id: self.world_id,
+            archetype_generation: self.archetype_generation,
+            matched_table_ids: self.matched_table_ids.clone(),
+            matched_archetype_ids: self.matched_archetype_ids.clone(),
+            is_dense: self.is_dense,
+            fetch_state,
+            filter_state,
+            component_access: self.component_access.clone(),
+            matched_tables: self.matched_tables.clone(),
+            matched_archetypes: self.matched_archetypes.clone(),
+            archetype_component_access: self.archetype_component_access.clone(),
+        }
+    }
+
+    /// Use this to combine two queries. The data accessed will be the intersection
+    /// of archetypes included in both queries. This can be useful for accessing a
+    /// subset of the entities between two queries.
+    ///
+    /// You should not call `update_archetypes` on the returned `QueryState` as the result
+    /// could be unpredictable. You might end up with a mix of archetypes that only matched
+    /// the original query + archetypes that only match the new `QueryState`. Most of the
+    /// safe methods on `QueryState` call [`QueryState::update_archetypes`] internally, so
+    /// this is best used through a `Query`.
+    ///
+    /// ## Performance
+    ///
+    /// This will have similar performance as constructing a new `QueryState` since much of internal state
+    /// needs to be reconstructed. But it will be a little faster as it only needs to compare the intersection
+    /// of matching archetypes rather than iterating over all archetypes.
+    ///
+    /// ## Panics
+    ///
+    /// Will panic if `NewQ` contains accesses not in `Q` or `OtherQ`.
+    pub fn join<OtherQ: WorldQuery, NewQ: WorldQuery>(
+        &self,
+        world: &World,
+        other: &QueryState<OtherQ>,
+    ) -> QueryState<NewQ, ()> {
+        self.join_filtered::<_, (), NewQ, ()>(world, other)
+    }
+
+    /// Use this to combine two queries. The data accessed will be the intersection
+    /// of archetypes included in both queries.
+    ///
+    /// ## Panics
+    ///
+    /// Will panic if `NewQ` or `NewF` requires accesses not in `Q` or `OtherQ`.
+    /// Will also panic if the queries were created with different [`World`]s.
+    pub fn join_filtered<
+        OtherQ: WorldQuery,
+        OtherF: ReadOnlyWorldQuery,
+        NewQ: WorldQuery,
+        NewF: ReadOnlyWorldQuery,
+    >(
+        &self,
+        world: &World,
+        other: &QueryState<OtherQ, OtherF>,
+    ) -> QueryState<NewQ, NewF> {
+        if self.world_id != other.world_id {
+            panic!("Joining queries initialized on different worlds is not allowed.");
+        }
+
+        let mut component_access = FilteredAccess::default();
+        let mut new_fetch_state = NewQ::get_state(world)
+            .expect("Could not create fetch_state, Please initialize all referenced components before transmuting.");
+        let new_filter_state = NewF::get_state(world)
+            .expect("Could not create filter_state, Please initialize all referenced components before transmuting.");
+
+        let mut joined_component_access = self.component_access.clone();
+        joined_component_access.extend(&other.component_access);
+
+        NewQ::set_access(&mut new_fetch_state, &joined_component_access);
+        NewQ::update_component_access(&new_fetch_state, &mut component_access);
+
+        let mut new_filter_component_access = FilteredAccess::default();
+        NewF::update_component_access(&new_filter_state, &mut new_filter_component_access);
+
+        component_access.extend(&new_filter_component_access);
+
+        assert!(
+            component_access.is_subset(&joined_component_access),
+            "Joined state for {} attempts to access terms that are not allowed by state {} joined with {}.",
+            std::any::type_name::<(NewQ, NewF)>(),
+            std::any::type_name::<(Q, F)>(),
+            std::any::type_name::<(OtherQ, OtherF)>()
+        );
+
+        if self.archetype_generation != other.archetype_generation {
+            warn!("You have tried to join queries with different archetype_generations. This could lead to unpredictable results.");
+        }
+
+        // the join is dense only if both of the queries were dense.
+        let is_dense = self.is_dense && other.is_dense;
+
+        // take the intersection of the matched ids
+        let mut matched_tables = self.matched_tables.clone();
+        let mut matched_archetypes = self.matched_archetypes.clone();
+        matched_tables.intersect_with(&other.matched_tables);
+        matched_archetypes.intersect_with(&other.matched_archetypes);
+        let matched_table_ids = matched_tables.ones().map(TableId::new).collect();
+        let matched_archetype_ids = matched_archetypes.ones().map(ArchetypeId::new).collect();
+
+        let mut archetype_component_access = self.archetype_component_access.clone();
+        archetype_component_access.extend(&other.archetype_component_access);
+
+        QueryState {
+            world_id: self.world_id,
+            archetype_generation: self.archetype_generation,
+            matched_table_ids,
+            matched_archetype_ids,
+            is_dense,
+            fetch_state: new_fetch_state,
+            filter_state: new_filter_state,
+            component_access: joined_component_access,
+            matched_tables,
+            matched_archetypes,
+            archetype_component_access,
+        }
+    }
+
     /// Checks if the query is empty for the given [`World`], where the last change and current tick are given.
     #[inline]
     pub fn is_empty(&self, world: &World, last_run: Tick, this_run: Tick) -> bool {
@@ -202,6 +405,10 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     pub fn new_archetype(&mut self, archetype: &Archetype) {
         if Q::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
+            // Queries built with a `QueryBuilder` can have filters that `Q` and `F` don't know about.
+            && self
+                .component_access
+                .matches_filters(|id| archetype.contains(id))
         {
             Q::update_archetype_component_access(
                 &self.fetch_state,
@@ -972,7 +1179,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         let mut filter = F::init_fetch(world, &self.filter_state, last_run, this_run);
 
         let tables = &world.storages().tables;
-        if Q::IS_DENSE && F::IS_DENSE {
+        if self.is_dense {
             for table_id in &self.matched_table_ids {
                 let table = tables.get(*table_id).debug_checked_unwrap();
                 Q::set_table(&mut fetch, &self.fetch_state, table);
@@ -1047,7 +1254,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         // NOTE: If you are changing query iteration code, remember to update the following places, where relevant:
         // QueryIter, QueryIterationCursor, QueryManyIter, QueryCombinationIter, QueryState::for_each_unchecked_manual, QueryState::par_for_each_unchecked_manual
         bevy_tasks::ComputeTaskPool::get().scope(|scope| {
-            if Q::IS_DENSE && F::IS_DENSE {
+            if self.is_dense {
                 // SAFETY: We only access table data that has been registered in `self.archetype_component_access`.
                 let tables = &world.storages().tables;
                 for table_id in &self.matched_table_ids {
@@ -1314,7 +1521,12 @@ impl fmt::Display for QueryEntityError {
 
 #[cfg(test)]
 mod tests {
-    use crate::{prelude::*, query::QueryEntityError};
+    use crate as bevy_ecs;
+    use crate::{
+        prelude::*,
+        query::{QueryEntityError, QueryState},
+        world::FilteredEntityRef,
+    };
 
     #[test]
     fn get_many_unchecked_manual_uniqueness() {
@@ -1419,6 +1631,183 @@ mod tests {
         let mut query_state = world_1.query::<Entity>();
         let _panics = query_state.get_many_mut(&mut world_2, []);
     }
+
+    #[derive(Component, PartialEq, Debug)]
+    struct A(usize);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct B(usize);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct C(usize);
+
+    #[test]
+    fn can_transmute_to_more_general() {
+        let mut world = World::new();
+        world.spawn((A(1), B(0)));
+
+        let query_state = world.query::<(&A, &B)>();
+        let mut new_query_state = query_state.transmute::<&A>(&world);
+        assert_eq!(new_query_state.iter(&world).len(), 1);
+        let a = new_query_state.single(&world);
+
+        assert_eq!(a.0, 1);
+    }
+
+    #[test]
+    fn can_transmute_entity_mut_to_filtered_ref() {
+        let mut world = World::new();
+        world.spawn((A(0), B(1)));
+
+        let query_state = world.query::<(&mut A, &B)>();
+        let mut new_query_state = query_state.transmute::<FilteredEntityRef>(&world);
+        let entity = new_query_state.single(&world);
+
+        assert_eq!(entity.get::<A>(), Some(&A(0)));
+        assert_eq!(entity.get::<B>(), Some(&B(1)));
+    }
+
+    #[test]
+    fn can_transmute_mut_fetch() {
+        let mut world = World::new();
+        world.spawn(A(0));
+
+        let query_state = world.query::<&mut A>();
+        let mut new_query_state = query_state.transmute::<&A>(&world);
+        assert_eq!(new_query_state.single(&world), &A(0));
+    }
+
+    #[test]
+    fn can_transmute_added() {
+        let mut world = World::new();
+        world.spawn(A(0));
+
+        let query_state = world.query_filtered::<&A, Added<A>>();
+        let mut new_query_state = query_state.transmute_filtered::<&A, Added<A>>(&world);
+        assert_eq!(new_query_state.iter(&world).count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempts to access terms that are not allowed by original state")]
+    fn cannot_transmute_to_include_data_not_in_original_query() {
+        let mut world = World::new();
+        world.init_component::<A>();
+        world.init_component::<B>();
+        world.spawn(A(0));
+
+        let query_state = world.query::<&A>();
+        let mut _new_query_state = query_state.transmute::<(&A, &B)>(&world);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempts to access terms that are not allowed by original state")]
+    fn cannot_transmute_immut_to_mut() {
+        let mut world = World::new();
+        world.spawn(A(0));
+
+        let query_state = world.query::<&A>();
+        let mut _new_query_state = query_state.transmute::<&mut A>(&world);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempts to access terms that are not allowed by original state")]
+    fn cannot_transmute_option_to_immut() {
+        let mut world = World::new();
+        world.spawn(C(0));
+
+        let query_state = world.query::<Option<&A>>();
+        let mut new_query_state = query_state.transmute::<&A>(&world);
+        let x = new_query_state.single(&world);
+        assert_eq!(x.0, 1234);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempts to access terms that are not allowed by original state")]
+    fn cannot_transmute_any_of_to_immut() {
+        let mut world = World::new();
+        world.spawn(A(0));
+
+        let query_state = world.query::<AnyOf<(&A, &B)>>();
+        let _new_query_state = query_state.transmute::<&A>(&world);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Could not create fetch_state, Please initialize all referenced components before transmuting."
+    )]
+    fn cannot_transmute_to_uninitialized_component() {
+        let mut world = World::new();
+        world.spawn(A(0));
+
+        let query_state = world.query::<&A>();
+        let _new_query_state = query_state.transmute::<Option<&B>>(&world);
+    }
+
+    #[test]
+    fn transmute_keeps_matched_archetypes_of_original() {
+        let mut world = World::new();
+        world.spawn((A(0), B(0)));
+        world.spawn(A(1));
+
+        let query_state = world.query_filtered::<&A, With<B>>();
+        let mut new_query_state = query_state.transmute::<&A>(&world);
+        // The `With<B>` filter of the original state is still applied.
+        assert_eq!(new_query_state.single(&world), &A(0));
+    }
+
+    #[test]
+    fn join() {
+        let mut world = World::new();
+        world.spawn(A(0));
+        world.spawn(B(1));
+        let entity_ab = world.spawn((A(2), B(3))).id();
+        world.spawn((A(4), B(5), C(6)));
+
+        let query_1 = QueryState::<&A, Without<C>>::new(&mut world);
+        let query_2 = QueryState::<&B, Without<C>>::new(&mut world);
+        let mut new_query: QueryState<Entity, ()> = query_1.join_filtered(&world, &query_2);
+
+        assert_eq!(new_query.single(&world), entity_ab);
+    }
+
+    #[test]
+    fn join_with_get() {
+        let mut world = World::new();
+        world.spawn(A(0));
+        world.spawn(B(1));
+        let entity_ab = world.spawn((A(2), B(3))).id();
+        let entity_abc = world.spawn((A(4), B(5), C(6))).id();
+
+        let query_1 = QueryState::<&A>::new(&mut world);
+        let query_2 = QueryState::<&B, Without<C>>::new(&mut world);
+        let mut new_query: QueryState<Entity, ()> = query_1.join_filtered(&world, &query_2);
+
+        assert!(new_query.get(&world, entity_ab).is_ok());
+        // should not be able to get entity with c.
+        assert!(new_query.get(&world, entity_abc).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Joined state for (&bevy_ecs::query::state::tests::C, ()) \
+            attempts to access terms that are not allowed by state \
+            (&bevy_ecs::query::state::tests::A, ()) joined with (&bevy_ecs::query::state::tests::B, ()).")]
+    fn cannot_join_wrong_fetch() {
+        let mut world = World::new();
+        world.init_component::<C>();
+        let query_1 = QueryState::<&A>::new(&mut world);
+        let query_2 = QueryState::<&B>::new(&mut world);
+        let _query: QueryState<&C> = query_1.join(&world, &query_2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Joining queries initialized on different worlds is not allowed.")]
+    fn cannot_join_queries_from_different_worlds() {
+        let mut world_1 = World::new();
+        let mut world_2 = World::new();
+        let query_1 = QueryState::<&A>::new(&mut world_1);
+        let query_2 = QueryState::<&B>::new(&mut world_2);
+        let _query: QueryState<Entity> = query_1.join(&world_1, &query_2);
+    }
 }
 
 /// An error that occurs when evaluating a [`Query`](crate::system::Query) or [`QueryState`] as a single expected result via
@@ -3,7 +3,7 @@ use crate::{
     entity::{Entity, EntityMap, EntityMapper, MapEntities},
     world::World,
 };
-use bevy_reflect::FromType;
+use bevy_reflect::{FromType, Reflect};
 
 /// For a specific type of component, this maps any fields with values of type [`Entity`] to a new world.
 /// Since a given `Entity` ID is only valid for the world it came from, when performing deserialization
@@ -14,6 +14,7 @@ use bevy_reflect::FromType;
 pub struct ReflectMapEntities {
     map_all_entities: fn(&mut World, &mut EntityMapper),
     map_entities: fn(&mut World, &mut EntityMapper, &[Entity]),
+    map_reflect_entities: fn(&mut dyn Reflect, &mut EntityMapper),
 }
 
 impl ReflectMapEntities {
@@ -41,11 +42,41 @@ impl ReflectMapEntities {
             (self.map_entities)(world, mapper, entities);
         });
     }
+
+    /// Applies [`MapEntities`] behavior to a component value that is not stored in a [`World`] yet.
+    ///
+    /// Mapping a component before inserting it lets its [hooks](crate::component::ComponentHooks),
+    /// such as the ones keeping [relationships](crate::relationship) in sync, see the mapped entities.
+    /// `component` must be a value of the concrete component type, otherwise it is left untouched.
+    pub fn map_reflect_entities(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        component: &mut dyn Reflect,
+    ) {
+        entity_map.world_scope(world, |_, mapper| {
+            (self.map_reflect_entities)(component, mapper);
+        });
+    }
+
+    /// Like [`map_reflect_entities`](Self::map_reflect_entities), but with an existing [`EntityMapper`].
+    pub fn map_reflect_entities_with_mapper(
+        &self,
+        component: &mut dyn Reflect,
+        entity_mapper: &mut EntityMapper,
+    ) {
+        (self.map_reflect_entities)(component, entity_mapper);
+    }
 }
 
-impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
+impl<C: Component + MapEntities + Reflect> FromType<C> for ReflectMapEntities {
     fn from_type() -> Self {
         ReflectMapEntities {
+            map_reflect_entities: |component, entity_mapper| {
+                if let Some(component) = component.downcast_mut::<C>() {
+                    component.map_entities(entity_mapper);
+                }
+            },
             map_entities: |world, entity_mapper, entities| {
                 for &entity in entities {
                     if let Some(mut component) = world.get_mut::<C>(entity) {
@@ -0,0 +1,401 @@
+//! Relationships between entities, kept in sync automatically.
+//!
+//! A relationship is made of two components:
+//! - a [`Relationship`] component on the *source* entity, pointing at a single target entity, and
+//! - a [`RelationshipTarget`] component on the *target* entity, listing all of its sources.
+//!
+//! Only the [`Relationship`] side should be inserted or removed by users. Hooks registered for
+//! both components keep the [`RelationshipTarget`] up to date, and apply the target's
+//! [`RelationshipDespawnBehavior`] to its sources when the target is despawned.
+//!
+//! Both traits can be derived alongside [`Component`] for structs with a single field:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::relationship::RelationshipTarget;
+//! /// Placed on an item to store it in an inventory.
+//! #[derive(Component)]
+//! #[relationship(relationship_target = InventoryContents)]
+//! struct InInventory(Entity);
+//!
+//! /// Lists the items stored in an inventory. Items are despawned along with their inventory.
+//! #[derive(Component)]
+//! #[relationship_target(relationship = InInventory, despawn_behavior = "Cascade")]
+//! struct InventoryContents(Vec<Entity>);
+//!
+//! let mut world = World::new();
+//! let inventory = world.spawn_empty().id();
+//! let sword = world.spawn(InInventory(inventory)).id();
+//! let shield = world.spawn(InInventory(inventory)).id();
+//!
+//! let contents = world.get::<InventoryContents>(inventory).unwrap();
+//! assert_eq!(contents.iter().collect::<Vec<_>>(), [sword, shield]);
+//!
+//! world.despawn(inventory);
+//! assert!(world.get_entity(sword).is_none());
+//! assert!(world.get_entity(shield).is_none());
+//! ```
+
+use crate::{
+    component::{Component, ComponentId},
+    entity::Entity,
+    world::{DeferredWorld, World},
+};
+use bevy_utils::tracing::warn;
+use smallvec::SmallVec;
+
+/// The source side of a relationship: a [`Component`] that points at a single target entity.
+///
+/// Inserting this component adds the entity to the target's [`RelationshipTarget`], inserting
+/// that component on the target if needed. Replacing or removing it removes the entity from the
+/// old target's [`RelationshipTarget`], which is itself removed once empty.
+///
+/// The hooks that maintain the relationship must be registered with
+/// [`Component::register_component_hooks`], which `#[derive(Component)]` does when the
+/// `#[relationship(relationship_target = T)]` attribute is used.
+pub trait Relationship: Component + Sized {
+    /// The component that lists the sources on the target entity.
+    type RelationshipTarget: RelationshipTarget<Relationship = Self>;
+
+    /// Returns the target entity of this relationship.
+    fn get(&self) -> Entity;
+
+    /// Creates a relationship pointing at `entity`.
+    fn from(entity: Entity) -> Self;
+
+    /// The `on_insert` hook that adds `entity` to its target's [`RelationshipTarget`].
+    fn on_insert(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let target = world.get::<Self>(entity).unwrap().get();
+        if target == entity {
+            warn!(
+                "The {} relationship on {entity:?} points at itself and was removed.",
+                std::any::type_name::<Self>()
+            );
+            world.commands().entity(entity).remove::<Self>();
+            return;
+        }
+        if let Some(mut relationship_target) = world.get_mut::<Self::RelationshipTarget>(target) {
+            relationship_target.sources_mut().add(entity);
+        } else if world.get_entity(target).is_some() {
+            // Inserting the target component is a structural change, so it is deferred.
+            world.commands().add(move |world: &mut World| {
+                // The relationship may have changed before the command was applied.
+                if world.get::<Self>(entity).map(Self::get) != Some(target) {
+                    return;
+                }
+                let Some(mut target) = world.get_entity_mut(target) else {
+                    return;
+                };
+                if let Some(mut relationship_target) = target.get_mut::<Self::RelationshipTarget>()
+                {
+                    relationship_target.sources_mut().add(entity);
+                } else {
+                    let mut sources =
+                        <Self::RelationshipTarget as RelationshipTarget>::Sources::default();
+                    sources.add(entity);
+                    target.insert(Self::RelationshipTarget::from_sources(sources));
+                }
+            });
+        } else {
+            warn!(
+                "The {} relationship on {entity:?} points at {target:?}, which does not exist. The relationship was removed.",
+                std::any::type_name::<Self>()
+            );
+            world.commands().entity(entity).remove::<Self>();
+        }
+    }
+
+    /// The `on_replace` hook that removes `entity` from its old target's [`RelationshipTarget`],
+    /// removing that component if `entity` was its last source.
+    fn on_replace(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let target = world.get::<Self>(entity).unwrap().get();
+        let Some(mut relationship_target) = world.get_mut::<Self::RelationshipTarget>(target)
+        else {
+            return;
+        };
+        // `entity` may already have been removed from the sources by hand, in which case the
+        // target component is left as it is, even if it is empty.
+        if !relationship_target.sources().as_slice().contains(&entity) {
+            return;
+        }
+        relationship_target.sources_mut().remove(entity);
+        if relationship_target.is_empty() {
+            world.commands().add(move |world: &mut World| {
+                let Some(mut target) = world.get_entity_mut(target) else {
+                    return;
+                };
+                // Sources may have been added before the command was applied.
+                if target
+                    .get::<Self::RelationshipTarget>()
+                    .map_or(false, RelationshipTarget::is_empty)
+                {
+                    target.remove::<Self::RelationshipTarget>();
+                }
+            });
+        }
+    }
+}
+
+/// What happens to the sources of a relationship when its [`RelationshipTarget`] is removed,
+/// most commonly because the target entity was despawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationshipDespawnBehavior {
+    /// The sources are despawned too, along with their own sources when they are targets of
+    /// cascading relationships themselves.
+    Cascade,
+    /// The sources keep their [`Relationship`] component, which keeps pointing at the old target.
+    ///
+    /// This is only useful when the target is never expected to go away before its sources,
+    /// or when dangling relationships are handled elsewhere.
+    Orphan,
+    /// The [`Relationship`] component is removed from the sources, which are otherwise untouched.
+    Remove,
+}
+
+/// The target side of a relationship: a [`Component`] that lists every entity whose
+/// [`Relationship`] points at this one.
+///
+/// This component is maintained by the hooks of its [`Relationship`] and should not be inserted
+/// or modified by hand, except for reordering its sources.
+pub trait RelationshipTarget: Component + Sized {
+    /// The component that points at this entity from each source.
+    type Relationship: Relationship<RelationshipTarget = Self>;
+
+    /// The collection used to store the sources.
+    type Sources: RelationshipSources;
+
+    /// What happens to the sources when this component is removed.
+    const DESPAWN_BEHAVIOR: RelationshipDespawnBehavior;
+
+    /// Returns the sources of this relationship target.
+    fn sources(&self) -> &Self::Sources;
+
+    /// Returns the sources of this relationship target mutably.
+    ///
+    /// Adding or removing sources without updating their [`Relationship`] components
+    /// breaks the relationship: this should only be used to reorder them.
+    fn sources_mut(&mut self) -> &mut Self::Sources;
+
+    /// Creates the component from a collection of sources.
+    fn from_sources(sources: Self::Sources) -> Self;
+
+    /// Iterates over the sources of this relationship target.
+    fn iter(&self) -> std::iter::Copied<std::slice::Iter<'_, Entity>> {
+        self.sources().as_slice().iter().copied()
+    }
+
+    /// Returns the number of sources.
+    fn len(&self) -> usize {
+        self.sources().as_slice().len()
+    }
+
+    /// Returns `true` if there are no sources.
+    fn is_empty(&self) -> bool {
+        self.sources().as_slice().is_empty()
+    }
+
+    /// The `on_remove` hook that applies [`RelationshipTarget::DESPAWN_BEHAVIOR`] to the sources.
+    fn on_remove(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        if Self::DESPAWN_BEHAVIOR == RelationshipDespawnBehavior::Orphan {
+            return;
+        }
+        let sources: Vec<Entity> = world.get::<Self>(entity).unwrap().iter().collect();
+        world.commands().add(move |world: &mut World| {
+            for source in sources {
+                // Skip sources that were given another target before the command was applied.
+                if world
+                    .get::<Self::Relationship>(source)
+                    .map(Relationship::get)
+                    != Some(entity)
+                {
+                    continue;
+                }
+                match Self::DESPAWN_BEHAVIOR {
+                    RelationshipDespawnBehavior::Cascade => {
+                        world.despawn(source);
+                    }
+                    RelationshipDespawnBehavior::Remove => {
+                        world.entity_mut(source).remove::<Self::Relationship>();
+                    }
+                    RelationshipDespawnBehavior::Orphan => {}
+                }
+            }
+        });
+    }
+}
+
+/// A collection of entities used to store the sources of a [`RelationshipTarget`].
+pub trait RelationshipSources: Default + Send + Sync + 'static {
+    /// Adds `entity` to the end of the collection, unless it is already present.
+    fn add(&mut self, entity: Entity);
+
+    /// Removes `entity` from the collection, if present.
+    fn remove(&mut self, entity: Entity);
+
+    /// Returns the entities in the collection.
+    fn as_slice(&self) -> &[Entity];
+}
+
+impl RelationshipSources for Vec<Entity> {
+    fn add(&mut self, entity: Entity) {
+        if !self.contains(&entity) {
+            self.push(entity);
+        }
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        self.retain(|&e| e != entity);
+    }
+
+    fn as_slice(&self) -> &[Entity] {
+        self
+    }
+}
+
+impl<const N: usize> RelationshipSources for SmallVec<[Entity; N]> {
+    fn add(&mut self, entity: Entity) {
+        if !self.contains(&entity) {
+            self.push(entity);
+        }
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        self.retain(|e| *e != entity);
+    }
+
+    fn as_slice(&self) -> &[Entity] {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::relationship::RelationshipTarget;
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Owned)]
+    struct OwnedBy(Entity);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = OwnedBy, despawn_behavior = "Remove")]
+    struct Owned(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Attachments)]
+    struct AttachedTo {
+        socket: Entity,
+    }
+
+    #[derive(Component)]
+    #[relationship_target(relationship = AttachedTo, despawn_behavior = "Cascade")]
+    struct Attachments(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Targeters)]
+    struct Targeting(Entity);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = Targeting, despawn_behavior = "Orphan")]
+    struct Targeters(Vec<Entity>);
+
+    fn sources<T: RelationshipTarget>(world: &World, entity: Entity) -> Option<Vec<Entity>> {
+        world.get::<T>(entity).map(|target| target.iter().collect())
+    }
+
+    #[test]
+    fn insert_and_remove_relationship() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let a = world.spawn(OwnedBy(owner)).id();
+        let b = world.spawn(OwnedBy(owner)).id();
+        assert_eq!(sources::<Owned>(&world, owner), Some(vec![a, b]));
+
+        world.entity_mut(a).remove::<OwnedBy>();
+        assert_eq!(sources::<Owned>(&world, owner), Some(vec![b]));
+
+        world.entity_mut(b).remove::<OwnedBy>();
+        assert_eq!(sources::<Owned>(&world, owner), None);
+    }
+
+    #[test]
+    fn replace_relationship() {
+        let mut world = World::new();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        let item = world.spawn(OwnedBy(first)).id();
+
+        world.entity_mut(item).insert(OwnedBy(second));
+        assert_eq!(sources::<Owned>(&world, first), None);
+        assert_eq!(sources::<Owned>(&world, second), Some(vec![item]));
+    }
+
+    #[test]
+    fn despawn_source() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let a = world.spawn(OwnedBy(owner)).id();
+        let b = world.spawn(OwnedBy(owner)).id();
+
+        world.despawn(a);
+        assert_eq!(sources::<Owned>(&world, owner), Some(vec![b]));
+    }
+
+    #[test]
+    fn despawn_behavior_remove() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let item = world.spawn(OwnedBy(owner)).id();
+
+        world.despawn(owner);
+        assert!(world.get_entity(item).is_some());
+        assert!(world.get::<OwnedBy>(item).is_none());
+    }
+
+    #[test]
+    fn despawn_behavior_cascade() {
+        let mut world = World::new();
+        let socket = world.spawn_empty().id();
+        let attachment = world.spawn(AttachedTo { socket }).id();
+        let nested = world.spawn(AttachedTo { socket: attachment }).id();
+        let unrelated = world.spawn_empty().id();
+
+        world.despawn(socket);
+        assert!(world.get_entity(attachment).is_none());
+        assert!(world.get_entity(nested).is_none());
+        assert!(world.get_entity(unrelated).is_some());
+    }
+
+    #[test]
+    fn despawn_behavior_orphan() {
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        let targeter = world.spawn(Targeting(target)).id();
+
+        world.despawn(target);
+        assert_eq!(world.get::<Targeting>(targeter).unwrap().0, target);
+    }
+
+    #[test]
+    fn relationship_to_self_is_removed() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        world.entity_mut(entity).insert(OwnedBy(entity));
+        assert!(world.get::<OwnedBy>(entity).is_none());
+        assert!(world.get::<Owned>(entity).is_none());
+    }
+
+    #[test]
+    fn relationships_with_commands() {
+        let mut world = World::new();
+        let mut queue = bevy_ecs::system::CommandQueue::default();
+        let owner = world.spawn_empty().id();
+        let item = {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.spawn(OwnedBy(owner)).id()
+        };
+        queue.apply(&mut world);
+        assert_eq!(sources::<Owned>(&world, owner), Some(vec![item]));
+    }
+}
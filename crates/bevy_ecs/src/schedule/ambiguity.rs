@@ -0,0 +1,333 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::export::{write_json_string, write_json_strings};
+use crate::{
+    component::{ComponentId, Components},
+    world::World,
+};
+
+/// A structured list of the pairs of systems in a [`Schedule`](super::Schedule) that have
+/// conflicting data access but no defined execution order.
+///
+/// Returned by [`Schedule::initialize`](super::Schedule::initialize). Ambiguities are sorted by
+/// system name, so reports can be stored (see [`to_json`](ScheduleAmbiguityReport::to_json))
+/// and compared between builds, for example to fail CI when
+/// [`new_since`](ScheduleAmbiguityReport::new_since) a known baseline returns anything.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::ScheduleAmbiguityReport;
+/// #[derive(Resource)]
+/// struct Counter(u32);
+///
+/// fn increment(mut counter: ResMut<Counter>) { counter.0 += 1; }
+/// fn reset(mut counter: ResMut<Counter>) { counter.0 = 0; }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Counter(0));
+/// let mut schedule = Schedule::new();
+/// schedule.add_systems((increment, reset));
+///
+/// let report = schedule.initialize(&mut world).unwrap();
+/// assert_eq!(report.len(), 1);
+///
+/// let baseline = ScheduleAmbiguityReport::default();
+/// assert_eq!(report.new_since(&baseline).count(), 1);
+/// assert!(report.to_json().contains("conflicts"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleAmbiguityReport {
+    /// The ambiguous pairs of systems.
+    pub ambiguities: Vec<SystemAmbiguity>,
+}
+
+/// A pair of systems with conflicting data access and no defined execution order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SystemAmbiguity {
+    /// The system whose name sorts first.
+    pub first: AmbiguousSystem,
+    /// The system whose name sorts last.
+    pub second: AmbiguousSystem,
+    /// The data both systems access, with at least one of them mutably.
+    pub conflicts: ConflictingAccess,
+}
+
+/// A system taking part in a [`SystemAmbiguity`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct AmbiguousSystem {
+    /// The name of the system.
+    pub name: String,
+    /// Where the system was added from, usually the name of a plugin.
+    ///
+    /// See [`Schedule::add_systems_with_origin`](super::Schedule::add_systems_with_origin).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+}
+
+/// The data access that makes two systems ambiguous with each other.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictingAccess {
+    /// At least one of the systems is exclusive, so it conflicts on the whole [`World`].
+    World,
+    /// The systems conflict on these components and resources.
+    Data {
+        /// The names of the conflicting components.
+        components: Vec<String>,
+        /// The names of the conflicting resources.
+        resources: Vec<String>,
+    },
+}
+
+impl ScheduleAmbiguityReport {
+    pub(crate) fn new(ambiguities: impl IntoIterator<Item = SystemAmbiguity>) -> Self {
+        let mut ambiguities: Vec<_> = ambiguities.into_iter().collect();
+        ambiguities.sort();
+        Self { ambiguities }
+    }
+
+    /// Returns `true` if the schedule has no ambiguities.
+    pub fn is_empty(&self) -> bool {
+        self.ambiguities.is_empty()
+    }
+
+    /// Returns the number of ambiguous pairs of systems.
+    pub fn len(&self) -> usize {
+        self.ambiguities.len()
+    }
+
+    /// Iterates over the ambiguities that are not part of `baseline`.
+    ///
+    /// The ambiguities of `baseline` do not need to be sorted.
+    pub fn new_since<'a>(
+        &'a self,
+        baseline: &'a ScheduleAmbiguityReport,
+    ) -> impl Iterator<Item = &'a SystemAmbiguity> + 'a {
+        let mut baseline: Vec<_> = baseline.ambiguities.iter().collect();
+        baseline.sort_unstable();
+        self.ambiguities
+            .iter()
+            .filter(move |ambiguity| baseline.binary_search(ambiguity).is_err())
+    }
+
+    /// Formats the report as JSON, with one ambiguity per line so that reports diff cleanly.
+    ///
+    /// The output matches the [`Serialize`] implementation of the report, so it can be read back
+    /// with any JSON deserializer.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"ambiguities\":[");
+        for (i, ambiguity) in self.ambiguities.iter().enumerate() {
+            json.push_str(if i == 0 { "\n" } else { ",\n" });
+            json.push_str("{\"first\":");
+            ambiguity.first.write_json(&mut json);
+            json.push_str(",\"second\":");
+            ambiguity.second.write_json(&mut json);
+            json.push_str(",\"conflicts\":");
+            match &ambiguity.conflicts {
+                ConflictingAccess::World => json.push_str("\"world\""),
+                ConflictingAccess::Data {
+                    components,
+                    resources,
+                } => {
+                    json.push_str("{\"data\":{\"components\":");
+                    write_json_strings(&mut json, components);
+                    json.push_str(",\"resources\":");
+                    write_json_strings(&mut json, resources);
+                    json.push_str("}}");
+                }
+            }
+            json.push('}');
+        }
+        if !self.ambiguities.is_empty() {
+            json.push('\n');
+        }
+        json.push_str("]}\n");
+        json
+    }
+}
+
+impl AmbiguousSystem {
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"name\":");
+        write_json_string(json, &self.name);
+        if let Some(origin) = &self.origin {
+            json.push_str(",\"origin\":");
+            write_json_string(json, origin);
+        }
+        json.push('}');
+    }
+}
+
+impl SystemAmbiguity {
+    pub(crate) fn new(
+        a: AmbiguousSystem,
+        b: AmbiguousSystem,
+        conflicts: ConflictingAccess,
+    ) -> Self {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        Self {
+            first,
+            second,
+            conflicts,
+        }
+    }
+}
+
+impl ConflictingAccess {
+    pub(crate) fn from_ids(ids: &[ComponentId], components: &Components) -> Self {
+        let mut conflicting_components = Vec::new();
+        let mut conflicting_resources = Vec::new();
+        for &id in ids {
+            let Some(info) = components.get_info(id) else {
+                continue;
+            };
+            let is_resource = info
+                .type_id()
+                .and_then(|type_id| components.get_resource_id(type_id))
+                == Some(id);
+            if is_resource {
+                conflicting_resources.push(info.name().to_string());
+            } else {
+                conflicting_components.push(info.name().to_string());
+            }
+        }
+        for names in [&mut conflicting_components, &mut conflicting_resources] {
+            names.sort();
+            names.dedup();
+        }
+        ConflictingAccess::Data {
+            components: conflicting_components,
+            resources: conflicting_resources,
+        }
+    }
+}
+
+impl fmt::Display for AmbiguousSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(origin) = &self.origin {
+            write!(f, " (added by {origin})")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ConflictingAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConflictingAccess::World => write!(f, "{}", std::any::type_name::<World>()),
+            ConflictingAccess::Data {
+                components,
+                resources,
+            } => {
+                let mut parts = Vec::new();
+                if !components.is_empty() {
+                    parts.push(format!("components {components:?}"));
+                }
+                if !resources.is_empty() {
+                    parts.push(format!("resources {resources:?}"));
+                }
+                write!(f, "{}", parts.join(", "))
+            }
+        }
+    }
+}
+
+/// Lists the ambiguities grouped by their first system.
+impl fmt::Display for ScheduleAmbiguityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} pairs of systems with conflicting data access have indeterminate execution order. \
+            Consider adding `before`, `after`, or `ambiguous_with` relationships between these:",
+            self.len()
+        )?;
+        let mut previous: Option<&AmbiguousSystem> = None;
+        for ambiguity in &self.ambiguities {
+            if previous != Some(&ambiguity.first) {
+                writeln!(f, " -- {}", ambiguity.first)?;
+                previous = Some(&ambiguity.first);
+            }
+            writeln!(f, "    and {}", ambiguity.second)?;
+            writeln!(f, "        conflict on: {}", ambiguity.conflicts)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AmbiguousSystem, ConflictingAccess, ScheduleAmbiguityReport, SystemAmbiguity};
+
+    fn system(name: &str, origin: Option<&str>) -> AmbiguousSystem {
+        AmbiguousSystem {
+            name: name.to_string(),
+            origin: origin.map(str::to_string),
+        }
+    }
+
+    fn report() -> ScheduleAmbiguityReport {
+        ScheduleAmbiguityReport::new([
+            SystemAmbiguity::new(
+                system("physics::step", Some("PhysicsPlugin")),
+                system("app::teleport", None),
+                ConflictingAccess::Data {
+                    components: vec!["Transform".to_string()],
+                    resources: vec![],
+                },
+            ),
+            SystemAmbiguity::new(
+                system("app::save \"slot\"", None),
+                system("app::teleport", None),
+                ConflictingAccess::World,
+            ),
+        ])
+    }
+
+    #[test]
+    fn ambiguities_are_sorted() {
+        let report = report();
+        assert_eq!(report.ambiguities[0].first.name, "app::save \"slot\"");
+        assert_eq!(report.ambiguities[1].first.name, "app::teleport");
+        assert_eq!(report.ambiguities[1].second.name, "physics::step");
+    }
+
+    #[test]
+    fn new_since_baseline() {
+        let mut baseline = report();
+        baseline.ambiguities.remove(0);
+        let report = report();
+        let new: Vec<_> = report.new_since(&baseline).collect();
+        assert_eq!(new, vec![&report.ambiguities[0]]);
+    }
+
+    #[test]
+    fn new_since_unsorted_baseline() {
+        let mut baseline = report();
+        baseline.ambiguities.reverse();
+        let report = report();
+        assert_eq!(report.new_since(&baseline).count(), 0);
+    }
+
+    #[test]
+    fn to_json() {
+        assert_eq!(
+            report().to_json(),
+            concat!(
+                r#"{"ambiguities":["#,
+                "\n",
+                r#"{"first":{"name":"app::save \"slot\""},"second":{"name":"app::teleport"},"conflicts":"world"},"#,
+                "\n",
+                r#"{"first":{"name":"app::teleport"},"second":{"name":"physics::step","origin":"PhysicsPlugin"},"conflicts":{"data":{"components":["Transform"],"resources":[]}}}"#,
+                "\n",
+                "]}\n",
+            )
+        );
+        assert_eq!(
+            ScheduleAmbiguityReport::default().to_json(),
+            "{\"ambiguities\":[]}\n"
+        );
+    }
+}
@@ -1045,6 +1045,11 @@ where
         !self.condition.run(input, world)
     }
 
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: The inner condition system asserts its own safety.
+        self.condition.validate_param_unsafe(world)
+    }
+
     fn apply_deferred(&mut self, world: &mut World) {
         self.condition.apply_deferred(world);
     }
@@ -6,7 +6,7 @@ use crate::{
         graph_utils::{Ambiguity, Dependency, DependencyKind, GraphInfo},
         set::{BoxedSystemSet, IntoSystemSet, SystemSet},
     },
-    system::{BoxedSystem, IntoSystem, System},
+    system::{BoxedSystem, FallibleSystem, IntoSystem, System, SystemError},
 };
 
 fn new_condition<M>(condition: impl Condition<M>) -> BoxedCondition {
@@ -32,7 +32,13 @@ fn ambiguous_with(graph_info: &mut GraphInfo, set: BoxedSystemSet) {
     }
 }
 
-impl<Marker, F> IntoSystemConfigs<Marker> for F
+#[doc(hidden)]
+pub struct InfallibleSystemMarker;
+
+#[doc(hidden)]
+pub struct FallibleSystemMarker;
+
+impl<Marker, F> IntoSystemConfigs<(InfallibleSystemMarker, Marker)> for F
 where
     F: IntoSystem<(), (), Marker>,
 {
@@ -41,6 +47,18 @@ where
     }
 }
 
+/// Systems returning `Result<(), E>` have their errors passed to the
+/// [`SystemErrorHandler`](crate::system::SystemErrorHandler).
+impl<Marker, E, F> IntoSystemConfigs<(FallibleSystemMarker, E, Marker)> for F
+where
+    F: IntoSystem<(), Result<(), E>, Marker>,
+    E: Into<SystemError> + 'static,
+{
+    fn into_configs(self) -> SystemConfigs {
+        SystemConfigs::new_system(Box::new(FallibleSystem::new(IntoSystem::into_system(self))))
+    }
+}
+
 impl IntoSystemConfigs<()> for BoxedSystem<(), ()> {
     fn into_configs(self) -> SystemConfigs {
         SystemConfigs::new_system(self)
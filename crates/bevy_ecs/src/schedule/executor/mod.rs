@@ -6,6 +6,7 @@ pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
 
+use bevy_utils::Duration;
 use fixedbitset::FixedBitSet;
 
 use crate::{
@@ -18,7 +19,17 @@ use crate::{
 pub(super) trait SystemExecutor: Send + Sync {
     fn kind(&self) -> ExecutorKind;
     fn init(&mut self, schedule: &SystemSchedule);
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World);
+    /// Runs the schedule, treating the systems in `skip_systems` as if they had already completed.
+    ///
+    /// If `system_durations` is provided, the run duration of each system that runs is written
+    /// to the slot matching its index in the schedule.
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        system_durations: Option<&mut [Option<Duration>]>,
+    );
     fn set_apply_final_deferred(&mut self, value: bool);
 }
 
@@ -4,10 +4,10 @@ use std::{
 };
 
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
-use bevy_utils::default;
 use bevy_utils::syncunsafecell::SyncUnsafeCell;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Instrument};
+use bevy_utils::{default, Duration, Instant};
 use std::panic::AssertUnwindSafe;
 
 use async_channel::{Receiver, Sender};
@@ -68,6 +68,8 @@ struct SystemTaskMetadata {
 struct SystemResult {
     system_index: usize,
     success: bool,
+    /// How long the system took to run, if timings are being recorded.
+    duration: Option<Duration>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -108,6 +110,8 @@ pub struct MultiThreadedExecutor {
     unapplied_systems: FixedBitSet,
     /// Setting when true applies deferred system buffers after all systems have run
     apply_final_deferred: bool,
+    /// Setting when true measures how long each system takes to run.
+    record_timings: bool,
     /// When set, tells the executor that a thread has panicked.
     panic_payload: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
     /// When set, stops the executor from running any more systems.
@@ -159,7 +163,13 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.num_dependencies_remaining = Vec::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        mut system_durations: Option<&mut [Option<Duration>]>,
+    ) {
         // reset counts
         self.num_systems = schedule.systems.len();
         if self.num_systems == 0 {
@@ -167,6 +177,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         }
         self.num_running_systems = 0;
         self.num_completed_systems = 0;
+        self.record_timings = system_durations.is_some();
         self.num_dependencies_remaining.clear();
         self.num_dependencies_remaining
             .extend_from_slice(&schedule.system_dependencies);
@@ -177,6 +188,16 @@ impl SystemExecutor for MultiThreadedExecutor {
             }
         }
 
+        // systems skipped by `Stepping` are treated as completed, as though they had run
+        if let Some(skipped_systems) = skip_systems {
+            self.completed_systems.union_with(skipped_systems);
+            self.num_completed_systems = self.completed_systems.count_ones(..);
+            for system_index in skipped_systems.ones() {
+                self.signal_dependents(system_index);
+                self.ready_systems.set(system_index, false);
+            }
+        }
+
         let thread_executor = world
             .get_resource::<MainThreadExecutor>()
             .map(|e| e.0.clone());
@@ -206,12 +227,14 @@ impl SystemExecutor for MultiThreadedExecutor {
                         if self.num_running_systems > 0 {
                             // wait for systems to complete
                             if let Ok(result) = self.receiver.recv().await {
+                                record_duration(&mut system_durations, &result);
                                 self.finish_system_and_handle_dependents(result);
                             } else {
                                 panic!("Channel closed unexpectedly!");
                             }
 
                             while let Ok(result) = self.receiver.try_recv() {
+                                record_duration(&mut system_durations, &result);
                                 self.finish_system_and_handle_dependents(result);
                             }
 
@@ -280,6 +303,7 @@ impl MultiThreadedExecutor {
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
             apply_final_deferred: true,
+            record_timings: false,
             panic_payload: Arc::new(Mutex::new(None)),
             stop_spawning: false,
         }
@@ -323,8 +347,10 @@ impl MultiThreadedExecutor {
             self.ready_systems.set(system_index, false);
 
             // SAFETY: `can_run` returned true, which means that:
-            // - It must have called `update_archetype_component_access` for each run condition.
-            // - There can be no systems running whose accesses would conflict with any conditions.
+            // - It must have called `update_archetype_component_access` for the system
+            //   and each run condition.
+            // - There can be no systems running whose accesses would conflict with the system
+            //   or any conditions.
             if !self.should_run(system_index, system, conditions, world_cell) {
                 self.skip_system_and_signal_dependents(system_index);
                 continue;
@@ -420,14 +446,14 @@ impl MultiThreadedExecutor {
 
     /// # Safety
     /// * `world` must have permission to read any world data required by
-    ///   the system's conditions: this includes conditions for the system
+    ///   the system and its conditions: this includes conditions for the system
     ///   itself, and conditions for any of the system's sets.
     /// * `update_archetype_component` must have been called with `world`
-    ///   for each run condition in `conditions`.
+    ///   for the system and each run condition in `conditions`.
     unsafe fn should_run(
         &mut self,
         system_index: usize,
-        _system: &BoxedSystem,
+        system: &mut BoxedSystem,
         conditions: &mut Conditions,
         world: UnsafeWorldCell,
     ) -> bool {
@@ -468,6 +494,19 @@ impl MultiThreadedExecutor {
 
         should_run &= system_conditions_met;
 
+        if should_run {
+            // Skip the system if its parameters can't be created.
+            // SAFETY:
+            // - The caller ensures that `world` has permission to read any data
+            //   required by the system.
+            // - `update_archetype_component_access` has been called for the system.
+            let valid_params = unsafe { system.validate_param_unsafe(world) };
+            if !valid_params {
+                self.skipped_systems.insert(system_index);
+            }
+            should_run &= valid_params;
+        }
+
         should_run
     }
 
@@ -494,9 +533,11 @@ impl MultiThreadedExecutor {
 
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let record_timings = self.record_timings;
         let task = async move {
             #[cfg(feature = "trace")]
             let system_guard = system_span.enter();
+            let start = record_timings.then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -511,6 +552,7 @@ impl MultiThreadedExecutor {
                 .try_send(SystemResult {
                     system_index,
                     success: res.is_ok(),
+                    duration: start.map(|start| start.elapsed()),
                 })
                 .unwrap_or_else(|error| unreachable!("{}", error));
             if let Err(payload) = res {
@@ -557,6 +599,7 @@ impl MultiThreadedExecutor {
 
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let record_timings = self.record_timings;
         if is_apply_deferred(system) {
             // TODO: avoid allocation
             let unapplied_systems = self.unapplied_systems.clone();
@@ -564,6 +607,7 @@ impl MultiThreadedExecutor {
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
+                let start = record_timings.then(Instant::now);
                 let res = apply_deferred(&unapplied_systems, systems, world);
                 #[cfg(feature = "trace")]
                 drop(system_guard);
@@ -572,6 +616,7 @@ impl MultiThreadedExecutor {
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        duration: start.map(|start| start.elapsed()),
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -588,6 +633,7 @@ impl MultiThreadedExecutor {
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
+                let start = record_timings.then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     system.run((), world);
                 }));
@@ -598,6 +644,7 @@ impl MultiThreadedExecutor {
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        duration: start.map(|start| start.elapsed()),
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -624,6 +671,7 @@ impl MultiThreadedExecutor {
         let SystemResult {
             system_index,
             success,
+            ..
         } = result;
 
         if self.system_task_metadata[system_index].is_exclusive {
@@ -682,6 +730,12 @@ impl MultiThreadedExecutor {
     }
 }
 
+fn record_duration(system_durations: &mut Option<&mut [Option<Duration>]>, result: &SystemResult) {
+    if let (Some(durations), Some(duration)) = (system_durations.as_deref_mut(), result.duration) {
+        durations[result.system_index] = Some(duration);
+    }
+}
+
 fn apply_deferred(
     unapplied_systems: &FixedBitSet,
     systems: &[SyncUnsafeCell<BoxedSystem>],
@@ -720,9 +774,12 @@ unsafe fn evaluate_and_fold_conditions(
         .map(|condition| {
             #[cfg(feature = "trace")]
             let _condition_span = info_span!("condition", name = &*condition.name()).entered();
-            // SAFETY: The caller ensures that `world` has permission to
-            // access any data required by the condition.
-            unsafe { condition.run_unsafe((), world) }
+            // SAFETY:
+            // - The caller ensures that `world` has permission to
+            //   access any data required by the condition.
+            // - `update_archetype_component_access` has been called for the condition.
+            // A condition whose parameters can't be created is not met.
+            unsafe { condition.validate_param_unsafe(world) && condition.run_unsafe((), world) }
         })
         .fold(true, |acc, res| acc && res)
 }
@@ -1,5 +1,6 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::{Duration, Instant};
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
@@ -34,7 +35,18 @@ impl SystemExecutor for SimpleExecutor {
         self.completed_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        mut system_durations: Option<&mut [Option<Duration>]>,
+    ) {
+        // systems skipped by `Stepping` are treated as completed
+        if let Some(skipped_systems) = skip_systems {
+            self.completed_systems.union_with(skipped_systems);
+        }
+
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -66,6 +78,11 @@ impl SystemExecutor for SimpleExecutor {
 
             should_run &= system_conditions_met;
 
+            // systems whose parameters can't be created are skipped
+            if should_run {
+                should_run = schedule.systems[system_index].validate_param(world);
+            }
+
             #[cfg(feature = "trace")]
             should_run_span.exit();
 
@@ -77,6 +94,7 @@ impl SystemExecutor for SimpleExecutor {
             }
 
             let system = &mut schedule.systems[system_index];
+            let start = system_durations.is_some().then(Instant::now);
             #[cfg(feature = "trace")]
             let system_span = info_span!("system", name = &*name).entered();
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
@@ -88,6 +106,9 @@ impl SystemExecutor for SimpleExecutor {
                 eprintln!("Encountered a panic in system `{}`!", &*system.name());
                 std::panic::resume_unwind(payload);
             }
+            if let (Some(durations), Some(start)) = (system_durations.as_deref_mut(), start) {
+                durations[system_index] = Some(start.elapsed());
+            }
 
             system.apply_deferred(world);
         }
@@ -116,7 +137,8 @@ fn evaluate_and_fold_conditions(conditions: &mut [BoxedCondition], world: &mut W
         .map(|condition| {
             #[cfg(feature = "trace")]
             let _condition_span = info_span!("condition", name = &*condition.name()).entered();
-            condition.run((), world)
+            // a condition whose parameters can't be created is not met
+            condition.validate_param(world) && condition.run((), world)
         })
         .fold(true, |acc, res| acc && res)
 }
@@ -1,5 +1,6 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::{Duration, Instant};
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
@@ -42,7 +43,18 @@ impl SystemExecutor for SingleThreadedExecutor {
         self.unapplied_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        mut system_durations: Option<&mut [Option<Duration>]>,
+    ) {
+        // systems skipped by `Stepping` are treated as completed
+        if let Some(skipped_systems) = skip_systems {
+            self.completed_systems.union_with(skipped_systems);
+        }
+
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -74,6 +86,11 @@ impl SystemExecutor for SingleThreadedExecutor {
 
             should_run &= system_conditions_met;
 
+            // systems whose parameters can't be created are skipped
+            if should_run {
+                should_run = schedule.systems[system_index].validate_param(world);
+            }
+
             #[cfg(feature = "trace")]
             should_run_span.exit();
 
@@ -84,6 +101,7 @@ impl SystemExecutor for SingleThreadedExecutor {
                 continue;
             }
 
+            let start = system_durations.is_some().then(Instant::now);
             let system = &mut schedule.systems[system_index];
             if is_apply_deferred(system) {
                 #[cfg(feature = "trace")]
@@ -105,6 +123,10 @@ impl SystemExecutor for SingleThreadedExecutor {
                 }
                 self.unapplied_systems.insert(system_index);
             }
+
+            if let (Some(durations), Some(start)) = (system_durations.as_deref_mut(), start) {
+                durations[system_index] = Some(start.elapsed());
+            }
         }
 
         if self.apply_final_deferred {
@@ -146,7 +168,8 @@ fn evaluate_and_fold_conditions(conditions: &mut [BoxedCondition], world: &mut W
         .map(|condition| {
             #[cfg(feature = "trace")]
             let _condition_span = info_span!("condition", name = &*condition.name()).entered();
-            condition.run((), world)
+            // a condition whose parameters can't be created is not met
+            condition.validate_param(world) && condition.run((), world)
         })
         .fold(true, |acc, res| acc && res)
 }
@@ -0,0 +1,357 @@
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of the graph of a [`Schedule`](super::Schedule): its systems, system sets, the
+/// hierarchy of those sets and the ordering constraints between them.
+///
+/// Created with [`Schedule::export`](super::Schedule::export). Nodes are referred to by their
+/// `id`, which is `"system:<index>"` for systems and `"set:<index>"` for system sets.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// fn physics() {}
+/// fn render() {}
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add_systems((physics, render.after(physics)));
+///
+/// let export = schedule.export();
+/// assert_eq!(export.systems.len(), 2);
+/// println!("{}", export.to_dot());
+/// println!("{}", export.to_json());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleExport {
+    /// The label of the schedule, if it has been inserted into
+    /// [`Schedules`](super::Schedules).
+    pub label: Option<String>,
+    /// The systems of the schedule.
+    pub systems: Vec<SystemExport>,
+    /// The system sets of the schedule.
+    pub sets: Vec<SystemSetExport>,
+    /// Edges from a system set to each of its direct members.
+    pub hierarchy: Vec<EdgeExport>,
+    /// Edges from a system or set to a system or set that must run after it.
+    pub dependencies: Vec<EdgeExport>,
+}
+
+/// A system in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemExport {
+    /// The id of the node.
+    pub id: String,
+    /// The name of the system.
+    pub name: String,
+    /// Where the system was added from, usually the name of a plugin.
+    ///
+    /// See [`Schedule::add_systems_with_origin`](super::Schedule::add_systems_with_origin).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// The names of the run conditions of the system.
+    pub conditions: Vec<String>,
+    /// Whether the system needs exclusive [`World`](crate::world::World) access.
+    pub is_exclusive: bool,
+    /// Whether the system is an [`apply_deferred`](super::apply_deferred) sync point.
+    pub is_apply_deferred: bool,
+    /// The position of the system in the execution order of the schedule, or `None` if the
+    /// schedule has not been built since the system was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<usize>,
+}
+
+/// A system set in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemSetExport {
+    /// The id of the node.
+    pub id: String,
+    /// The name of the system set.
+    pub name: String,
+    /// The names of the run conditions of the system set.
+    pub conditions: Vec<String>,
+    /// Whether this is the set created implicitly for a system type, used to order against that
+    /// system.
+    pub is_system_type: bool,
+}
+
+/// A directed edge between two nodes of a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeExport {
+    /// The id of the source node.
+    pub from: String,
+    /// The id of the target node.
+    pub to: String,
+}
+
+impl ScheduleExport {
+    /// Formats the schedule as a [Graphviz](https://graphviz.org) DOT digraph.
+    ///
+    /// Systems are drawn as boxes labelled with their run conditions, `apply_deferred` sync
+    /// points as black bars and system sets as dashed ellipses. Hierarchy edges are dashed and
+    /// point from a set to its members, dependency edges are solid and point from a node to the
+    /// nodes that run after it.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph ");
+        write_dot_string(&mut dot, self.label.as_deref().unwrap_or("Schedule"));
+        dot.push_str(" {\n    rankdir=LR;\n    node [fontname=\"monospace\"];\n");
+        for system in &self.systems {
+            dot.push_str("    ");
+            write_dot_string(&mut dot, &system.id);
+            if system.is_apply_deferred {
+                dot.push_str(
+                    " [shape=box, style=filled, fillcolor=black, height=0.1, label=\"\", xlabel=",
+                );
+                write_dot_string(&mut dot, &system.name);
+            } else {
+                dot.push_str(" [shape=box, label=");
+                let mut label = system.name.clone();
+                if let Some(order) = system.order {
+                    label = format!("{order}: {label}");
+                }
+                for condition in &system.conditions {
+                    let _ = write!(label, "\nif {condition}");
+                }
+                write_dot_string(&mut dot, &label);
+            }
+            dot.push_str("];\n");
+        }
+        for set in &self.sets {
+            dot.push_str("    ");
+            write_dot_string(&mut dot, &set.id);
+            dot.push_str(if set.is_system_type {
+                " [shape=ellipse, style=dotted, label="
+            } else {
+                " [shape=ellipse, style=dashed, label="
+            });
+            let mut label = set.name.clone();
+            for condition in &set.conditions {
+                let _ = write!(label, "\nif {condition}");
+            }
+            write_dot_string(&mut dot, &label);
+            dot.push_str("];\n");
+        }
+        for (edges, attributes) in [
+            (&self.hierarchy, " [style=dashed, arrowhead=none]"),
+            (&self.dependencies, ""),
+        ] {
+            for edge in edges {
+                dot.push_str("    ");
+                write_dot_string(&mut dot, &edge.from);
+                dot.push_str(" -> ");
+                write_dot_string(&mut dot, &edge.to);
+                dot.push_str(attributes);
+                dot.push_str(";\n");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Formats the schedule as JSON, with one node or edge per line so that exports diff cleanly.
+    ///
+    /// The output matches the [`Serialize`] implementation of the export, so it can be read back
+    /// with any JSON deserializer.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"label\":");
+        match &self.label {
+            Some(label) => write_json_string(&mut json, label),
+            None => json.push_str("null"),
+        }
+        json.push_str(",\n\"systems\":[");
+        write_json_lines(&mut json, &self.systems, |json, system| {
+            json.push_str("{\"id\":");
+            write_json_string(json, &system.id);
+            json.push_str(",\"name\":");
+            write_json_string(json, &system.name);
+            if let Some(origin) = &system.origin {
+                json.push_str(",\"origin\":");
+                write_json_string(json, origin);
+            }
+            json.push_str(",\"conditions\":");
+            write_json_strings(json, &system.conditions);
+            let _ = write!(
+                json,
+                ",\"is_exclusive\":{},\"is_apply_deferred\":{}",
+                system.is_exclusive, system.is_apply_deferred
+            );
+            if let Some(order) = system.order {
+                let _ = write!(json, ",\"order\":{order}");
+            }
+            json.push('}');
+        });
+        json.push_str(",\n\"sets\":[");
+        write_json_lines(&mut json, &self.sets, |json, set| {
+            json.push_str("{\"id\":");
+            write_json_string(json, &set.id);
+            json.push_str(",\"name\":");
+            write_json_string(json, &set.name);
+            json.push_str(",\"conditions\":");
+            write_json_strings(json, &set.conditions);
+            let _ = write!(json, ",\"is_system_type\":{}}}", set.is_system_type);
+        });
+        for (key, edges) in [
+            ("hierarchy", &self.hierarchy),
+            ("dependencies", &self.dependencies),
+        ] {
+            let _ = write!(json, ",\n\"{key}\":[");
+            write_json_lines(&mut json, edges, |json, edge| {
+                json.push_str("{\"from\":");
+                write_json_string(json, &edge.from);
+                json.push_str(",\"to\":");
+                write_json_string(json, &edge.to);
+                json.push('}');
+            });
+        }
+        json.push_str("}\n");
+        json
+    }
+}
+
+/// Writes `items` as the rest of a JSON array, one item per line, including the closing `]`.
+fn write_json_lines<T>(
+    json: &mut String,
+    items: &[T],
+    mut write_item: impl FnMut(&mut String, &T),
+) {
+    for (i, item) in items.iter().enumerate() {
+        json.push_str(if i == 0 { "\n" } else { ",\n" });
+        write_item(json, item);
+    }
+    if !items.is_empty() {
+        json.push('\n');
+    }
+    json.push(']');
+}
+
+pub(super) fn write_json_strings(json: &mut String, values: &[String]) {
+    json.push('[');
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write_json_string(json, value);
+    }
+    json.push(']');
+}
+
+pub(super) fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+fn write_dot_string(dot: &mut String, value: &str) {
+    dot.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => dot.push_str("\\\""),
+            '\\' => dot.push_str("\\\\"),
+            '\n' => dot.push_str("\\n"),
+            c => dot.push(c),
+        }
+    }
+    dot.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{
+        prelude::*,
+        schedule::{apply_deferred, ScheduleExport},
+    };
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Physics;
+
+    fn step() {}
+    fn integrate() {}
+    fn render() {}
+
+    fn schedule() -> Schedule {
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(Physics.run_if(|| true))
+            .add_systems((
+                (step, integrate).chain().in_set(Physics),
+                apply_deferred.after(Physics),
+                render.after(apply_deferred),
+            ));
+        schedule
+    }
+
+    fn id_of<'a>(export: &'a ScheduleExport, suffix: &str) -> &'a str {
+        &export
+            .systems
+            .iter()
+            .find(|system| system.name.ends_with(suffix))
+            .unwrap()
+            .id
+    }
+
+    #[test]
+    fn exports_nodes_and_edges() {
+        let mut world = World::new();
+        let mut schedule = schedule();
+        let before = schedule.export();
+        assert!(before.systems.iter().all(|system| system.order.is_none()));
+
+        schedule.initialize(&mut world).unwrap();
+        let export = schedule.export();
+        assert_eq!(export.systems.len(), 4);
+        let mut orders: Vec<_> = export.systems.iter().map(|s| s.order.unwrap()).collect();
+        orders.sort();
+        assert_eq!(orders, vec![0, 1, 2, 3]);
+        assert_eq!(
+            export.systems.iter().map(|s| &s.name).collect::<Vec<_>>(),
+            before.systems.iter().map(|s| &s.name).collect::<Vec<_>>()
+        );
+        assert!(export.systems.iter().any(|system| system.is_apply_deferred));
+
+        let physics = export
+            .sets
+            .iter()
+            .find(|set| set.name == "Physics")
+            .unwrap();
+        assert_eq!(physics.conditions.len(), 1);
+        assert!(!physics.is_system_type);
+
+        let step = id_of(&export, "step");
+        let integrate = id_of(&export, "integrate");
+        assert!(export
+            .hierarchy
+            .iter()
+            .any(|edge| edge.from == physics.id && edge.to == step));
+        assert!(export
+            .dependencies
+            .iter()
+            .any(|edge| edge.from == step && edge.to == integrate));
+    }
+
+    #[test]
+    fn formats_dot_and_json() {
+        let export = schedule().export();
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph \"Schedule\" {\n"));
+        assert!(dot.contains("\"Physics\\nif "));
+        assert!(dot.ends_with("}\n"));
+
+        let json = export.to_json();
+        assert!(json.starts_with("{\"label\":null,\n\"systems\":[\n{\"id\":\"system:0\""));
+        assert!(json.contains("\"is_apply_deferred\":true"));
+        assert_eq!(
+            ScheduleExport::default().to_json(),
+            "{\"label\":null,\n\"systems\":[],\n\"sets\":[],\n\"hierarchy\":[],\n\"dependencies\":[]}\n"
+        );
+    }
+}
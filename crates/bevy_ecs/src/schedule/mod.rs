@@ -1,21 +1,29 @@
 //! Contains APIs for ordering systems and executing them on a [`World`](crate::world::World)
 
+mod ambiguity;
 mod condition;
 mod config;
 mod executor;
+mod export;
 mod graph_utils;
 #[allow(clippy::module_inception)]
 mod schedule;
 mod set;
 mod state;
+mod stepping;
+mod timings;
 
+pub use self::ambiguity::*;
 pub use self::condition::*;
 pub use self::config::*;
 pub use self::executor::*;
+pub use self::export::*;
 use self::graph_utils::*;
 pub use self::schedule::*;
 pub use self::set::*;
 pub use self::state::*;
+pub use self::stepping::*;
+pub use self::timings::*;
 
 pub use self::graph_utils::NodeId;
 
@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     fmt::{Debug, Write},
     result::Result,
 };
@@ -10,7 +11,7 @@ use bevy_utils::{
     petgraph::{algo::TarjanScc, prelude::*},
     thiserror::Error,
     tracing::{error, warn},
-    HashMap, HashSet,
+    HashMap, HashSet, Instant,
 };
 
 use fixedbitset::FixedBitSet;
@@ -41,8 +42,13 @@ impl Schedules {
     ///
     /// If the map already had an entry for `label`, `schedule` is inserted,
     /// and the old schedule is returned. Otherwise, `None` is returned.
-    pub fn insert(&mut self, label: impl ScheduleLabel, schedule: Schedule) -> Option<Schedule> {
+    pub fn insert(
+        &mut self,
+        label: impl ScheduleLabel,
+        mut schedule: Schedule,
+    ) -> Option<Schedule> {
         let label = label.dyn_clone();
+        schedule.label = Some(label.dyn_clone());
         self.inner.insert(label, schedule)
     }
 
@@ -87,6 +93,16 @@ impl Schedules {
             .map(|(label, schedule)| (&**label, schedule))
     }
 
+    /// Returns a [`ScheduleExport`] of every stored schedule, sorted by label.
+    ///
+    /// Schedules that are currently running are removed from `Schedules`, so they are not
+    /// included.
+    pub fn export(&self) -> Vec<ScheduleExport> {
+        let mut exports: Vec<_> = self.inner.values().map(Schedule::export).collect();
+        exports.sort_by(|a, b| a.label.cmp(&b.label));
+        exports
+    }
+
     /// Iterates the change ticks of all systems in all stored schedules and clamps any older than
     /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
     /// This prevents overflow and thus prevents false positives.
@@ -151,6 +167,8 @@ fn make_executor(kind: ExecutorKind) -> Box<dyn SystemExecutor> {
 /// }
 /// ```
 pub struct Schedule {
+    /// The label the schedule was last inserted into [`Schedules`] with.
+    label: Option<BoxedScheduleLabel>,
     graph: ScheduleGraph,
     executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
@@ -167,6 +185,7 @@ impl Schedule {
     /// Constructs an empty `Schedule`.
     pub fn new() -> Self {
         Self {
+            label: None,
             graph: ScheduleGraph::new(),
             executable: SystemSchedule::new(),
             executor: make_executor(ExecutorKind::default()),
@@ -180,6 +199,21 @@ impl Schedule {
         self
     }
 
+    /// Add a collection of systems to the schedule, recording `origin` (for example the name of
+    /// the plugin adding them) as where they came from.
+    ///
+    /// The origin is included in the [`ScheduleAmbiguityReport`] of the schedule.
+    pub fn add_systems_with_origin<M>(
+        &mut self,
+        origin: impl Into<Cow<'static, str>>,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.graph.current_origin = Some(origin.into());
+        self.graph.add_systems_inner(systems.into_configs(), false);
+        self.graph.current_origin = None;
+        self
+    }
+
     /// Configures a system set in this schedule, adding it if it does not exist.
     pub fn configure_set(&mut self, set: impl IntoSystemSetConfig) -> &mut Self {
         self.graph.configure_set(set);
@@ -224,15 +258,73 @@ impl Schedule {
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
     pub fn run(&mut self, world: &mut World) {
         world.check_change_ticks();
-        self.initialize(world).unwrap_or_else(|e| panic!("{e}"));
-        self.executor.run(&mut self.executable, world);
+        self.build(world).unwrap_or_else(|e| panic!("{e}"));
+
+        let skip_systems = world
+            .get_resource_mut::<Stepping>()
+            .and_then(|mut stepping| stepping.skipped_systems(self));
+
+        // only measure run durations while `ScheduleTimings` is collecting them
+        let mut system_durations = world
+            .get_resource::<ScheduleTimings>()
+            .filter(|timings| timings.is_enabled())
+            .map(|_| vec![None; self.executable.systems.len()]);
+        let start = system_durations.is_some().then(Instant::now);
+        self.executor.run(
+            &mut self.executable,
+            world,
+            skip_systems.as_ref(),
+            system_durations.as_deref_mut(),
+        );
+
+        if let (Some(durations), Some(start)) = (system_durations, start) {
+            let elapsed = start.elapsed();
+            if let Some(mut timings) = world.get_resource_mut::<ScheduleTimings>() {
+                let name = self.name();
+                let systems = self.executable.system_ids.iter();
+                let systems = systems.zip(&self.executable.systems).zip(durations);
+                for ((id, system), duration) in systems {
+                    if let Some(duration) = duration {
+                        // the graph id of a system is stable, unlike its position in the
+                        // executable schedule, which changes when the schedule is rebuilt
+                        let key = SystemKey {
+                            schedule: name.clone(),
+                            index: id.index(),
+                        };
+                        timings.record_system(key, system.name(), duration);
+                    }
+                }
+                timings.record_schedule(name, elapsed);
+            }
+        }
+    }
+
+    /// Returns the name of the schedule's label, or `"<unlabeled>"` if it has not been inserted
+    /// into [`Schedules`].
+    ///
+    /// This is the name [`ScheduleTimings`] reports the schedule's run durations under.
+    pub fn name(&self) -> String {
+        self.label
+            .as_ref()
+            .map_or_else(|| "<unlabeled>".to_string(), |label| format!("{label:?}"))
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
     /// and re-initializes the executor.
     ///
     /// Moves all systems and run conditions out of the [`ScheduleGraph`].
-    pub fn initialize(&mut self, world: &mut World) -> Result<(), ScheduleBuildError> {
+    ///
+    /// Returns the [`ScheduleAmbiguityReport`] of the schedule, listing the systems with
+    /// conflicting data access whose execution order is not defined.
+    pub fn initialize(
+        &mut self,
+        world: &mut World,
+    ) -> Result<ScheduleAmbiguityReport, ScheduleBuildError> {
+        self.build(world)?;
+        Ok(self.graph.ambiguity_report().clone())
+    }
+
+    fn build(&mut self, world: &mut World) -> Result<(), ScheduleBuildError> {
         if self.graph.changed {
             self.graph.initialize(world);
             self.graph
@@ -259,6 +351,128 @@ impl Schedule {
         &mut self.graph
     }
 
+    /// Returns the label of the schedule, if it has been inserted into [`Schedules`].
+    pub fn label(&self) -> Option<&dyn ScheduleLabel> {
+        self.label.as_deref()
+    }
+
+    /// Returns the systems of the schedule in execution order, along with their [`NodeId`].
+    ///
+    /// This is empty until the schedule has been [initialized](Schedule::initialize).
+    pub fn systems(&self) -> impl Iterator<Item = (NodeId, &dyn System<In = (), Out = ()>)> {
+        self.executable
+            .system_ids
+            .iter()
+            .copied()
+            .zip(self.executable.systems.iter().map(|system| &**system))
+    }
+
+    /// Returns a [`ScheduleExport`] of the systems, system sets, hierarchy and dependencies of
+    /// the schedule, which can be formatted as Graphviz DOT or JSON.
+    ///
+    /// Systems only have an execution order once the schedule has been
+    /// [initialized](Schedule::initialize) or run.
+    pub fn export(&self) -> ScheduleExport {
+        let graph = &self.graph;
+        let order: HashMap<usize, usize> = self
+            .executable
+            .system_ids
+            .iter()
+            .enumerate()
+            .map(|(order, id)| (id.index(), order))
+            .collect();
+        // once built, systems and conditions live in the executable schedule
+        let system_at = |index: usize| match graph.systems[index].get() {
+            Some(system) => system,
+            None => &self.executable.systems[order[&index]],
+        };
+        let system_conditions = |index: usize| match order.get(&index) {
+            Some(&order) => &self.executable.system_conditions[order],
+            None => &graph.system_conditions[index],
+        };
+        let set_conditions = |index: usize| {
+            let set_ids = &self.executable.set_ids;
+            match set_ids.iter().position(|id| id.index() == index) {
+                Some(position) => &self.executable.set_conditions[position],
+                None => &graph.system_set_conditions[index],
+            }
+        };
+        let condition_names = |conditions: &[BoxedCondition]| {
+            conditions
+                .iter()
+                .map(|condition| condition.name().to_string())
+                .collect()
+        };
+        let node_id = |id: NodeId| match id {
+            NodeId::System(index) => format!("system:{index}"),
+            NodeId::Set(index) => format!("set:{index}"),
+        };
+        let edges = |dag: &Dag| {
+            let mut edges: Vec<_> = dag.graph.all_edges().map(|(a, b, _)| (a, b)).collect();
+            edges.sort();
+            edges
+                .into_iter()
+                .map(|(from, to)| EdgeExport {
+                    from: node_id(from),
+                    to: node_id(to),
+                })
+                .collect()
+        };
+
+        let systems = (0..graph.systems.len())
+            .map(|index| {
+                let system = system_at(index);
+                SystemExport {
+                    id: node_id(NodeId::System(index)),
+                    name: system.name().to_string(),
+                    origin: graph
+                        .system_origin(NodeId::System(index))
+                        .map(str::to_string),
+                    conditions: condition_names(system_conditions(index)),
+                    is_exclusive: system.is_exclusive(),
+                    is_apply_deferred: is_apply_deferred(system),
+                    order: order.get(&index).copied(),
+                }
+            })
+            .collect();
+
+        let sets = graph
+            .system_sets
+            .iter()
+            .enumerate()
+            .map(|(index, set)| {
+                let name = if set.is_anonymous() {
+                    let members: Vec<_> = graph
+                        .hierarchy
+                        .graph
+                        .neighbors_directed(NodeId::Set(index), Direction::Outgoing)
+                        .map(|member| match member {
+                            NodeId::System(index) => system_at(index).name().to_string(),
+                            NodeId::Set(index) => graph.system_sets[index].name(),
+                        })
+                        .collect();
+                    format!("({})", members.join(", "))
+                } else {
+                    set.name()
+                };
+                SystemSetExport {
+                    id: node_id(NodeId::Set(index)),
+                    name,
+                    conditions: condition_names(set_conditions(index)),
+                    is_system_type: set.is_system_type(),
+                }
+            })
+            .collect();
+
+        ScheduleExport {
+            label: self.label.as_ref().map(|label| format!("{label:?}")),
+            systems,
+            sets,
+            hierarchy: edges(&graph.hierarchy),
+            dependencies: edges(&graph.dependency),
+        }
+    }
+
     /// Iterates the change ticks of all systems in the schedule and clamps any older than
     /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
     /// This prevents overflow and thus prevents false positives.
@@ -375,6 +589,8 @@ impl SystemNode {
 #[derive(Default)]
 pub struct ScheduleGraph {
     systems: Vec<SystemNode>,
+    system_origins: Vec<Option<Cow<'static, str>>>,
+    current_origin: Option<Cow<'static, str>>,
     system_conditions: Vec<Vec<BoxedCondition>>,
     system_sets: Vec<SystemSetNode>,
     system_set_conditions: Vec<Vec<BoxedCondition>>,
@@ -387,6 +603,7 @@ pub struct ScheduleGraph {
     ambiguous_with_flattened: UnGraphMap<NodeId, ()>,
     ambiguous_with_all: HashSet<NodeId>,
     conflicting_systems: Vec<(NodeId, NodeId, Vec<ComponentId>)>,
+    ambiguity_report: ScheduleAmbiguityReport,
     changed: bool,
     settings: ScheduleBuildSettings,
 }
@@ -396,6 +613,8 @@ impl ScheduleGraph {
     pub fn new() -> Self {
         Self {
             systems: Vec::new(),
+            system_origins: Vec::new(),
+            current_origin: None,
             system_conditions: Vec::new(),
             system_sets: Vec::new(),
             system_set_conditions: Vec::new(),
@@ -408,6 +627,7 @@ impl ScheduleGraph {
             ambiguous_with_flattened: UnGraphMap::new(),
             ambiguous_with_all: HashSet::new(),
             conflicting_systems: Vec::new(),
+            ambiguity_report: ScheduleAmbiguityReport::default(),
             changed: false,
             settings: default(),
         }
@@ -499,6 +719,56 @@ impl ScheduleGraph {
         &self.conflicting_systems
     }
 
+    /// Returns the origin recorded for the system at the given [`NodeId`], if any.
+    ///
+    /// See [`Schedule::add_systems_with_origin`].
+    pub fn system_origin(&self, id: NodeId) -> Option<&str> {
+        if !id.is_system() {
+            return None;
+        }
+        self.system_origins.get(id.index())?.as_deref()
+    }
+
+    /// Returns a [`ScheduleAmbiguityReport`] describing the [`conflicting_systems`].
+    ///
+    /// Must be called after [`ScheduleGraph::build_schedule`] to be non-empty.
+    ///
+    /// [`conflicting_systems`]: ScheduleGraph::conflicting_systems
+    pub fn ambiguity_report(&self) -> &ScheduleAmbiguityReport {
+        &self.ambiguity_report
+    }
+
+    /// Builds a [`ScheduleAmbiguityReport`] from `conflicts`. If `use_settings` is true, system
+    /// names are formatted according to the [`ScheduleBuildSettings`], otherwise the full type
+    /// names are used so that reports stay comparable.
+    fn build_ambiguity_report(
+        &self,
+        conflicts: &[(NodeId, NodeId, Vec<ComponentId>)],
+        components: &Components,
+        use_settings: bool,
+    ) -> ScheduleAmbiguityReport {
+        let system = |id: &NodeId| {
+            debug_assert!(id.is_system(), "{id:?} is not a system.");
+            AmbiguousSystem {
+                name: if use_settings {
+                    self.get_node_name(id)
+                } else {
+                    self.systems[id.index()].get().unwrap().name().to_string()
+                },
+                origin: self.system_origin(*id).map(str::to_string),
+            }
+        };
+        ScheduleAmbiguityReport::new(conflicts.iter().map(|(a, b, conflicts)| {
+            let access = if conflicts.is_empty() {
+                // one or both systems must be exclusive
+                ConflictingAccess::World
+            } else {
+                ConflictingAccess::from_ids(conflicts, components)
+            };
+            SystemAmbiguity::new(system(a), system(b), access)
+        }))
+    }
+
     /// Adds the systems to the graph. Returns a vector of all node ids contained the nested `SystemConfigs`
     /// if `ancestor_chained` is true. Also returns true if "densely chained", meaning that all nested items
     /// are linearly chained in the order they are defined
@@ -550,8 +820,8 @@ impl ScheduleGraph {
                     let Some(prev) = config_iter.next() else {
                         return AddSystemsInnerResult {
                             nodes: Vec::new(),
-                            densely_chained: true
-                        }
+                            densely_chained: true,
+                        };
                     };
                     let mut previous_result = self.add_systems_inner(prev, true);
                     densely_chained = previous_result.densely_chained;
@@ -655,6 +925,7 @@ impl ScheduleGraph {
         // system init has to be deferred (need `&mut World`)
         self.uninit.push((id, 0));
         self.systems.push(SystemNode::new(config.system));
+        self.system_origins.push(self.current_origin.clone());
         self.system_conditions.push(config.conditions);
 
         Ok(id)
@@ -1078,6 +1349,8 @@ impl ScheduleGraph {
                 return Err(ScheduleBuildError::Ambiguity);
             }
         }
+        self.ambiguity_report =
+            self.build_ambiguity_report(&conflicting_systems, components, false);
         self.conflicting_systems = conflicting_systems;
 
         // build the schedule
@@ -1423,36 +1696,8 @@ impl ScheduleGraph {
         ambiguities: &[(NodeId, NodeId, Vec<ComponentId>)],
         components: &Components,
     ) {
-        let n_ambiguities = ambiguities.len();
-
-        let mut string = format!(
-            "{n_ambiguities} pairs of systems with conflicting data access have indeterminate execution order. \
-            Consider adding `before`, `after`, or `ambiguous_with` relationships between these:\n",
-        );
-
-        for (system_a, system_b, conflicts) in ambiguities {
-            let name_a = self.get_node_name(system_a);
-            let name_b = self.get_node_name(system_b);
-
-            debug_assert!(system_a.is_system(), "{name_a} is not a system.");
-            debug_assert!(system_b.is_system(), "{name_b} is not a system.");
-
-            writeln!(string, " -- {name_a} and {name_b}").unwrap();
-            if !conflicts.is_empty() {
-                let conflict_names: Vec<_> = conflicts
-                    .iter()
-                    .map(|id| components.get_name(*id).unwrap())
-                    .collect();
-
-                writeln!(string, "    conflict on: {conflict_names:?}").unwrap();
-            } else {
-                // one or both systems must be exclusive
-                let world = std::any::type_name::<World>();
-                writeln!(string, "    conflict on: {world}").unwrap();
-            }
-        }
-
-        warn!("{}", string);
+        let report = self.build_ambiguity_report(ambiguities, components, true);
+        warn!("{}", report);
     }
 
     fn traverse_sets_containing_node(&self, id: NodeId, f: &mut impl FnMut(NodeId) -> bool) {
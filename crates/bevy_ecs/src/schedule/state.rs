@@ -1,13 +1,18 @@
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
 
 use crate as bevy_ecs;
 use crate::change_detection::DetectChangesMut;
-use crate::schedule::ScheduleLabel;
-use crate::system::Resource;
+use crate::schedule::{
+    IntoSystemConfigs, IntoSystemSetConfig, IntoSystemSetConfigs, Schedule, ScheduleLabel,
+    SystemSet,
+};
+use crate::system::{Local, Resource};
 use crate::world::World;
+use bevy_utils::all_tuples;
 
 pub use bevy_ecs_macros::States;
 
@@ -154,3 +159,564 @@ pub fn apply_state_transition<S: States>(world: &mut World) {
         }
     }
 }
+
+/// A [`States`] type that only exists while its [source states](SubStates::SourceStates)
+/// have some specific values.
+///
+/// The [`State<S>`] resource of a sub-state is inserted with its [`Default`] value when
+/// [`should_exist`](SubStates::should_exist) becomes `true`, and removed when it becomes `false`,
+/// running the [`OnEnter`] and [`OnExit`] schedules accordingly.
+/// While it exists, it can be changed through [`NextState<S>`] like any other state.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum AppState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum Paused {
+///     #[default]
+///     Running,
+///     Paused,
+/// }
+///
+/// impl SubStates for Paused {
+///     type SourceStates = AppState;
+///
+///     fn should_exist(app_state: AppState) -> bool {
+///         app_state == AppState::InGame
+///     }
+/// }
+/// ```
+pub trait SubStates: States {
+    /// The states this sub-state depends on.
+    type SourceStates: StateSet;
+
+    /// Returns whether this sub-state exists for the given values of its source states.
+    ///
+    /// This is not called, and the sub-state does not exist, if one of the required source states does not exist.
+    fn should_exist(sources: <Self::SourceStates as StateSet>::Values) -> bool;
+}
+
+/// A [`States`] type whose value is derived from its [source states](ComputedStates::SourceStates).
+///
+/// The [`State<S>`] resource of a computed state is updated whenever its sources change, running
+/// the [`OnEnter`], [`OnExit`] and [`OnTransition`] schedules like any other state.
+/// It is removed when [`compute`](ComputedStates::compute) returns `None`.
+/// Computed states cannot be changed through [`NextState<S>`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum AppState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum Inventory {
+///     #[default]
+///     Closed,
+///     Open,
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum IsMenuVisible {
+///     #[default]
+///     Visible,
+/// }
+///
+/// impl ComputedStates for IsMenuVisible {
+///     type SourceStates = (AppState, Option<Inventory>);
+///
+///     fn compute((app_state, inventory): (AppState, Option<Inventory>)) -> Option<Self> {
+///         let visible = app_state == AppState::MainMenu || inventory == Some(Inventory::Open);
+///         visible.then_some(IsMenuVisible::Visible)
+///     }
+/// }
+/// ```
+pub trait ComputedStates: States {
+    /// The states this computed state is derived from.
+    type SourceStates: StateSet;
+
+    /// Computes the value of this state from the values of its source states,
+    /// or returns `None` if it should not exist.
+    ///
+    /// This is not called, and the computed state does not exist, if one of the required source states does not exist.
+    fn compute(sources: <Self::SourceStates as StateSet>::Values) -> Option<Self>;
+}
+
+/// A set of [`States`] that [`SubStates`] and [`ComputedStates`] depend on.
+///
+/// This is implemented for any [`States`] type `S`, which must exist for the dependent state to exist,
+/// `Option<S>`, which gives `None` when `S` does not exist, and tuples of those.
+pub trait StateSet: 'static {
+    /// The current values of the states in this set.
+    type Values;
+
+    /// Returns the current values of the states in this set, or `None` if a required state does not exist.
+    fn values(world: &World) -> Option<Self::Values>;
+
+    /// Orders the transition systems of the `Dependent` state relative to the ones of the states in this set.
+    fn configure_dependent<Dependent: States>(schedule: &mut Schedule);
+}
+
+impl<S: States> StateSet for S {
+    type Values = S;
+
+    fn values(world: &World) -> Option<Self::Values> {
+        world
+            .get_resource::<State<S>>()
+            .map(|state| state.0.clone())
+    }
+
+    fn configure_dependent<Dependent: States>(schedule: &mut Schedule) {
+        use StateTransitionSteps::*;
+        let set = StateTransitionSystems::<Dependent>::new;
+        let source = StateTransitionSystems::<S>::new;
+        schedule.configure_sets((
+            set(DependentTransitions).after(source(DependentTransitions)),
+            set(ExitSchedules).before(source(ExitSchedules)),
+            set(TransitionSchedules).after(source(TransitionSchedules)),
+            set(EnterSchedules).after(source(EnterSchedules)),
+        ));
+    }
+}
+
+impl<S: States> StateSet for Option<S> {
+    type Values = Option<S>;
+
+    fn values(world: &World) -> Option<Self::Values> {
+        Some(S::values(world))
+    }
+
+    fn configure_dependent<Dependent: States>(schedule: &mut Schedule) {
+        S::configure_dependent::<Dependent>(schedule);
+    }
+}
+
+macro_rules! impl_state_set_tuple {
+    ($($name: ident),*) => {
+        impl<$($name: StateSet),*> StateSet for ($($name,)*) {
+            type Values = ($($name::Values,)*);
+
+            fn values(world: &World) -> Option<Self::Values> {
+                Some(($($name::values(world)?,)*))
+            }
+
+            fn configure_dependent<Dependent: States>(schedule: &mut Schedule) {
+                $($name::configure_dependent::<Dependent>(schedule);)*
+            }
+        }
+    };
+}
+
+all_tuples!(impl_state_set_tuple, 1, 8, S);
+
+/// The steps of the state transitions added by [`add_state_transition_systems`],
+/// [`add_sub_state_transition_systems`] and [`add_computed_state_transition_systems`], in order.
+///
+/// Within each step, the systems of a state are ordered relative to its source states:
+/// source states are updated first, dependent states exit first, and source states enter first.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StateTransitionSteps {
+    /// Applies the queued [`NextState`] values and updates [`SubStates`] and [`ComputedStates`].
+    DependentTransitions,
+    /// Runs the [`OnExit`] schedules.
+    ExitSchedules,
+    /// Runs the [`OnTransition`] schedules.
+    TransitionSchedules,
+    /// Runs the [`OnEnter`] schedules.
+    EnterSchedules,
+}
+
+/// The systems of [`StateTransitionSteps`] for the state `S`.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+struct StateTransitionSystems<S: States> {
+    step: StateTransitionSteps,
+    _marker: PhantomData<S>,
+}
+
+impl<S: States> StateTransitionSystems<S> {
+    fn new(step: StateTransitionSteps) -> Self {
+        Self {
+            step,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The transition of [`State<S>`] whose schedules have not run yet.
+///
+/// `None` means that the state did not exist before, or does not exist anymore.
+#[derive(Resource)]
+struct PendingTransition<S: States> {
+    exited: Option<S>,
+    entered: Option<S>,
+}
+
+/// Adds the systems applying the transitions of the state `S` to the `schedule`.
+///
+/// The [`OnEnter`] schedule of the starting state is run the first time the schedule runs.
+/// The [`State<S>`] and [`NextState<S>`] resources must be added separately.
+pub fn add_state_transition_systems<S: States>(schedule: &mut Schedule) {
+    configure_state_transition_systems::<S, _>(schedule, apply_queued_state_transition::<S>);
+}
+
+/// Adds the systems creating, removing and applying the transitions of the sub-state `S` to the `schedule`.
+///
+/// The [`NextState<S>`] resource must be added separately.
+pub fn add_sub_state_transition_systems<S: SubStates>(schedule: &mut Schedule) {
+    configure_state_transition_systems::<S, _>(schedule, apply_sub_state_transition::<S>);
+    S::SourceStates::configure_dependent::<S>(schedule);
+}
+
+/// Adds the systems updating the computed state `S` to the `schedule`.
+pub fn add_computed_state_transition_systems<S: ComputedStates>(schedule: &mut Schedule) {
+    configure_state_transition_systems::<S, _>(schedule, apply_computed_state_transition::<S>);
+    S::SourceStates::configure_dependent::<S>(schedule);
+}
+
+fn configure_state_transition_systems<S: States, M>(
+    schedule: &mut Schedule,
+    apply_transition: impl IntoSystemConfigs<M>,
+) {
+    use StateTransitionSteps::*;
+    let set = StateTransitionSystems::<S>::new;
+    schedule
+        .configure_sets(
+            (
+                DependentTransitions,
+                ExitSchedules,
+                TransitionSchedules,
+                EnterSchedules,
+            )
+                .chain(),
+        )
+        .configure_sets((
+            set(DependentTransitions).in_set(DependentTransitions),
+            set(ExitSchedules).in_set(ExitSchedules),
+            set(TransitionSchedules).in_set(TransitionSchedules),
+            set(EnterSchedules).in_set(EnterSchedules),
+        ))
+        .add_systems((
+            apply_transition.in_set(set(DependentTransitions)),
+            run_pending_exit_schedule::<S>.in_set(set(ExitSchedules)),
+            run_pending_transition_schedule::<S>.in_set(set(TransitionSchedules)),
+            run_pending_enter_schedule::<S>.in_set(set(EnterSchedules)),
+        ));
+}
+
+/// Sets [`State<S>`] to `entered`, inserting or removing it as needed, and records the transition
+/// for the schedules of [`StateTransitionSteps`].
+fn set_state<S: States>(world: &mut World, entered: Option<S>) {
+    let current = world
+        .get_resource::<State<S>>()
+        .map(|state| state.0.clone());
+    if current == entered {
+        return;
+    }
+    match entered.clone() {
+        Some(entered) => match world.get_resource_mut::<State<S>>() {
+            Some(mut state) => state.0 = entered,
+            None => world.insert_resource(State(entered)),
+        },
+        None => {
+            world.remove_resource::<State<S>>();
+        }
+    }
+    // Keep the state exited by a transition whose schedules have not run yet.
+    let exited = match world.remove_resource::<PendingTransition<S>>() {
+        Some(pending) => pending.exited,
+        None => current,
+    };
+    world.insert_resource(PendingTransition { exited, entered });
+}
+
+/// Takes the queued [`NextState<S>`] value, if any.
+fn take_next_state<S: States>(world: &mut World) -> Option<S> {
+    let mut next_state = world.get_resource_mut::<NextState<S>>()?;
+    let next = next_state.bypass_change_detection().0.take()?;
+    next_state.set_changed();
+    Some(next)
+}
+
+fn apply_queued_state_transition<S: States>(world: &mut World, mut started: Local<bool>) {
+    if !mem::replace(&mut *started, true) {
+        if let Some(state) = world.get_resource::<State<S>>() {
+            let entered = Some(state.0.clone());
+            world.insert_resource(PendingTransition::<S> {
+                exited: None,
+                entered,
+            });
+        }
+    }
+    if let Some(entered) = take_next_state::<S>(world) {
+        set_state(world, Some(entered));
+    }
+}
+
+fn apply_sub_state_transition<S: SubStates>(world: &mut World) {
+    let should_exist = match S::SourceStates::values(world) {
+        Some(sources) => S::should_exist(sources),
+        None => false,
+    };
+    // Transitions queued while the sub-state does not exist are discarded.
+    let next = take_next_state::<S>(world);
+    let entered = should_exist.then(|| {
+        next.or_else(|| {
+            world
+                .get_resource::<State<S>>()
+                .map(|state| state.0.clone())
+        })
+        .unwrap_or_default()
+    });
+    set_state(world, entered);
+}
+
+fn apply_computed_state_transition<S: ComputedStates>(world: &mut World) {
+    let entered = S::SourceStates::values(world).and_then(S::compute);
+    set_state(world, entered);
+}
+
+fn run_pending_exit_schedule<S: States>(world: &mut World) {
+    let Some(exited) = world
+        .get_resource::<PendingTransition<S>>()
+        .and_then(|pending| pending.exited.clone())
+    else {
+        return;
+    };
+    world.try_run_schedule(OnExit(exited)).ok();
+}
+
+fn run_pending_transition_schedule<S: States>(world: &mut World) {
+    let Some(PendingTransition {
+        exited: Some(from),
+        entered: Some(to),
+    }) = world.get_resource::<PendingTransition<S>>()
+    else {
+        return;
+    };
+    let transition = OnTransition {
+        from: from.clone(),
+        to: to.clone(),
+    };
+    world.try_run_schedule(transition).ok();
+}
+
+fn run_pending_enter_schedule<S: States>(world: &mut World) {
+    let Some(entered) = world
+        .remove_resource::<PendingTransition<S>>()
+        .and_then(|pending| pending.entered)
+    else {
+        return;
+    };
+    world.try_run_schedule(OnEnter(entered)).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::{ResMut, Resource};
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum AppState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum Paused {
+        #[default]
+        Running,
+        Paused,
+    }
+
+    impl SubStates for Paused {
+        type SourceStates = AppState;
+
+        fn should_exist(app_state: AppState) -> bool {
+            app_state == AppState::InGame
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum IsMenuVisible {
+        #[default]
+        Visible,
+    }
+
+    impl ComputedStates for IsMenuVisible {
+        type SourceStates = (AppState, Option<Paused>);
+
+        fn compute((app_state, paused): (AppState, Option<Paused>)) -> Option<Self> {
+            (app_state == AppState::Menu || paused == Some(Paused::Paused))
+                .then_some(IsMenuVisible::Visible)
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<String>);
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Transitions;
+
+    fn log_in(world: &mut World, label: impl ScheduleLabel + Clone) {
+        let message = format!("{label:?}");
+        let mut schedule = Schedule::new();
+        schedule.add_systems(move |mut log: ResMut<Log>| log.0.push(message.clone()));
+        world.add_schedule(schedule, label);
+    }
+
+    fn take_log(world: &mut World) -> Vec<String> {
+        mem::take(&mut world.resource_mut::<Log>().0)
+    }
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.init_resource::<State<AppState>>();
+        world.init_resource::<NextState<AppState>>();
+        world.init_resource::<NextState<Paused>>();
+
+        // Added in reverse order to check that the order does not depend on it.
+        let mut schedule = Schedule::new();
+        add_computed_state_transition_systems::<IsMenuVisible>(&mut schedule);
+        add_sub_state_transition_systems::<Paused>(&mut schedule);
+        add_state_transition_systems::<AppState>(&mut schedule);
+        world.add_schedule(schedule, Transitions);
+
+        for state in AppState::variants() {
+            log_in(&mut world, OnEnter(state));
+            log_in(&mut world, OnExit(state));
+        }
+        for state in Paused::variants() {
+            log_in(&mut world, OnEnter(state));
+            log_in(&mut world, OnExit(state));
+        }
+        log_in(&mut world, OnEnter(IsMenuVisible::Visible));
+        log_in(&mut world, OnExit(IsMenuVisible::Visible));
+        log_in(
+            &mut world,
+            OnTransition {
+                from: Paused::Running,
+                to: Paused::Paused,
+            },
+        );
+        world
+    }
+
+    #[test]
+    fn initial_state_is_entered() {
+        let mut world = setup();
+
+        world.run_schedule(Transitions);
+        assert_eq!(take_log(&mut world), ["OnEnter(Menu)", "OnEnter(Visible)"]);
+        assert!(world.get_resource::<State<Paused>>().is_none());
+
+        world.run_schedule(Transitions);
+        assert!(take_log(&mut world).is_empty());
+    }
+
+    #[test]
+    fn sub_state_follows_source_state() {
+        let mut world = setup();
+        world.run_schedule(Transitions);
+        take_log(&mut world);
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        world.run_schedule(Transitions);
+        assert_eq!(
+            take_log(&mut world),
+            [
+                "OnExit(Visible)",
+                "OnExit(Menu)",
+                "OnEnter(InGame)",
+                "OnEnter(Running)"
+            ]
+        );
+        assert_eq!(*world.resource::<State<Paused>>(), Paused::Running);
+
+        world
+            .resource_mut::<NextState<Paused>>()
+            .set(Paused::Paused);
+        world.run_schedule(Transitions);
+        assert_eq!(
+            take_log(&mut world),
+            [
+                "OnExit(Running)",
+                "OnTransition { from: Running, to: Paused }",
+                "OnEnter(Paused)",
+                "OnEnter(Visible)"
+            ]
+        );
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Menu);
+        world.run_schedule(Transitions);
+        assert_eq!(
+            take_log(&mut world),
+            ["OnExit(Paused)", "OnExit(InGame)", "OnEnter(Menu)"]
+        );
+        assert!(world.get_resource::<State<Paused>>().is_none());
+        assert_eq!(
+            *world.resource::<State<IsMenuVisible>>(),
+            IsMenuVisible::Visible
+        );
+    }
+
+    #[test]
+    fn sub_state_transitions_are_discarded_while_it_does_not_exist() {
+        let mut world = setup();
+        world.run_schedule(Transitions);
+        take_log(&mut world);
+
+        world
+            .resource_mut::<NextState<Paused>>()
+            .set(Paused::Paused);
+        world.run_schedule(Transitions);
+        assert!(take_log(&mut world).is_empty());
+        assert!(world.get_resource::<State<Paused>>().is_none());
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        world.run_schedule(Transitions);
+        assert_eq!(*world.resource::<State<Paused>>(), Paused::Running);
+    }
+
+    #[test]
+    fn sub_state_can_be_entered_with_queued_value() {
+        let mut world = setup();
+        world.run_schedule(Transitions);
+        take_log(&mut world);
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        world
+            .resource_mut::<NextState<Paused>>()
+            .set(Paused::Paused);
+        world.run_schedule(Transitions);
+        assert_eq!(
+            take_log(&mut world),
+            ["OnExit(Menu)", "OnEnter(InGame)", "OnEnter(Paused)"]
+        );
+        assert!(world.get_resource::<State<IsMenuVisible>>().is_some());
+    }
+}
@@ -0,0 +1,645 @@
+use std::any::TypeId;
+
+use bevy_utils::HashMap;
+use fixedbitset::FixedBitSet;
+
+use crate as bevy_ecs;
+use crate::schedule::{BoxedScheduleLabel, NodeId, Schedule, ScheduleLabel};
+use crate::system::{IntoSystem, ResMut, Resource, System};
+
+/// How a system is treated by [`Stepping`] while stepping is enabled.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum SystemBehavior {
+    /// The system runs every frame, whether the schedule is being stepped or not.
+    ///
+    /// Use this for systems that must keep working while the rest of the schedule
+    /// is paused, like rendering, input handling or the debug UI driving [`Stepping`].
+    AlwaysRun,
+    /// The system never runs while stepping is enabled.
+    NeverRun,
+    /// The system is stepped, and [`Stepping::continue_frame`] stops right before it.
+    Break,
+    /// The system is stepped: it only runs when the stepping cursor reaches it.
+    #[default]
+    Continue,
+}
+
+/// What the stepped schedules should do during the current frame.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Action {
+    /// Stepping is disabled, every system runs normally.
+    RunAll,
+    /// Stepping is enabled, but no stepped system should run.
+    Waiting,
+    /// Run the stepped system under the cursor, then wait.
+    Step,
+    /// Run stepped systems until the end of the frame or the next breakpoint.
+    Continue,
+}
+
+/// Identifies the systems a [`SystemBehavior`] applies to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum SystemIdentifier {
+    /// Every instance of a system type in the schedule.
+    Type(TypeId),
+    /// A single system in the schedule.
+    Node(NodeId),
+}
+
+/// The stepping state of a single schedule.
+#[derive(Debug)]
+struct ScheduleState {
+    label: BoxedScheduleLabel,
+    behaviors: HashMap<SystemIdentifier, SystemBehavior>,
+    /// The [`NodeId`]s of the schedule's systems the last time it ran, in execution order.
+    node_ids: Vec<NodeId>,
+}
+
+impl ScheduleState {
+    fn new(label: BoxedScheduleLabel) -> Self {
+        Self {
+            label,
+            behaviors: HashMap::default(),
+            node_ids: Vec::new(),
+        }
+    }
+
+    /// Returns the behavior of a system of the schedule, preferring the behavior
+    /// set for its [`NodeId`] over the one set for its type.
+    fn behavior(&self, node_id: NodeId, system: &dyn System<In = (), Out = ()>) -> SystemBehavior {
+        self.behaviors
+            .get(&SystemIdentifier::Node(node_id))
+            .or_else(|| {
+                self.behaviors
+                    .get(&SystemIdentifier::Type(System::type_id(system)))
+            })
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// The position of the next system to step.
+#[derive(Debug, Default, Copy, Clone)]
+struct Cursor {
+    /// Index of the schedule in [`Stepping::schedules`].
+    schedule: usize,
+    /// Index of the system in the execution order of that schedule.
+    system: usize,
+}
+
+/// Resource that allows the systems of selected schedules to be run one at a time, for debugging.
+///
+/// Schedules added with [`Stepping::add_schedule`] are stepped in the order they were added.
+/// While stepping is enabled, their systems are skipped unless:
+/// - they were marked with [`Stepping::always_run`],
+/// - [`Stepping::step_frame`] was called and the system is the next one under the cursor,
+/// - or [`Stepping::continue_frame`] was called, in which case every stepped system runs until the
+///   end of the frame or the next system with a breakpoint set by [`Stepping::set_breakpoint`].
+///
+/// Changes to the stepping action ([`enable`](Self::enable), [`disable`](Self::disable),
+/// [`step_frame`](Self::step_frame) and [`continue_frame`](Self::continue_frame)) take effect at the
+/// start of the next frame, when the [`Stepping::begin_frame`] system runs. `bevy_app` adds it to
+/// the `Main` schedule, so changes requested from a debug console are applied on the next frame.
+///
+/// Only schedules run from the [`Schedules`](super::Schedules) resource, e.g. through
+/// [`World::run_schedule`](crate::world::World::run_schedule), can be stepped.
+/// This works with every [`ExecutorKind`](super::ExecutorKind).
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::{ScheduleLabel, Stepping}, system::RunSystemOnce};
+/// # #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// # struct Update;
+/// # #[derive(Resource, Default)]
+/// # struct Log(Vec<&'static str>);
+/// fn first(mut log: ResMut<Log>) {
+///     log.0.push("first");
+/// }
+/// fn second(mut log: ResMut<Log>) {
+///     log.0.push("second");
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Log>();
+/// world.add_schedule(Schedule::new(), Update);
+/// world.schedule_scope(Update, |_, schedule| {
+///     schedule.add_systems((first, second).chain());
+/// });
+///
+/// let mut stepping = Stepping::new();
+/// stepping.add_schedule(Update).enable().step_frame();
+/// world.insert_resource(stepping);
+///
+/// // each frame starts with `Stepping::begin_frame`
+/// world.run_system_once(Stepping::begin_frame);
+/// world.run_schedule(Update);
+/// assert_eq!(world.resource::<Log>().0, vec!["first"]);
+///
+/// // nothing runs until the next step
+/// world.run_system_once(Stepping::begin_frame);
+/// world.run_schedule(Update);
+/// assert_eq!(world.resource::<Log>().0, vec!["first"]);
+/// ```
+#[derive(Resource, Debug)]
+pub struct Stepping {
+    schedules: Vec<ScheduleState>,
+    action: Action,
+    /// The action requested since the start of the frame, applied in [`Stepping::begin_frame`].
+    pending_action: Option<Action>,
+    cursor: Cursor,
+}
+
+impl Default for Stepping {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stepping {
+    /// Creates a disabled [`Stepping`] resource that does not step any schedule.
+    pub fn new() -> Self {
+        Self {
+            schedules: Vec::new(),
+            action: Action::RunAll,
+            pending_action: None,
+            cursor: Cursor::default(),
+        }
+    }
+
+    /// System that applies the changes requested during the previous frame.
+    ///
+    /// This must run once per frame, before any stepped schedule.
+    pub fn begin_frame(stepping: Option<ResMut<Self>>) {
+        if let Some(mut stepping) = stepping {
+            stepping.next_frame();
+        }
+    }
+
+    fn next_frame(&mut self) {
+        let Some(action) = self.pending_action.take() else {
+            return;
+        };
+        // entering or leaving stepping restarts from the first stepped system
+        if (self.action == Action::RunAll) != (action == Action::RunAll) {
+            self.cursor = Cursor::default();
+        }
+        self.action = action;
+    }
+
+    /// The action that will be used by the next frame.
+    fn next_action(&self) -> Action {
+        self.pending_action.unwrap_or(self.action)
+    }
+
+    /// Adds a schedule to the ones being stepped. Schedules are stepped in the order they are added.
+    pub fn add_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        self.schedule_state_mut(&schedule);
+        self
+    }
+
+    /// Stops stepping a schedule, which will then run normally.
+    pub fn remove_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        if let Some(index) = self.schedule_index(&schedule) {
+            self.schedules.remove(index);
+            if self.cursor.schedule > index {
+                self.cursor.schedule -= 1;
+            } else if self.cursor.schedule == index {
+                self.cursor.system = 0;
+            }
+            if self.cursor.schedule >= self.schedules.len() {
+                self.cursor = Cursor::default();
+            }
+        }
+        self
+    }
+
+    /// Removes every [`SystemBehavior`] set for the systems of a schedule.
+    pub fn clear_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        if let Some(index) = self.schedule_index(&schedule) {
+            self.schedules[index].behaviors.clear();
+        }
+        self
+    }
+
+    /// Returns the labels of the stepped schedules, in stepping order.
+    pub fn schedules(&self) -> impl Iterator<Item = &dyn ScheduleLabel> {
+        self.schedules.iter().map(|state| &*state.label)
+    }
+
+    /// Returns the schedule and [`NodeId`] of the next system to be stepped,
+    /// or `None` if stepping is disabled or the cursor is at the end of a schedule.
+    pub fn cursor(&self) -> Option<(&dyn ScheduleLabel, NodeId)> {
+        if self.action == Action::RunAll {
+            return None;
+        }
+        let state = self.schedules.get(self.cursor.schedule)?;
+        let node_id = state.node_ids.get(self.cursor.system)?;
+        Some((&*state.label, *node_id))
+    }
+
+    /// Enables stepping from the start of the next frame.
+    /// The stepped schedules will only run the systems marked with [`Stepping::always_run`].
+    pub fn enable(&mut self) -> &mut Self {
+        if self.next_action() == Action::RunAll {
+            self.pending_action = Some(Action::Waiting);
+        }
+        self
+    }
+
+    /// Disables stepping from the start of the next frame, running every system normally.
+    pub fn disable(&mut self) -> &mut Self {
+        self.pending_action = Some(Action::RunAll);
+        self
+    }
+
+    /// Returns `true` if stepping is, or will be from the next frame on, enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.next_action() != Action::RunAll
+    }
+
+    /// Runs the next stepped system during the next frame.
+    ///
+    /// Does nothing if stepping is not enabled.
+    pub fn step_frame(&mut self) -> &mut Self {
+        if self.is_enabled() {
+            self.pending_action = Some(Action::Step);
+        }
+        self
+    }
+
+    /// Runs every remaining stepped system during the next frame, stopping at the end of the
+    /// frame or before the next system with a breakpoint.
+    ///
+    /// Does nothing if stepping is not enabled.
+    pub fn continue_frame(&mut self) -> &mut Self {
+        if self.is_enabled() {
+            self.pending_action = Some(Action::Continue);
+        }
+        self
+    }
+
+    /// Runs every instance of `system` in `schedule` even while stepping.
+    pub fn always_run<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+    ) -> &mut Self {
+        let id = system_type_id(system);
+        self.set_behavior(schedule, id, Some(SystemBehavior::AlwaysRun))
+    }
+
+    /// Runs the system with the given [`NodeId`] in `schedule` even while stepping.
+    pub fn always_run_node(&mut self, schedule: impl ScheduleLabel, node: NodeId) -> &mut Self {
+        let id = SystemIdentifier::Node(node);
+        self.set_behavior(schedule, id, Some(SystemBehavior::AlwaysRun))
+    }
+
+    /// Never runs any instance of `system` in `schedule` while stepping.
+    pub fn never_run<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+    ) -> &mut Self {
+        let id = system_type_id(system);
+        self.set_behavior(schedule, id, Some(SystemBehavior::NeverRun))
+    }
+
+    /// Never runs the system with the given [`NodeId`] in `schedule` while stepping.
+    pub fn never_run_node(&mut self, schedule: impl ScheduleLabel, node: NodeId) -> &mut Self {
+        let id = SystemIdentifier::Node(node);
+        self.set_behavior(schedule, id, Some(SystemBehavior::NeverRun))
+    }
+
+    /// Sets a breakpoint on every instance of `system` in `schedule`.
+    pub fn set_breakpoint<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+    ) -> &mut Self {
+        let id = system_type_id(system);
+        self.set_behavior(schedule, id, Some(SystemBehavior::Break))
+    }
+
+    /// Sets a breakpoint on the system with the given [`NodeId`] in `schedule`.
+    pub fn set_breakpoint_node(&mut self, schedule: impl ScheduleLabel, node: NodeId) -> &mut Self {
+        let id = SystemIdentifier::Node(node);
+        self.set_behavior(schedule, id, Some(SystemBehavior::Break))
+    }
+
+    /// Resets every instance of `system` in `schedule` to the default [`SystemBehavior`].
+    pub fn clear_system<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+    ) -> &mut Self {
+        let id = system_type_id(system);
+        self.set_behavior(schedule, id, None)
+    }
+
+    /// Resets the system with the given [`NodeId`] in `schedule` to the default [`SystemBehavior`].
+    pub fn clear_node(&mut self, schedule: impl ScheduleLabel, node: NodeId) -> &mut Self {
+        self.set_behavior(schedule, SystemIdentifier::Node(node), None)
+    }
+
+    fn set_behavior(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        id: SystemIdentifier,
+        behavior: Option<SystemBehavior>,
+    ) -> &mut Self {
+        let behaviors = &mut self.schedule_state_mut(&schedule).behaviors;
+        match behavior {
+            Some(behavior) => {
+                behaviors.insert(id, behavior);
+            }
+            None => {
+                behaviors.remove(&id);
+            }
+        }
+        self
+    }
+
+    /// Returns the state of `schedule`, adding it to the stepped schedules if needed.
+    fn schedule_state_mut(&mut self, schedule: &dyn ScheduleLabel) -> &mut ScheduleState {
+        let index = self.schedule_index(schedule).unwrap_or_else(|| {
+            self.schedules
+                .push(ScheduleState::new(schedule.dyn_clone()));
+            self.schedules.len() - 1
+        });
+        &mut self.schedules[index]
+    }
+
+    fn schedule_index(&self, schedule: &dyn ScheduleLabel) -> Option<usize> {
+        self.schedules
+            .iter()
+            .position(|state| &*state.label == schedule)
+    }
+
+    /// Moves the cursor to the first system of the next stepped schedule.
+    ///
+    /// Returns `true` if the cursor went past the last stepped schedule, i.e. the frame is over.
+    fn advance_schedule(&mut self) -> bool {
+        self.cursor.system = 0;
+        self.cursor.schedule += 1;
+        if self.cursor.schedule >= self.schedules.len() {
+            self.cursor.schedule = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Returns the systems of `schedule` that should not run this time, or `None` if
+    /// it should run normally.
+    pub(crate) fn skipped_systems(&mut self, schedule: &Schedule) -> Option<FixedBitSet> {
+        if self.action == Action::RunAll {
+            return None;
+        }
+        let index = self.schedule_index(schedule.label()?)?;
+
+        let state = &mut self.schedules[index];
+        state.node_ids.clear();
+        state
+            .node_ids
+            .extend(schedule.systems().map(|(node_id, _)| node_id));
+        let behaviors: Vec<SystemBehavior> = schedule
+            .systems()
+            .map(|(node_id, system)| state.behavior(node_id, system))
+            .collect();
+
+        let mut skipped = FixedBitSet::with_capacity(behaviors.len());
+        for (i, behavior) in behaviors.iter().enumerate() {
+            if *behavior != SystemBehavior::AlwaysRun {
+                skipped.insert(i);
+            }
+        }
+
+        if index != self.cursor.schedule {
+            return Some(skipped);
+        }
+
+        let is_stepped = |i: usize| {
+            matches!(
+                behaviors[i],
+                SystemBehavior::Break | SystemBehavior::Continue
+            )
+        };
+        match self.action {
+            Action::RunAll | Action::Waiting => {}
+            Action::Step => {
+                let mut stepped = (self.cursor.system..behaviors.len()).filter(|&i| is_stepped(i));
+                if let Some(i) = stepped.next() {
+                    skipped.set(i, false);
+                    self.cursor.system = i + 1;
+                    self.action = Action::Waiting;
+                }
+                // once the last system is stepped, the next step runs the next stepped schedule
+                if stepped.next().is_none() {
+                    self.advance_schedule();
+                }
+            }
+            Action::Continue => {
+                for i in (self.cursor.system..behaviors.len()).filter(|&i| is_stepped(i)) {
+                    // the breakpoint under the cursor is the one we stopped at last time
+                    if behaviors[i] == SystemBehavior::Break && i != self.cursor.system {
+                        self.cursor.system = i;
+                        self.action = Action::Waiting;
+                        return Some(skipped);
+                    }
+                    skipped.set(i, false);
+                }
+                if self.advance_schedule() {
+                    self.action = Action::Waiting;
+                }
+            }
+        }
+
+        Some(skipped)
+    }
+}
+
+fn system_type_id<Marker>(system: impl IntoSystem<(), (), Marker>) -> SystemIdentifier {
+    let system = IntoSystem::into_system(system);
+    SystemIdentifier::Type(System::type_id(&system))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::{ExecutorKind, ScheduleLabel},
+        system::RunSystemOnce,
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct OtherSchedule;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn first(mut log: ResMut<Log>) {
+        log.0.push("first");
+    }
+
+    fn second(mut log: ResMut<Log>) {
+        log.0.push("second");
+    }
+
+    fn third(mut log: ResMut<Log>) {
+        log.0.push("third");
+    }
+
+    fn other(mut log: ResMut<Log>) {
+        log.0.push("other");
+    }
+
+    fn setup(executor: ExecutorKind) -> World {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+
+        let mut schedule = Schedule::new();
+        schedule
+            .set_executor_kind(executor)
+            .add_systems((first, second, third).chain());
+        world.add_schedule(schedule, TestSchedule);
+
+        let mut schedule = Schedule::new();
+        schedule.set_executor_kind(executor).add_systems(other);
+        world.add_schedule(schedule, OtherSchedule);
+
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(TestSchedule).enable();
+        world.insert_resource(stepping);
+        world
+    }
+
+    fn run_frame(world: &mut World) -> Vec<&'static str> {
+        world.run_system_once(Stepping::begin_frame);
+        world.run_schedule(TestSchedule);
+        world.run_schedule(OtherSchedule);
+        std::mem::take(&mut world.resource_mut::<Log>().0)
+    }
+
+    const EXECUTORS: [ExecutorKind; 3] = [
+        ExecutorKind::SingleThreaded,
+        ExecutorKind::Simple,
+        ExecutorKind::MultiThreaded,
+    ];
+
+    #[test]
+    fn disabled_stepping_runs_everything() {
+        for executor in EXECUTORS {
+            let mut world = setup(executor);
+            world.resource_mut::<Stepping>().disable();
+            assert_eq!(
+                run_frame(&mut world),
+                vec!["first", "second", "third", "other"]
+            );
+        }
+    }
+
+    #[test]
+    fn enabled_stepping_skips_stepped_schedules() {
+        for executor in EXECUTORS {
+            let mut world = setup(executor);
+            assert_eq!(run_frame(&mut world), vec!["other"]);
+            assert_eq!(run_frame(&mut world), vec!["other"]);
+        }
+    }
+
+    #[test]
+    fn step_frame_runs_one_system_at_a_time() {
+        for executor in EXECUTORS {
+            let mut world = setup(executor);
+            run_frame(&mut world);
+
+            for expected in ["first", "second", "third"] {
+                world.resource_mut::<Stepping>().step_frame();
+                assert_eq!(run_frame(&mut world), vec![expected, "other"]);
+                assert_eq!(run_frame(&mut world), vec!["other"]);
+            }
+
+            // the schedule is done, the next step starts over from its first system
+            world.resource_mut::<Stepping>().step_frame();
+            assert_eq!(run_frame(&mut world), vec!["first", "other"]);
+        }
+    }
+
+    #[test]
+    fn continue_frame_stops_at_breakpoints() {
+        for executor in EXECUTORS {
+            let mut world = setup(executor);
+            world
+                .resource_mut::<Stepping>()
+                .set_breakpoint(TestSchedule, third);
+            run_frame(&mut world);
+
+            world.resource_mut::<Stepping>().continue_frame();
+            assert_eq!(run_frame(&mut world), vec!["first", "second", "other"]);
+            assert_eq!(run_frame(&mut world), vec!["other"]);
+
+            // continuing from the breakpoint runs it, and the rest of the frame
+            world.resource_mut::<Stepping>().continue_frame();
+            assert_eq!(run_frame(&mut world), vec!["third", "other"]);
+
+            world.resource_mut::<Stepping>().continue_frame();
+            assert_eq!(run_frame(&mut world), vec!["first", "second", "other"]);
+        }
+    }
+
+    #[test]
+    fn always_run_and_never_run() {
+        for executor in EXECUTORS {
+            let mut world = setup(executor);
+            world
+                .resource_mut::<Stepping>()
+                .always_run(TestSchedule, second)
+                .never_run(TestSchedule, first);
+            assert_eq!(run_frame(&mut world), vec!["second", "other"]);
+
+            world.resource_mut::<Stepping>().continue_frame();
+            assert_eq!(run_frame(&mut world), vec!["second", "third", "other"]);
+
+            world
+                .resource_mut::<Stepping>()
+                .clear_system(TestSchedule, first);
+            world.resource_mut::<Stepping>().step_frame();
+            assert_eq!(run_frame(&mut world), vec!["first", "second", "other"]);
+        }
+    }
+
+    #[test]
+    fn cursor_follows_steps() {
+        let mut world = setup(ExecutorKind::SingleThreaded);
+        run_frame(&mut world);
+        let (label, first_node) = world.resource::<Stepping>().cursor().unwrap();
+        assert_eq!(label, &TestSchedule as &dyn ScheduleLabel);
+
+        world.resource_mut::<Stepping>().step_frame();
+        run_frame(&mut world);
+        let (_, second_node) = world.resource::<Stepping>().cursor().unwrap();
+        assert_ne!(first_node, second_node);
+
+        world.resource_mut::<Stepping>().disable();
+        run_frame(&mut world);
+        assert!(world.resource::<Stepping>().cursor().is_none());
+    }
+
+    #[test]
+    fn step_across_schedules() {
+        let mut world = setup(ExecutorKind::SingleThreaded);
+        world.resource_mut::<Stepping>().add_schedule(OtherSchedule);
+        run_frame(&mut world);
+
+        let mut stepped = Vec::new();
+        for _ in 0..4 {
+            world.resource_mut::<Stepping>().step_frame();
+            stepped.extend(run_frame(&mut world));
+        }
+        assert_eq!(stepped, vec!["first", "second", "third", "other"]);
+    }
+}
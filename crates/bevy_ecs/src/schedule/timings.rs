@@ -0,0 +1,278 @@
+use std::borrow::Cow;
+
+use bevy_utils::{Duration, HashMap};
+
+use crate as bevy_ecs;
+use crate::system::Resource;
+
+/// The accumulated run time of a system or schedule.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct RunTiming {
+    /// The total time spent running.
+    pub duration: Duration,
+    /// The number of times it ran.
+    pub runs: u32,
+}
+
+impl RunTiming {
+    fn record(&mut self, duration: Duration) {
+        self.duration += duration;
+        self.runs += 1;
+    }
+}
+
+/// Identifies a system measured by [`ScheduleTimings`].
+///
+/// Systems are identified by their position rather than their name, so that systems sharing a
+/// name (like two closures, or a function added twice) are measured separately.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SystemKey {
+    /// The [name](super::Schedule::name) of the schedule the system belongs to.
+    pub schedule: String,
+    /// The index of the system in its schedule, in the order systems were added.
+    ///
+    /// Indices do not change when systems are added to the schedule later.
+    pub index: usize,
+}
+
+/// The accumulated run time of a system, along with its name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemTiming {
+    /// The [name](crate::system::System::name) of the system.
+    pub name: Cow<'static, str>,
+    /// The accumulated run time of the system.
+    pub timing: RunTiming,
+}
+
+/// Collects how long each system and [`Schedule`](super::Schedule) took to run.
+///
+/// Executors only measure run durations while this resource is in the [`World`](crate::world::World)
+/// and [`enabled`](Self::is_enabled). Measurements accumulate until they are
+/// [drained](Self::drain), so a system that runs several times between two drains (like a system in
+/// a fixed timestep schedule) reports its total duration along with its run count.
+///
+/// Systems are identified by a [`SystemKey`], and schedules by the [`Debug`] representation of
+/// their label.
+#[derive(Resource, Debug)]
+pub struct ScheduleTimings {
+    enabled: bool,
+    systems: HashMap<SystemKey, SystemTiming>,
+    schedules: HashMap<String, RunTiming>,
+}
+
+impl Default for ScheduleTimings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScheduleTimings {
+    /// Creates an enabled [`ScheduleTimings`] with no measurements.
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            systems: HashMap::new(),
+            schedules: HashMap::new(),
+        }
+    }
+
+    /// Returns `true` if executors are measuring run durations.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Starts or stops measuring run durations.
+    ///
+    /// Measurements that were already collected are kept.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns the accumulated timing of the given system.
+    pub fn system(&self, key: &SystemKey) -> Option<&SystemTiming> {
+        self.systems.get(key)
+    }
+
+    /// Returns the accumulated timing of the schedule with the given name.
+    pub fn schedule(&self, name: &str) -> Option<RunTiming> {
+        self.schedules.get(name).copied()
+    }
+
+    /// Returns the accumulated timings of all systems that ran since the last drain.
+    pub fn systems(&self) -> impl Iterator<Item = (&SystemKey, &SystemTiming)> {
+        self.systems.iter()
+    }
+
+    /// Returns the accumulated timings of all schedules that ran since the last drain.
+    pub fn schedules(&self) -> impl Iterator<Item = (&str, RunTiming)> {
+        self.schedules
+            .iter()
+            .map(|(name, timing)| (name.as_str(), *timing))
+    }
+
+    /// Returns up to `count` systems with the longest accumulated durations, slowest first.
+    pub fn slowest_systems(&self, count: usize) -> Vec<(&SystemKey, &SystemTiming)> {
+        let mut systems: Vec<_> = self.systems().collect();
+        systems.sort_by_key(|(_, system)| std::cmp::Reverse(system.timing.duration));
+        systems.truncate(count);
+        systems
+    }
+
+    /// Removes and returns all measurements, as `(systems, schedules)`.
+    pub fn drain(&mut self) -> (HashMap<SystemKey, SystemTiming>, HashMap<String, RunTiming>) {
+        (
+            std::mem::take(&mut self.systems),
+            std::mem::take(&mut self.schedules),
+        )
+    }
+
+    /// Discards all measurements.
+    pub fn clear(&mut self) {
+        self.systems.clear();
+        self.schedules.clear();
+    }
+
+    pub(super) fn record_system(
+        &mut self,
+        key: SystemKey,
+        name: Cow<'static, str>,
+        duration: Duration,
+    ) {
+        self.systems
+            .entry(key)
+            .or_insert_with(|| SystemTiming {
+                name,
+                timing: RunTiming::default(),
+            })
+            .timing
+            .record(duration);
+    }
+
+    pub(super) fn record_schedule(&mut self, name: String, duration: Duration) {
+        self.schedules.entry(name).or_default().record(duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::{ExecutorKind, ScheduleLabel},
+        system::System,
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    fn quick() {}
+
+    fn slow() {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    fn never() {}
+
+    fn name_of<Marker>(system: impl IntoSystem<(), (), Marker>) -> Cow<'static, str> {
+        IntoSystem::into_system(system).name()
+    }
+
+    fn timings_named<'a>(
+        timings: &'a ScheduleTimings,
+        name: Cow<'static, str>,
+    ) -> impl Iterator<Item = (&'a SystemKey, RunTiming)> {
+        timings
+            .systems()
+            .filter(move |(_, system)| system.name == name)
+            .map(|(key, system)| (key, system.timing))
+    }
+
+    const EXECUTORS: [ExecutorKind; 3] = [
+        ExecutorKind::SingleThreaded,
+        ExecutorKind::Simple,
+        ExecutorKind::MultiThreaded,
+    ];
+
+    fn setup(executor: ExecutorKind) -> World {
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        schedule
+            .set_executor_kind(executor)
+            .add_systems((quick, slow, never.run_if(|| false)));
+        world.add_schedule(schedule, TestSchedule);
+        world
+    }
+
+    #[test]
+    fn records_systems_that_ran() {
+        for executor in EXECUTORS {
+            let mut world = setup(executor);
+            world.init_resource::<ScheduleTimings>();
+            world.run_schedule(TestSchedule);
+            world.run_schedule(TestSchedule);
+
+            let timings = world.resource::<ScheduleTimings>();
+            let (slow_key, slow_timing) = timings_named(timings, name_of(slow)).next().unwrap();
+            assert_eq!(slow_key.schedule, "TestSchedule");
+            assert_eq!(slow_timing.runs, 2);
+            assert!(slow_timing.duration >= Duration::from_millis(10));
+            let (_, quick_timing) = timings_named(timings, name_of(quick)).next().unwrap();
+            assert_eq!(quick_timing.runs, 2);
+            assert_eq!(timings_named(timings, name_of(never)).count(), 0);
+
+            let schedule = timings.schedule("TestSchedule").unwrap();
+            assert_eq!(schedule.runs, 2);
+            assert!(schedule.duration >= slow_timing.duration);
+
+            let slowest = timings.slowest_systems(1);
+            assert_eq!(slowest, vec![(slow_key, timings.system(slow_key).unwrap())]);
+        }
+    }
+
+    #[test]
+    fn same_named_systems_are_recorded_separately() {
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        schedule.add_systems((quick, quick));
+        world.add_schedule(schedule, TestSchedule);
+        world.init_resource::<ScheduleTimings>();
+        world.run_schedule(TestSchedule);
+
+        let timings = world.resource::<ScheduleTimings>();
+        let mut indices: Vec<_> = timings_named(timings, name_of(quick))
+            .map(|(key, timing)| {
+                assert_eq!(timing.runs, 1);
+                key.index
+            })
+            .collect();
+        indices.sort_unstable();
+        assert_eq!(indices, [0, 1]);
+    }
+
+    #[test]
+    fn disabled_timings_record_nothing() {
+        for executor in EXECUTORS {
+            let mut world = setup(executor);
+            let mut timings = ScheduleTimings::new();
+            timings.set_enabled(false);
+            world.insert_resource(timings);
+            world.run_schedule(TestSchedule);
+
+            let timings = world.resource::<ScheduleTimings>();
+            assert_eq!(timings.systems().count(), 0);
+            assert_eq!(timings.schedules().count(), 0);
+        }
+    }
+
+    #[test]
+    fn drain_takes_measurements() {
+        let mut world = setup(ExecutorKind::SingleThreaded);
+        world.init_resource::<ScheduleTimings>();
+        world.run_schedule(TestSchedule);
+
+        let (systems, schedules) = world.resource_mut::<ScheduleTimings>().drain();
+        assert_eq!(systems.len(), 2);
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(world.resource::<ScheduleTimings>().systems().count(), 0);
+    }
+}
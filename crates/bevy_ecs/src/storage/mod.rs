@@ -21,14 +21,18 @@
 //! [`World::storages`]: crate::world::World::storages
 
 mod blob_vec;
+mod previous;
 mod resource;
 mod sparse_set;
 mod table;
 
+pub use previous::*;
 pub use resource::*;
 pub use sparse_set::*;
 pub use table::*;
 
+use crate::component::ComponentId;
+
 /// The raw data stores of a [World](crate::world::World)
 #[derive(Default)]
 pub struct Storages {
@@ -40,4 +44,6 @@ pub struct Storages {
     pub resources: Resources<true>,
     /// Backing storage for `!Send` resources.
     pub non_send_resources: Resources<false>,
+    /// Previous values of the components that track them.
+    pub previous_values: SparseSet<ComponentId, PreviousValues>,
 }
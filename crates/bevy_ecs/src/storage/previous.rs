@@ -0,0 +1,233 @@
+use std::{
+    any::Any,
+    sync::{PoisonError, RwLock},
+};
+
+use bevy_ptr::Ptr;
+use bevy_utils::HashMap;
+
+use crate::{
+    component::{Tick, TrackPrevious},
+    entity::Entity,
+};
+
+/// The values a component type had before their first mutation in the current frame.
+///
+/// Only exists for components with [`TrackPrevious`] enabled. Values are captured by the
+/// first mutable dereference of the component in each frame, and discarded by
+/// [`World::clear_trackers`](crate::world::World::clear_trackers).
+pub struct PreviousValues {
+    track: TrackPrevious,
+    // Entries are only inserted while the component is accessed mutably, and only removed
+    // through `&mut self`. The values are boxed, so references to them stay valid while other
+    // entities insert their own entries.
+    values: RwLock<HashMap<Entity, PreviousValue>>,
+}
+
+struct PreviousValue {
+    value: Box<dyn Any + Send + Sync>,
+    changed: Tick,
+}
+
+impl PreviousValues {
+    pub(crate) fn new(track: TrackPrevious) -> Self {
+        Self {
+            track,
+            values: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the number of entities with a captured previous value.
+    pub fn len(&self) -> usize {
+        self.values
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns `true` if no previous value has been captured in the current frame.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if a previous value has been captured for `entity` in the current frame.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.values
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(&entity)
+    }
+
+    /// Captures a clone of the component value of `entity`, unless one was already captured in
+    /// the current frame.
+    ///
+    /// `changed` is the change tick of the value before the mutation.
+    ///
+    /// # Safety
+    /// - `value` must point to the component value of `entity`, of the type these values track.
+    /// - The caller must have mutable access to the component of `entity`.
+    pub(crate) unsafe fn capture(&self, entity: Entity, value: Ptr<'_>, changed: Tick) {
+        if self.contains(entity) {
+            return;
+        }
+        let mut values = self.values.write().unwrap_or_else(PoisonError::into_inner);
+        values.entry(entity).or_insert_with(|| PreviousValue {
+            // SAFETY: ensured by the caller
+            value: unsafe { self.track.clone_value(value) },
+            changed,
+        });
+    }
+
+    /// Returns the value captured for `entity` in the current frame, if any.
+    ///
+    /// # Safety
+    /// The caller must have shared access to the component of `entity` for `'a`, so that its
+    /// captured value is not replaced while borrowed.
+    pub(crate) unsafe fn get<'a, T: Any>(&'a self, entity: Entity) -> Option<&'a T> {
+        let values = self.values.read().unwrap_or_else(PoisonError::into_inner);
+        let value: *const T = values.get(&entity)?.value.downcast_ref::<T>()?;
+        // SAFETY: the value is boxed, so it does not move when the map is modified, and it can
+        // only be replaced or dropped by mutable access to the component or through `&mut self`.
+        Some(unsafe { &*value })
+    }
+
+    /// Calls `f` with every captured value, along with the change tick its component had before
+    /// it was captured.
+    pub(crate) fn for_each(&mut self, mut f: impl FnMut(Entity, &dyn Any, Tick)) {
+        let values = self
+            .values
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        for (&entity, previous) in values.iter() {
+            f(entity, &*previous.value, previous.changed);
+        }
+    }
+
+    pub(crate) fn check_change_ticks(&mut self, change_tick: Tick) {
+        let values = self
+            .values
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        for previous in values.values_mut() {
+            previous.changed.check_tick(change_tick);
+        }
+    }
+
+    /// Moves the captured values out into a new store, leaving this one empty.
+    pub(crate) fn take(&mut self) -> Self {
+        let values = self
+            .values
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        Self {
+            track: self.track,
+            values: RwLock::new(std::mem::take(values)),
+        }
+    }
+
+    /// Discards every captured value.
+    pub(crate) fn clear(&mut self) {
+        self.values
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{
+        change_detection::DetectChangesMut,
+        prelude::*,
+        query::Previous,
+        world::{Mut, Ref},
+    };
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    #[component(track_previous)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    #[component(storage = "SparseSet")]
+    struct Shield(u32);
+
+    #[test]
+    fn captures_value_before_first_mutation() {
+        let mut world = World::new();
+        let entity = world.spawn(Health(1)).id();
+        world.clear_trackers();
+
+        world.get_mut::<Health>(entity).unwrap().0 = 2;
+        world.get_mut::<Health>(entity).unwrap().0 = 3;
+        let mut query = world.query::<(&Health, Previous<Health>)>();
+        assert_eq!(query.single(&world), (&Health(3), &Health(1)));
+        let health: Ref<Health> = world.entity(entity).get_ref().unwrap();
+        assert_eq!(health.previous(), Some(&Health(1)));
+
+        world.clear_trackers();
+        assert_eq!(query.single(&world), (&Health(3), &Health(3)));
+        let health: Ref<Health> = world.entity(entity).get_ref().unwrap();
+        assert_eq!(health.previous(), Some(&Health(3)));
+        let shield = world.spawn(Shield(1)).id();
+        let shield: Ref<Shield> = world.entity(shield).get_ref().unwrap();
+        assert_eq!(shield.previous(), None);
+    }
+
+    #[test]
+    fn reading_and_bypassing_do_not_capture() {
+        let mut world = World::new();
+        let entity = world.spawn(Health(1)).id();
+        let id = world.component_id::<Health>().unwrap();
+
+        let mut health: Mut<Health> = world.get_mut(entity).unwrap();
+        assert_eq!(health.0, 1);
+        health.bypass_change_detection().0 = 2;
+        assert!(world.storages().previous_values.get(id).unwrap().is_empty());
+
+        let mut query = world.query::<&mut Health>();
+        query.single_mut(&mut world).0 = 3;
+        assert!(world
+            .storages()
+            .previous_values
+            .get(id)
+            .unwrap()
+            .contains(entity));
+    }
+
+    #[test]
+    fn track_previous_at_runtime() {
+        let mut world = World::new();
+        let entity = world.spawn(Shield(5)).id();
+        world.get_mut::<Shield>(entity).unwrap().0 = 6;
+        let mut query = world.query::<Previous<Shield>>();
+        assert_eq!(query.single(&world), &Shield(6));
+
+        world.track_previous::<Shield>();
+        world.get_mut::<Shield>(entity).unwrap().0 = 7;
+        assert_eq!(query.single(&world), &Shield(6));
+    }
+
+    #[test]
+    fn suppress_unchanged_writes() {
+        let mut world = World::new();
+        let same = world.spawn(Health(1)).id();
+        let changed = world.spawn(Health(1)).id();
+        world.clear_trackers();
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems(move |mut query: Query<(Entity, &mut Health)>| {
+            for (entity, mut health) in &mut query {
+                health.0 = 2;
+                if entity == same {
+                    health.0 = 1;
+                }
+            }
+        });
+        schedule.run(&mut world);
+        world.suppress_unchanged_writes::<Health>();
+
+        let mut query = world.query_filtered::<Entity, Changed<Health>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![changed]);
+    }
+}
@@ -74,6 +74,18 @@ impl<const SEND: bool> ResourceData<SEND> {
         self.id
     }
 
+    /// Gets the type name of the resource.
+    #[inline]
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Gets the number of bytes allocated for the resource, including its change detection ticks.
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.column.allocated_bytes()
+    }
+
     /// Returns a reference to the resource, if it exists.
     ///
     /// # Panics
@@ -289,4 +301,12 @@ impl<const SEND: bool> Resources<SEND> {
             info.column.check_change_ticks(change_tick);
         }
     }
+
+    /// Releases the memory of resources that have been removed.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for info in self.resources.values_mut() {
+            // Only ever releases memory of empty columns, so `!Send` values are never touched.
+            info.column.shrink_to_fit();
+        }
+    }
 }
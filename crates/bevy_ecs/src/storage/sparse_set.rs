@@ -102,6 +102,22 @@ impl<I: SparseSetIndex, V> SparseArray<I, V> {
         self.values.clear();
     }
 
+    /// Gets the number of bytes allocated by the [`SparseArray`].
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        allocated_bytes(&self.values)
+    }
+
+    /// Drops trailing empty slots and releases any unused memory.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let len = self
+            .values
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |index| index + 1);
+        self.values.truncate(len);
+        self.values.shrink_to_fit();
+    }
+
     /// Converts the [`SparseArray`] into an immutable variant.
     pub(crate) fn into_immutable(self) -> ImmutableSparseArray<I, V> {
         ImmutableSparseArray {
@@ -340,6 +356,40 @@ impl ComponentSparseSet {
     pub(crate) fn check_change_ticks(&mut self, change_tick: Tick) {
         self.dense.check_change_ticks(change_tick);
     }
+
+    /// Gets the number of component values the sparse set can store without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.dense.capacity()
+    }
+
+    /// Gets the number of bytes allocated by the sparse set, including change detection ticks and
+    /// the entity lookup tables.
+    pub fn allocated_bytes(&self) -> usize {
+        self.dense.allocated_bytes()
+            + allocated_bytes(&self.entities)
+            + self.sparse.allocated_bytes()
+    }
+
+    /// Gets the number of bytes used by the component values, their change detection ticks and the
+    /// entity lookup tables.
+    pub fn used_bytes(&self) -> usize {
+        self.dense.used_bytes()
+            + std::mem::size_of_val(self.entities.as_slice())
+            + self.sparse.allocated_bytes()
+    }
+
+    /// Shrinks the capacity of the sparse set to its length, releasing any unused memory.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.dense.shrink_to_fit();
+        self.entities.shrink_to_fit();
+        self.sparse.shrink_to_fit();
+    }
+}
+
+/// Gets the number of bytes allocated by `vec`.
+fn allocated_bytes<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * std::mem::size_of::<T>()
 }
 
 /// A data structure that blends dense and sparse storage
@@ -629,6 +679,13 @@ impl SparseSets {
             set.check_change_ticks(change_tick);
         }
     }
+
+    /// Shrinks the capacity of every [`ComponentSparseSet`] to its length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for set in self.sets.values_mut() {
+            set.shrink_to_fit();
+        }
+    }
 }
 
 #[cfg(test)]
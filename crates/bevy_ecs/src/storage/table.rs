@@ -182,6 +182,32 @@ impl Column {
         self.data.is_empty()
     }
 
+    /// Gets the number of elements the column can store without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.added_ticks.capacity()
+    }
+
+    /// Gets the number of bytes allocated by the column, including its change detection ticks.
+    pub fn allocated_bytes(&self) -> usize {
+        self.data.layout().size() * self.data.capacity()
+            + (self.added_ticks.capacity() + self.changed_ticks.capacity())
+                * std::mem::size_of::<UnsafeCell<Tick>>()
+    }
+
+    /// Gets the number of bytes used by the elements stored in the column, including their change
+    /// detection ticks.
+    pub fn used_bytes(&self) -> usize {
+        self.len() * (self.data.layout().size() + 2 * std::mem::size_of::<UnsafeCell<Tick>>())
+    }
+
+    /// Shrinks the capacity of the column to its length, releasing any unused memory.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+        self.added_ticks.shrink_to_fit();
+        self.changed_ticks.shrink_to_fit();
+    }
+
     /// Removes an element from the [`Column`].
     ///
     /// - The value will be dropped if it implements [`Drop`].
@@ -778,6 +804,20 @@ impl Table {
         self.columns.values()
     }
 
+    /// Iterates over the [`Column`]s of the [`Table`], along with the [`ComponentId`] stored in
+    /// each of them.
+    pub fn iter_with_ids(&self) -> impl Iterator<Item = (ComponentId, &Column)> {
+        self.columns.iter().map(|(id, column)| (*id, column))
+    }
+
+    /// Shrinks the capacity of the table to its entity count, releasing any unused memory.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+        for column in self.columns.values_mut() {
+            column.shrink_to_fit();
+        }
+    }
+
     /// Clears all of the stored components in the [`Table`].
     pub(crate) fn clear(&mut self) {
         self.entities.clear();
@@ -886,6 +926,13 @@ impl Tables {
         }
     }
 
+    /// Shrinks the capacity of every [`Table`] stored within to its entity count.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for table in &mut self.tables {
+            table.shrink_to_fit();
+        }
+    }
+
     pub(crate) fn check_change_ticks(&mut self, change_tick: Tick) {
         for table in &mut self.tables {
             table.check_change_ticks(change_tick);
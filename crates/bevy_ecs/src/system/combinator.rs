@@ -190,6 +190,12 @@ where
         )
     }
 
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: The world accesses for both underlying systems have been registered,
+        // and `update_archetype_component_access` forwards to both of them.
+        self.a.validate_param_unsafe(world) && self.b.validate_param_unsafe(world)
+    }
+
     fn apply_deferred(&mut self, world: &mut World) {
         self.a.apply_deferred(world);
         self.b.apply_deferred(world);
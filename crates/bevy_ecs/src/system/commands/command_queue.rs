@@ -90,8 +90,17 @@ impl CommandQueue {
         }
     }
 
+    /// Returns `true` if there are no commands in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
     /// Execute the queued [`Command`]s in the world.
     /// This clears the queue.
+    ///
+    /// Any commands queued by [`ComponentHooks`](crate::component::ComponentHooks) while applying
+    /// this queue are applied afterwards, see [`World::flush_commands`].
     #[inline]
     pub fn apply(&mut self, world: &mut World) {
         // flush the previously queued entities
@@ -135,6 +144,8 @@ impl CommandQueue {
             // or 1 byte past the end, so this addition will not overflow the pointer's allocation.
             cursor = unsafe { cursor.add(size) };
         }
+
+        world.flush_commands();
     }
 }
 
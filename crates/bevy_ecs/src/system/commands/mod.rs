@@ -4,7 +4,7 @@ mod parallel_scope;
 use crate::{
     self as bevy_ecs,
     bundle::Bundle,
-    entity::{Entities, Entity},
+    entity::{Entities, Entity, EntityCloneBuilder},
     world::{FromWorld, World},
 };
 use bevy_ecs_macros::SystemParam;
@@ -13,7 +13,7 @@ pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
 
-use super::{Deferred, Resource, SystemBuffer, SystemMeta};
+use super::{Deferred, Resource, RunSystem, SystemBuffer, SystemId, SystemMeta};
 
 /// A [`World`] mutation.
 ///
@@ -519,6 +519,15 @@ impl<'w, 's> Commands<'w, 's> {
         self.queue.push(RemoveResource::<R>::new());
     }
 
+    /// Runs the system corresponding to the given [`SystemId`].
+    /// Systems are ran in an exclusive and single threaded way.
+    /// Running slow systems can become a bottleneck.
+    ///
+    /// Calls [`World::run_system`](crate::world::World::run_system).
+    pub fn run_system(&mut self, id: SystemId) {
+        self.queue.push(RunSystem::new(id));
+    }
+
     /// Pushes a generic [`Command`] to the command queue.
     ///
     /// `command` can be a built-in command, custom struct that implements [`Command`] or a closure
@@ -831,6 +840,51 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         });
     }
 
+    /// Spawns a clone of this entity with all of its components that can be cloned, and returns
+    /// the [`EntityCommands`] of the clone.
+    ///
+    /// See [`World::clone_entity`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if the associated entity does not exist.
+    pub fn clone_and_spawn(&mut self) -> EntityCommands<'w, 's, '_> {
+        self.clone_and_spawn_with(|_| {})
+    }
+
+    /// Spawns a clone of this entity configured by `f`, and returns the [`EntityCommands`] of the clone.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Selected;
+    /// fn duplicate(mut commands: Commands, query: Query<Entity, With<Selected>>) {
+    ///     for entity in &query {
+    ///         commands.entity(entity).clone_and_spawn_with(|builder| {
+    ///             builder.recursive(true).deny::<Selected>();
+    ///         });
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(duplicate);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if the associated entity does not exist.
+    pub fn clone_and_spawn_with(
+        &mut self,
+        f: impl FnOnce(&mut EntityCloneBuilder),
+    ) -> EntityCommands<'w, 's, '_> {
+        let mut builder = EntityCloneBuilder::new();
+        f(&mut builder);
+        let source = self.entity;
+        let target = self.commands.spawn_empty().id();
+        self.commands.add(move |world: &mut World| {
+            builder.clone_entity_into(world, source, target);
+        });
+        self.commands.entity(target)
+    }
+
     /// Returns the underlying [`Commands`].
     pub fn commands(&mut self) -> &mut Commands<'w, 's> {
         self.commands
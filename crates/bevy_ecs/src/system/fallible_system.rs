@@ -0,0 +1,312 @@
+use std::{any::TypeId, borrow::Cow, sync::Arc};
+
+use bevy_utils::tracing::{error, warn};
+
+use crate::{
+    self as bevy_ecs,
+    archetype::ArchetypeComponentId,
+    component::{ComponentId, Tick},
+    query::Access,
+    system::{Resource, System},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// The error type returned by fallible systems.
+///
+/// Any error type can be converted into a [`SystemError`] with `?`, so systems can return
+/// `Result<(), SystemError>` and propagate errors of different types.
+pub type SystemError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Information about the system that returned a [`SystemError`].
+#[derive(Debug, Clone)]
+pub struct SystemErrorContext {
+    /// The name of the system that returned the error.
+    pub name: Cow<'static, str>,
+    /// The tick at which the system last ran before the error.
+    pub last_run: Tick,
+}
+
+/// [`Resource`] holding the function that is called when a system returning a [`Result`] fails.
+///
+/// Systems returning `Result<(), E>` can be added to schedules like any other system. When they
+/// return an error, it is converted into a [`SystemError`] and passed to the handler found in the
+/// world. If there is no handler, [`SystemErrorHandler::panic`] is used.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::SystemErrorHandler;
+/// #
+/// fn parse_config() -> Result<(), SystemError> {
+///     let _value: u32 = "not a number".parse()?;
+///     Ok(())
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(SystemErrorHandler::warn());
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(parse_config);
+/// // logs a warning instead of panicking
+/// schedule.run(&mut world);
+/// ```
+#[derive(Resource, Clone)]
+pub struct SystemErrorHandler(Arc<dyn Fn(SystemError, SystemErrorContext) + Send + Sync>);
+
+impl SystemErrorHandler {
+    /// Creates a handler that calls `handler` with each error.
+    pub fn new(handler: impl Fn(SystemError, SystemErrorContext) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(handler))
+    }
+
+    /// Creates a handler that panics with the error. This is the default.
+    pub fn panic() -> Self {
+        Self::new(|error, context| {
+            panic!("Encountered an error in system `{}`: {error}", context.name);
+        })
+    }
+
+    /// Creates a handler that logs the error at the error level.
+    pub fn error() -> Self {
+        Self::new(|error, context| {
+            error!("Encountered an error in system `{}`: {error}", context.name);
+        })
+    }
+
+    /// Creates a handler that logs the error at the warning level.
+    pub fn warn() -> Self {
+        Self::new(|error, context| {
+            warn!("Encountered an error in system `{}`: {error}", context.name);
+        })
+    }
+
+    /// Creates a handler that ignores the error.
+    pub fn ignore() -> Self {
+        Self::new(|_, _| {})
+    }
+
+    /// Calls the handler with an error returned by a system.
+    pub fn handle(&self, error: SystemError, context: SystemErrorContext) {
+        (self.0)(error, context);
+    }
+}
+
+impl Default for SystemErrorHandler {
+    fn default() -> Self {
+        Self::panic()
+    }
+}
+
+impl std::fmt::Debug for SystemErrorHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SystemErrorHandler").finish_non_exhaustive()
+    }
+}
+
+/// A [`System`] that passes the errors returned by the system it wraps to the
+/// [`SystemErrorHandler`].
+///
+/// This is created when a system returning `Result<(), E>` is added to a schedule.
+pub struct FallibleSystem<S> {
+    system: S,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl<S, E> FallibleSystem<S>
+where
+    S: System<In = (), Out = Result<(), E>>,
+    E: Into<SystemError> + 'static,
+{
+    /// Wraps a system returning a [`Result`].
+    pub fn new(system: S) -> Self {
+        Self {
+            system,
+            component_access: Access::default(),
+            archetype_component_access: Access::default(),
+        }
+    }
+
+    fn context(&self) -> SystemErrorContext {
+        SystemErrorContext {
+            name: self.system.name(),
+            last_run: self.system.get_last_run(),
+        }
+    }
+}
+
+impl<S, E> System for FallibleSystem<S>
+where
+    S: System<In = (), Out = Result<(), E>>,
+    E: Into<SystemError> + 'static,
+{
+    type In = ();
+    type Out = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn type_id(&self) -> TypeId {
+        self.system.type_id()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: Self::In, world: UnsafeWorldCell) -> Self::Out {
+        // SAFETY: The inner system's accesses have been registered on this system,
+        // so the caller ensures that `world` has permission to access them.
+        let result = self.system.run_unsafe(input, world);
+        if let Err(error) = result {
+            let context = self.context();
+            // SAFETY: Read access to `SystemErrorHandler` was registered in `initialize`,
+            // and the inner system has finished running.
+            match world.get_resource::<SystemErrorHandler>() {
+                Some(handler) => handler.handle(error.into(), context),
+                None => SystemErrorHandler::panic().handle(error.into(), context),
+            }
+        }
+    }
+
+    fn run(&mut self, input: Self::In, world: &mut World) -> Self::Out {
+        let result = self.system.run(input, world);
+        if let Err(error) = result {
+            let context = self.context();
+            let handler = world
+                .get_resource::<SystemErrorHandler>()
+                .cloned()
+                .unwrap_or_default();
+            handler.handle(error.into(), context);
+        }
+    }
+
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: The inner system's accesses have been registered on this system.
+        self.system.validate_param_unsafe(world)
+    }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+        let handler_id = world.initialize_resource::<SystemErrorHandler>();
+        self.component_access.extend(self.system.component_access());
+        self.component_access.add_read(handler_id);
+        let archetype_component_id = world
+            .get_resource_archetype_component_id(handler_id)
+            .unwrap();
+        self.archetype_component_access
+            .add_read(archetype_component_id);
+    }
+
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.system.update_archetype_component_access(world);
+        self.archetype_component_access
+            .extend(self.system.archetype_component_access());
+    }
+
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    fn default_system_sets(&self) -> Vec<Box<dyn crate::schedule::SystemSet>> {
+        self.system.default_system_sets()
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate as bevy_ecs;
+    use crate::{prelude::*, system::SystemErrorHandler};
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn fails(mut counter: ResMut<Counter>) -> Result<(), SystemError> {
+        counter.0 += 1;
+        let _: u32 = "nope".parse()?;
+        Ok(())
+    }
+
+    fn succeeds(mut counter: ResMut<Counter>) -> Result<(), std::fmt::Error> {
+        counter.0 += 1;
+        Ok(())
+    }
+
+    #[test]
+    fn errors_are_passed_to_handler() {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let captured = errors.clone();
+        world.insert_resource(SystemErrorHandler::new(move |error, context| {
+            captured
+                .lock()
+                .unwrap()
+                .push(format!("{}: {error}", context.name));
+        }));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((fails, succeeds));
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Counter>().0, 2);
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("fails"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Encountered an error in system")]
+    fn default_handler_panics() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(crate::schedule::ExecutorKind::SingleThreaded);
+        schedule.add_systems(fails);
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn exclusive_systems_can_fail() {
+        fn exclusive(world: &mut World) -> Result<(), SystemError> {
+            world.resource_mut::<Counter>().0 += 1;
+            Err("exclusive failure".into())
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.insert_resource(SystemErrorHandler::ignore());
+        let mut schedule = Schedule::default();
+        schedule.add_systems(exclusive);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+}
@@ -462,6 +462,17 @@ where
         out
     }
 
+    #[inline]
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        let param_state = self.param_state.as_ref().expect(Self::PARAM_MESSAGE);
+        // SAFETY:
+        // - The caller has invoked `update_archetype_component_access`, which will panic
+        //   if the world does not match.
+        // - All world accesses used by `F::Param` have been registered, so the caller
+        //   will ensure that there are no data access conflicts.
+        F::Param::validate_param(param_state, &self.system_meta, world)
+    }
+
     fn get_last_run(&self) -> Tick {
         self.system_meta.last_run
     }
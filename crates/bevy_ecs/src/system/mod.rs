@@ -106,11 +106,13 @@ mod combinator;
 mod commands;
 mod exclusive_function_system;
 mod exclusive_system_param;
+mod fallible_system;
 mod function_system;
 mod query;
 #[allow(clippy::module_inception)]
 mod system;
 mod system_param;
+mod system_registry;
 
 use std::borrow::Cow;
 
@@ -118,10 +120,12 @@ pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
 pub use exclusive_system_param::*;
+pub use fallible_system::*;
 pub use function_system::*;
 pub use query::*;
 pub use system::*;
 pub use system_param::*;
+pub use system_registry::*;
 
 use crate::world::World;
 
@@ -506,12 +510,12 @@ mod tests {
         query::{Added, Changed, Or, With, Without},
         removal_detection::RemovedComponents,
         schedule::{
-            apply_deferred, common_conditions::resource_exists, Condition, IntoSystemConfigs,
-            Schedule,
+            apply_deferred, common_conditions::resource_exists, Condition, ExecutorKind,
+            IntoSystemConfigs, Schedule,
         },
         system::{
             adapter::new, Commands, In, IntoSystem, Local, NonSend, NonSendMut, ParamSet, Query,
-            QueryComponentError, Res, ResMut, Resource, System, SystemState,
+            QueryComponentError, Res, ResMut, Resource, Single, System, SystemState,
         },
         world::{FromWorld, World},
     };
@@ -560,6 +564,67 @@ mod tests {
         schedule.run(world);
     }
 
+    #[test]
+    fn single_skips_system_without_exactly_one_match() {
+        fn single_system(mut ran: ResMut<SystemRan>, mut a: Single<&mut W<u32>, With<A>>) {
+            a.0 += 1;
+            *ran = SystemRan::Yes;
+        }
+
+        for executor in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::default();
+            world.insert_resource(SystemRan::No);
+            let mut schedule = Schedule::default();
+            schedule
+                .set_executor_kind(executor)
+                .add_systems(single_system);
+
+            schedule.run(&mut world);
+            assert_eq!(*world.resource::<SystemRan>(), SystemRan::No);
+
+            let entity = world.spawn((A, W(0u32))).id();
+            schedule.run(&mut world);
+            assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+            assert_eq!(world.get::<W<u32>>(entity).unwrap().0, 1);
+
+            world.insert_resource(SystemRan::No);
+            world.spawn((A, W(0u32)));
+            schedule.run(&mut world);
+            assert_eq!(*world.resource::<SystemRan>(), SystemRan::No);
+        }
+    }
+
+    #[test]
+    fn single_in_run_condition_skips_system_without_exactly_one_match() {
+        fn set_ran(mut ran: ResMut<SystemRan>) {
+            *ran = SystemRan::Yes;
+        }
+
+        for executor in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::default();
+            world.insert_resource(SystemRan::No);
+            let mut schedule = Schedule::default();
+            schedule
+                .set_executor_kind(executor)
+                .add_systems(set_ran.run_if(|a: Single<&W<u32>, With<A>>| a.0 > 0));
+
+            schedule.run(&mut world);
+            assert_eq!(*world.resource::<SystemRan>(), SystemRan::No);
+
+            world.spawn((A, W(1u32)));
+            schedule.run(&mut world);
+            assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+        }
+    }
+
     #[test]
     fn query_system_gets() {
         fn query_system(
@@ -1732,6 +1797,37 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Cannot transmute a read-only query")]
+    fn readonly_query_transmute_lens_to_mut_fails() {
+        let mut world = World::new();
+        world.spawn(W(42u32));
+        run_system(&mut world, move |q: Query<&mut W<u32>>| {
+            let mut rq = q.to_readonly();
+            let _lens = rq.transmute_lens::<&mut W<u32>>();
+        });
+    }
+
+    #[test]
+    fn query_transmute_lens_and_join() {
+        let mut world = World::new();
+        world.spawn((W(1u32), W(2u64)));
+        world.spawn(W(3u32));
+        world.spawn(W(4u64));
+        run_system(
+            &mut world,
+            move |mut a: Query<(Entity, &mut W<u32>)>, mut b: Query<&W<u64>>| {
+                let mut lens = a.transmute_lens::<&W<u32>>();
+                assert_eq!(lens.query().iter().map(|w| w.0).sum::<u32>(), 4);
+
+                let mut joined = a.join::<_, (&W<u32>, &W<u64>)>(&mut b);
+                let query = joined.query();
+                let (w1, w2) = query.single();
+                assert_eq!((w1.0, w2.0), (1, 2));
+            },
+        );
+    }
+
     #[test]
     #[should_panic = "Attempted to use bevy_ecs::query::state::QueryState<()> with a mismatched World."]
     fn query_validates_world_id() {
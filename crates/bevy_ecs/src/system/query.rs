@@ -2,12 +2,19 @@ use crate::{
     component::{Component, Tick},
     entity::Entity,
     query::{
-        BatchingStrategy, QueryCombinationIter, QueryEntityError, QueryIter, QueryManyIter,
-        QueryParIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyWorldQuery, WorldQuery,
+        BatchingStrategy, FilteredAccess, QueryCombinationIter, QueryEntityError, QueryIter,
+        QueryManyIter, QueryParIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyWorldQuery,
+        WorldQuery,
     },
     world::{unsafe_world_cell::UnsafeWorldCell, Mut},
 };
-use std::{any::TypeId, borrow::Borrow, fmt::Debug};
+use std::{
+    any::TypeId,
+    borrow::Borrow,
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 /// [System parameter] that provides selective access to the [`Component`] data stored in a [`World`].
 ///
@@ -1406,6 +1413,231 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
                 .is_ok()
         }
     }
+
+    /// Returns a [`QueryLens`] that can be used to get a query with a more general fetch.
+    ///
+    /// For example, this can transform a `Query<(&A, &mut B)>` to a `Query<&B>`.
+    /// This can be useful for passing the query to another function. Note that since
+    /// filter terms are dropped, non-archetypal filters like `Added` and `Changed` will
+    /// not be respected. To maintain or change filter terms see [`Self::transmute_lens_filtered`].
+    ///
+    /// ## Panics
+    ///
+    /// This will panic if `NewQ` is not a subset of the original fetch `Q`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::QueryLens;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct A(usize);
+    /// #
+    /// # #[derive(Component)]
+    /// # struct B(usize);
+    /// #
+    /// # let mut world = World::new();
+    /// #
+    /// # world.spawn((A(10), B(5)));
+    /// #
+    /// fn reusable_function(lens: &mut QueryLens<&A>) {
+    ///     assert_eq!(lens.query().single().0, 10);
+    /// }
+    ///
+    /// // We can use the function in a system that takes the exact query.
+    /// fn system_1(mut query: Query<&A>) {
+    ///     reusable_function(&mut query.as_query_lens());
+    /// }
+    ///
+    /// // We can also use it with a query that does not match exactly
+    /// // by transmuting it.
+    /// fn system_2(mut query: Query<(&mut A, &B)>) {
+    ///     let mut lens = query.transmute_lens::<&A>();
+    ///     reusable_function(&mut lens);
+    /// }
+    ///
+    /// # let mut schedule = Schedule::default();
+    /// # schedule.add_systems((system_1, system_2));
+    /// # schedule.run(&mut world);
+    /// ```
+    ///
+    /// ## Allowed Transmutes
+    ///
+    /// Besides removing parameters from the query, you can also
+    /// make limited changes to the types of parameters.
+    ///
+    /// * Can always add/remove `Entity`
+    /// * `Ref<T>` <-> `&T`
+    /// * `&mut T` -> `&T`
+    /// * `&mut T` -> `Ref<T>`
+    /// * [`EntityRef`](crate::world::EntityRef) -> `Ref<T>`
+    /// * [`FilteredEntityRef`](crate::world::FilteredEntityRef) and
+    ///   [`FilteredEntityMut`](crate::world::FilteredEntityMut) can be taken from any query, and
+    ///   only see the components the original query accesses
+    ///
+    /// The lens iterates over the tables and archetypes matched by the original query,
+    /// so archetypal filters added with [`Self::transmute_lens_filtered`] are not used
+    /// to exclude any entity.
+    #[track_caller]
+    pub fn transmute_lens<NewQ: WorldQuery>(&mut self) -> QueryLens<'_, NewQ> {
+        self.transmute_lens_filtered::<NewQ, ()>()
+    }
+
+    /// Equivalent to [`Self::transmute_lens`] but also includes a [`ReadOnlyWorldQuery`] filter type.
+    ///
+    /// Note that the lens will iterate the same tables and archetypes as the original query. This means that
+    /// additional archetypal query terms like [`With`](crate::query::With) and [`Without`](crate::query::Without)
+    /// will not necessarily be respected and non-archetypal terms like [`Added`](crate::query::Added) and
+    /// [`Changed`](crate::query::Changed) will only be respected if they are in the type signature.
+    #[track_caller]
+    pub fn transmute_lens_filtered<NewQ: WorldQuery, NewF: ReadOnlyWorldQuery>(
+        &mut self,
+    ) -> QueryLens<'_, NewQ, NewF> {
+        // SAFETY: `get_state` only reads component metadata from the world.
+        let world = unsafe { self.world.world_metadata() };
+        let state = self.state.transmute_filtered::<NewQ, NewF>(world);
+        if self.force_read_only_component_access {
+            assert_read_only_fetch::<NewQ>(&state);
+        }
+        QueryLens {
+            world: self.world,
+            state,
+            last_run: self.last_run,
+            this_run: self.this_run,
+            force_read_only_component_access: self.force_read_only_component_access,
+        }
+    }
+
+    /// Gets a [`QueryLens`] with the same accesses as the existing query
+    pub fn as_query_lens(&mut self) -> QueryLens<'_, Q> {
+        self.transmute_lens()
+    }
+
+    /// Returns a [`QueryLens`] that can be used to get a query with the combined fetch.
+    ///
+    /// For example, this can take a `Query<&A>` and a `Query<&B>` and return a `Query<(&A, &B)>`.
+    /// The returned query will only return items with both `A` and `B`. Note that since filters
+    /// are dropped, non-archetypal filters like `Added` and `Changed` will not be respected.
+    /// To maintain or change filter terms see `Self::join_filtered`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::QueryLens;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Transform;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Player;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// #
+    /// # let mut world = World::default();
+    /// # world.spawn((Transform, Player));
+    /// # world.spawn((Transform, Enemy));
+    ///
+    /// fn system(
+    ///     mut transforms: Query<&Transform>,
+    ///     mut players: Query<&Player>,
+    ///     mut enemies: Query<&Enemy>
+    /// ) {
+    ///     let mut players_transforms: QueryLens<(&Transform, &Player)> = transforms.join(&mut players);
+    ///     for (transform, player) in &players_transforms.query() {
+    ///         // do something with the transform
+    ///     }
+    ///
+    ///     let mut enemies_transforms: QueryLens<(&Transform, &Enemy)> = transforms.join(&mut enemies);
+    ///     for (transform, enemy) in &enemies_transforms.query() {
+    ///         // do something with the transform
+    ///     }
+    /// }
+    ///
+    /// # let mut schedule = Schedule::default();
+    /// # schedule.add_systems(system);
+    /// # schedule.run(&mut world);
+    /// ```
+    /// ## Panics
+    ///
+    /// This will panic if `NewQ` is not a subset of the union of the original fetch `Q` and `OtherQ`.
+    ///
+    /// ## Allowed Transmutes
+    ///
+    /// Like `transmute_lens` the query terms can be changed with some restrictions.
+    /// See [`Self::transmute_lens`] for more details.
+    ///
+    /// ## Borrowing
+    ///
+    /// The lens borrows both queries, so neither can be used while it is alive:
+    ///
+    /// ```compile_fail
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::QueryLens;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct A;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct B;
+    /// #
+    /// fn system(mut a: Query<&A>, mut b: Query<&mut B>) {
+    ///     let mut lens: QueryLens<(&A, &mut B)> = a.join(&mut b);
+    ///     let mut joined = lens.query();
+    ///     // `b` is still borrowed by `lens`
+    ///     for mut b in &mut b {
+    ///         for (_, mut joined_b) in &mut joined {
+    ///             std::mem::swap(&mut *b, &mut *joined_b);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn join<'a, OtherQ: WorldQuery, NewQ: WorldQuery>(
+        &'a mut self,
+        other: &'a mut Query<OtherQ>,
+    ) -> QueryLens<'a, NewQ> {
+        self.join_filtered(other)
+    }
+
+    /// Equivalent to [`Self::join`] but also includes a [`ReadOnlyWorldQuery`] filter type.
+    ///
+    /// Note that the lens with iterate a subset of the original queries' tables
+    /// and archetypes. This means that additional archetypal query terms like
+    /// `With` and `Without` will not necessarily be respected and non-archetypal
+    /// terms like `Added` and `Changed` will only be respected if they are in
+    /// the type signature.
+    #[track_caller]
+    pub fn join_filtered<
+        'a,
+        OtherQ: WorldQuery,
+        OtherF: ReadOnlyWorldQuery,
+        NewQ: WorldQuery,
+        NewF: ReadOnlyWorldQuery,
+    >(
+        &'a mut self,
+        other: &'a mut Query<OtherQ, OtherF>,
+    ) -> QueryLens<'a, NewQ, NewF> {
+        // SAFETY: `get_state` only reads component metadata from the world.
+        let world = unsafe { self.world.world_metadata() };
+        let state = self
+            .state
+            .join_filtered::<OtherQ, OtherF, NewQ, NewF>(world, other.state);
+        let force_read_only_component_access =
+            self.force_read_only_component_access || other.force_read_only_component_access;
+        if force_read_only_component_access {
+            assert_read_only_fetch::<NewQ>(&state);
+        }
+        QueryLens {
+            world: self.world,
+            state,
+            last_run: self.last_run,
+            this_run: self.this_run,
+            force_read_only_component_access,
+        }
+    }
 }
 
 impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> IntoIterator for &'w Query<'_, 's, Q, F> {
@@ -1426,6 +1658,105 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> IntoIterator for &'w mut Quer
     }
 }
 
+// A query that was made read-only with `Query::to_readonly` still carries the write access of the
+// original one, so a lens created from it must not be allowed to write either.
+#[track_caller]
+fn assert_read_only_fetch<Q: WorldQuery>(state: &QueryState<Q, impl ReadOnlyWorldQuery>) {
+    let mut access = FilteredAccess::default();
+    Q::update_component_access(&state.fetch_state, &mut access);
+    assert!(
+        !access.access().has_any_write(),
+        "Cannot transmute a read-only query into {}, which writes to components.",
+        std::any::type_name::<Q>()
+    );
+}
+
+/// Type returned from [`Query::transmute_lens`] containing the new [`QueryState`].
+///
+/// Call [`query`](QueryLens::query) or [`into`](Into::into) to construct the resulting [`Query`]
+pub struct QueryLens<'w, Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
+    world: UnsafeWorldCell<'w>,
+    state: QueryState<Q, F>,
+    last_run: Tick,
+    this_run: Tick,
+    force_read_only_component_access: bool,
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryLens<'w, Q, F> {
+    /// Create a [`Query`] from the underlying [`QueryState`].
+    pub fn query(&mut self) -> Query<'w, '_, Q, F> {
+        // SAFETY: the lens borrows the query it was created from mutably, and its state
+        // was checked to only access a subset of what that query is allowed to access.
+        unsafe {
+            Query::new(
+                self.world,
+                &self.state,
+                self.last_run,
+                self.this_run,
+                self.force_read_only_component_access,
+            )
+        }
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> From<&'s mut QueryLens<'w, Q, F>>
+    for Query<'w, 's, Q, F>
+{
+    fn from(value: &'s mut QueryLens<'w, Q, F>) -> Query<'w, 's, Q, F> {
+        value.query()
+    }
+}
+
+/// [System parameter] that provides access to the single entity matching a [`Query`].
+///
+/// Unlike [`Query::single`], which panics when the query does not match exactly one entity,
+/// a system with a `Single` parameter is skipped when there is no matching entity, or more than one.
+/// This makes it useful for entities that only exist some of the time, like the player character.
+///
+/// [System parameter]: crate::system::SystemParam
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Player;
+/// #
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// #
+/// fn heal_player(mut health: Single<&mut Health, With<Player>>) {
+///     health.0 += 1;
+/// }
+/// # bevy_ecs::system::assert_is_system(heal_player);
+/// ```
+pub struct Single<'w, Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
+    pub(crate) item: Q::Item<'w>,
+    pub(crate) _filter: PhantomData<F>,
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> Single<'w, Q, F> {
+    /// Returns the inner query item.
+    pub fn into_inner(self) -> Q::Item<'w> {
+        self.item
+    }
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> Deref for Single<'w, Q, F> {
+    type Target = Q::Item<'w>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> DerefMut for Single<'w, Q, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
 /// An error that occurs when retrieving a specific [`Entity`]'s component from a [`Query`].
 #[derive(Debug, PartialEq, Eq)]
 pub enum QueryComponentError {
@@ -69,6 +69,34 @@ pub trait System: Send + Sync + 'static {
         unsafe { self.run_unsafe(input, world) }
     }
 
+    /// Returns `false` if the system's parameters can't currently be created, in which case
+    /// executors skip the system as if one of its run conditions had returned `false`.
+    ///
+    /// See [`SystemParam::validate_param`](crate::system::SystemParam::validate_param).
+    ///
+    /// # Safety
+    ///
+    /// - The caller must ensure that `world` has permission to read any world data
+    ///   registered in [`Self::archetype_component_access`]. There must be no conflicting
+    ///   simultaneous mutable accesses while the parameters are being validated.
+    /// - The method [`Self::update_archetype_component_access`] must be called at some
+    ///   point before this one, with the same exact [`World`].
+    unsafe fn validate_param_unsafe(&mut self, _world: UnsafeWorldCell) -> bool {
+        true
+    }
+
+    /// Returns `false` if the system's parameters can't currently be created.
+    ///
+    /// See [`System::validate_param_unsafe`].
+    fn validate_param(&mut self, world: &World) -> bool {
+        let world = world.as_unsafe_world_cell_readonly();
+        self.update_archetype_component_access(world);
+        // SAFETY:
+        // - We have read-only access to the entire world.
+        // - `update_archetype_component_access` has been called.
+        unsafe { self.validate_param_unsafe(world) }
+    }
+
     /// Applies any [`Deferred`](crate::system::Deferred) system parameters (or other system buffers) of this system to the world.
     ///
     /// This is where [`Commands`](crate::system::Commands) get applied.
@@ -188,9 +216,9 @@ impl Debug for dyn System<In = (), Out = ()> {
 /// This usage is helpful when trying to test systems or functions that operate on [`Commands`](crate::system::Commands):
 /// ```
 /// # use bevy_ecs::prelude::*;
-/// # use bevy_ecs::system::RunSystem;
+/// # use bevy_ecs::system::RunSystemOnce;
 /// let mut world = World::default();
-/// let entity = world.run_system(|mut commands: Commands| {
+/// let entity = world.run_system_once(|mut commands: Commands| {
 ///     commands.spawn_empty().id()
 /// });
 /// # assert!(world.get_entity(entity).is_some());
@@ -201,7 +229,7 @@ impl Debug for dyn System<In = (), Out = ()> {
 /// This usage is helpful when trying to run an arbitrary query on a world for testing or debugging purposes:
 /// ```
 /// # use bevy_ecs::prelude::*;
-/// # use bevy_ecs::system::RunSystem;
+/// # use bevy_ecs::system::RunSystemOnce;
 ///
 /// #[derive(Component)]
 /// struct T(usize);
@@ -210,7 +238,7 @@ impl Debug for dyn System<In = (), Out = ()> {
 /// world.spawn(T(0));
 /// world.spawn(T(1));
 /// world.spawn(T(1));
-/// let count = world.run_system(|query: Query<&T>| {
+/// let count = world.run_system_once(|query: Query<&T>| {
 ///     query.iter().filter(|t| t.0 == 1).count()
 /// });
 ///
@@ -221,7 +249,7 @@ impl Debug for dyn System<In = (), Out = ()> {
 ///
 /// ```
 /// # use bevy_ecs::prelude::*;
-/// # use bevy_ecs::system::RunSystem;
+/// # use bevy_ecs::system::RunSystemOnce;
 ///
 /// #[derive(Component)]
 /// struct T(usize);
@@ -234,26 +262,26 @@ impl Debug for dyn System<In = (), Out = ()> {
 /// world.spawn(T(0));
 /// world.spawn(T(1));
 /// world.spawn(T(1));
-/// let count = world.run_system(count);
+/// let count = world.run_system_once(count);
 ///
 /// # assert_eq!(count, 2);
 /// ```
-pub trait RunSystem: Sized {
+pub trait RunSystemOnce: Sized {
     /// Runs a system and applies its deferred parameters.
-    fn run_system<T: IntoSystem<(), Out, Marker>, Out, Marker>(self, system: T) -> Out {
-        self.run_system_with((), system)
+    fn run_system_once<T: IntoSystem<(), Out, Marker>, Out, Marker>(self, system: T) -> Out {
+        self.run_system_once_with((), system)
     }
 
     /// Runs a system with given input and applies its deferred parameters.
-    fn run_system_with<T: IntoSystem<In, Out, Marker>, In, Out, Marker>(
+    fn run_system_once_with<T: IntoSystem<In, Out, Marker>, In, Out, Marker>(
         self,
         input: In,
         system: T,
     ) -> Out;
 }
 
-impl RunSystem for &mut World {
-    fn run_system_with<T: IntoSystem<In, Out, Marker>, In, Out, Marker>(
+impl RunSystemOnce for &mut World {
+    fn run_system_once_with<T: IntoSystem<In, Out, Marker>, In, Out, Marker>(
         self,
         input: In,
         system: T,
@@ -272,7 +300,7 @@ mod tests {
     use crate::prelude::*;
 
     #[test]
-    fn run_system() {
+    fn run_system_once() {
         struct T(usize);
 
         impl Resource for T {}
@@ -283,7 +311,7 @@ mod tests {
         }
 
         let mut world = World::default();
-        let n = world.run_system_with(1, system);
+        let n = world.run_system_once_with(1, system);
         assert_eq!(n, 2);
         assert_eq!(world.resource::<T>().0, 1);
     }
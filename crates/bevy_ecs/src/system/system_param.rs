@@ -8,7 +8,7 @@ use crate::{
     query::{
         Access, FilteredAccess, FilteredAccessSet, QueryState, ReadOnlyWorldQuery, WorldQuery,
     },
-    system::{Query, SystemMeta},
+    system::{Query, Single, SystemMeta},
     world::{unsafe_world_cell::UnsafeWorldCell, FromWorld, World},
 };
 use bevy_ecs_macros::impl_param_set;
@@ -125,6 +125,28 @@ pub unsafe trait SystemParam: Sized {
     #[allow(unused_variables)]
     fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {}
 
+    /// Returns `false` if this parameter cannot currently be created, in which case executors skip
+    /// the system instead of calling [`get_param`](SystemParam::get_param), as if one of its
+    /// run conditions had returned `false`.
+    ///
+    /// Parameters that can't fail, or that panic on failure like [`Res`], don't need to implement
+    /// this. See [`Single`](crate::system::Single) for a parameter that does.
+    ///
+    /// # Safety
+    ///
+    /// - The passed [`UnsafeWorldCell`] must have read access to any world data
+    ///   registered in [`init_state`](SystemParam::init_state).
+    /// - `world` must be the same `World` that was used to initialize [`state`](SystemParam::init_state).
+    #[inline]
+    #[allow(unused_variables)]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        true
+    }
+
     /// Creates a parameter to be passed into a [`SystemParamFunction`].
     ///
     /// [`SystemParamFunction`]: super::SystemParamFunction
@@ -205,6 +227,68 @@ unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemPara
     }
 }
 
+// SAFETY: QueryState is constrained to read-only fetches, so it only reads World.
+unsafe impl<'w, Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static>
+    ReadOnlySystemParam for Single<'w, Q, F>
+{
+}
+
+// SAFETY: Relevant query ComponentId and ArchetypeComponentId access is applied to SystemMeta
+// by `Query`'s implementation, which this defers to.
+unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemParam
+    for Single<'_, Q, F>
+{
+    type State = QueryState<Q, F>;
+    type Item<'w, 's> = Single<'w, Q, F>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Query::<Q, F>::init_state(world, system_meta)
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        Query::<Q, F>::new_archetype(state, archetype, system_meta);
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: `NopWorldQuery` does not access any component data, and the caller ensures
+        // that `world` has read access to the data used by the query's filter.
+        let mut query =
+            state
+                .as_nop()
+                .iter_unchecked_manual(world, system_meta.last_run, world.change_tick());
+        query.next().is_some() && query.next().is_none()
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: We have registered all of the query's world accesses,
+        // so the caller ensures that `world` has permission to access any
+        // world data that the query needs.
+        let item = state
+            .get_single_unchecked_manual(world, system_meta.last_run, change_tick)
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Single parameter of system {} did not match exactly one entity: {error}",
+                    system_meta.name
+                )
+            });
+        Single {
+            item,
+            _filter: PhantomData,
+        }
+    }
+}
+
 fn assert_component_access_compatibility(
     system_name: &str,
     query_type: &'static str,
@@ -554,6 +638,7 @@ unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
                 changed: value.ticks.changed,
                 last_run: system_meta.last_run,
                 this_run: change_tick,
+                previous: None,
             },
         }
     }
@@ -584,6 +669,7 @@ unsafe impl<'a, T: Resource> SystemParam for Option<ResMut<'a, T>> {
                     changed: value.ticks.changed,
                     last_run: system_meta.last_run,
                     this_run: change_tick,
+                    previous: None,
                 },
             })
     }
@@ -1394,6 +1480,16 @@ macro_rules! impl_system_param_tuple {
                 $($param::apply($param, _system_meta, _world);)*
             }
 
+            #[inline]
+            unsafe fn validate_param(
+                ($($param,)*): &Self::State,
+                _system_meta: &SystemMeta,
+                _world: UnsafeWorldCell,
+            ) -> bool {
+                // not short-circuiting keeps this consistent with how run conditions are evaluated
+                true $(& $param::validate_param($param, _system_meta, _world))*
+            }
+
             #[inline]
             #[allow(clippy::unused_unit)]
             unsafe fn get_param<'w, 's>(
@@ -1537,6 +1633,15 @@ unsafe impl<P: SystemParam + 'static> SystemParam for StaticSystemParam<'_, '_,
         P::apply(state, system_meta, world);
     }
 
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Defer to the safety of P::SystemParam
+        P::validate_param(state, system_meta, world)
+    }
+
     unsafe fn get_param<'world, 'state>(
         state: &'state mut Self::State,
         system_meta: &SystemMeta,
@@ -0,0 +1,377 @@
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use bevy_utils::synccell::SyncCell;
+
+use crate::{self as bevy_ecs, entity::Entity, system::Resource, world::World};
+
+/// Async tasks that can `await` access to the [`World`] and the next frame.
+///
+/// Tasks are spawned with [`spawn`](AsyncTasks::spawn) and driven by
+/// [`apply_async_tasks`], an exclusive system that acts as their sync point: every time it runs,
+/// the tasks that were woken are polled and the [`World`] accesses they requested with
+/// [`AsyncWorld::with`] are applied, until they wait on something else.
+/// Long-running work should be spawned on a task pool such as
+/// [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool) and awaited from the task.
+///
+/// A task spawned with [`spawn_for`](AsyncTasks::spawn_for) is cancelled when its owning entity
+/// is despawned. Cancelling a task drops its future at the point where it was waiting.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::{apply_async_tasks, AsyncTasks};
+/// #[derive(Component)]
+/// struct Path(Vec<u32>);
+///
+/// fn find_path(mut commands: Commands, mut tasks: ResMut<AsyncTasks>) {
+///     let entity = commands.spawn_empty().id();
+///     tasks.spawn_for(entity, move |world| async move {
+///         let path = vec![1, 2, 3];
+///         world
+///             .with(move |world| {
+///                 world.entity_mut(entity).insert(Path(path));
+///             })
+///             .await;
+///     });
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<AsyncTasks>();
+/// let mut schedule = Schedule::new();
+/// schedule.add_systems((find_path, apply_deferred, apply_async_tasks).chain());
+/// schedule.run(&mut world);
+///
+/// assert_eq!(world.query::<&Path>().single(&world).0, vec![1, 2, 3]);
+/// ```
+#[derive(Resource, Default)]
+pub struct AsyncTasks {
+    shared: Arc<Shared>,
+    tasks: Vec<AsyncTask>,
+    next_id: u64,
+}
+
+/// The id of a task spawned on [`AsyncTasks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AsyncTaskId(u64);
+
+/// A handle given to async tasks spawned on [`AsyncTasks`] to access the [`World`].
+#[derive(Clone)]
+pub struct AsyncWorld {
+    shared: Arc<Shared>,
+    task: AsyncTaskId,
+}
+
+type WorldAccess = Box<dyn FnOnce(&mut World) + Send>;
+
+#[derive(Default)]
+struct Shared {
+    frame: AtomicU64,
+    frame_wakers: Mutex<Vec<Waker>>,
+    accesses: Mutex<Vec<(AsyncTaskId, WorldAccess)>>,
+}
+
+struct AsyncTask {
+    id: AsyncTaskId,
+    owner: Option<Entity>,
+    woken: Arc<WakeFlag>,
+    future: SyncCell<Pin<Box<dyn Future<Output = ()> + Send>>>,
+}
+
+struct WakeFlag(AtomicBool);
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+impl AsyncTasks {
+    /// Spawns an async task, created by calling `task` with its [`AsyncWorld`].
+    ///
+    /// The task is first polled the next time [`apply_async_tasks`] runs.
+    pub fn spawn<F, Fut>(&mut self, task: F) -> AsyncTaskId
+    where
+        F: FnOnce(AsyncWorld) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_inner(None, task)
+    }
+
+    /// Spawns an async task like [`spawn`](AsyncTasks::spawn), which is cancelled when `owner`
+    /// is despawned.
+    pub fn spawn_for<F, Fut>(&mut self, owner: Entity, task: F) -> AsyncTaskId
+    where
+        F: FnOnce(AsyncWorld) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_inner(Some(owner), task)
+    }
+
+    fn spawn_inner<F, Fut>(&mut self, owner: Option<Entity>, task: F) -> AsyncTaskId
+    where
+        F: FnOnce(AsyncWorld) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let id = AsyncTaskId(self.next_id);
+        self.next_id += 1;
+        let future = task(AsyncWorld {
+            shared: self.shared.clone(),
+            task: id,
+        });
+        self.tasks.push(AsyncTask {
+            id,
+            owner,
+            woken: Arc::new(WakeFlag(AtomicBool::new(true))),
+            future: SyncCell::new(Box::pin(future)),
+        });
+        id
+    }
+
+    /// Cancels the task with the given id, returning `true` if it was still running.
+    pub fn cancel(&mut self, id: AsyncTaskId) -> bool {
+        let len = self.tasks.len();
+        self.tasks.retain(|task| task.id != id);
+        self.tasks.len() != len
+    }
+
+    /// Returns `true` if the task with the given id is still running.
+    pub fn contains(&self, id: AsyncTaskId) -> bool {
+        self.tasks.iter().any(|task| task.id == id)
+    }
+
+    /// Returns the number of running tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if no task is running.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+impl AsyncWorld {
+    /// Runs `f` with exclusive access to the [`World`] the next time [`apply_async_tasks`] runs,
+    /// and returns its result.
+    pub async fn with<R, F>(&self, f: F) -> R
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut World) -> R + Send + 'static,
+    {
+        let result = Arc::new(Mutex::new(None));
+        let mut f = Some(f);
+        poll_fn(|cx| {
+            if let Some(f) = f.take() {
+                let result = result.clone();
+                let waker = cx.waker().clone();
+                let access: WorldAccess = Box::new(move |world| {
+                    *result.lock().unwrap_or_else(PoisonError::into_inner) = Some(f(world));
+                    waker.wake();
+                });
+                self.shared
+                    .accesses
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push((self.task, access));
+                return Poll::Pending;
+            }
+            match result.lock().unwrap_or_else(PoisonError::into_inner).take() {
+                Some(result) => Poll::Ready(result),
+                None => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Waits until the next time [`apply_async_tasks`] runs, usually the next frame.
+    pub async fn next_frame(&self) {
+        let frame = self.shared.frame.load(Ordering::Acquire);
+        poll_fn(|cx| {
+            if self.shared.frame.load(Ordering::Acquire) > frame {
+                return Poll::Ready(());
+            }
+            self.shared
+                .frame_wakers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(cx.waker().clone());
+            Poll::Pending
+        })
+        .await;
+    }
+
+    /// Returns the id of the task this handle was given to.
+    pub fn task_id(&self) -> AsyncTaskId {
+        self.task
+    }
+}
+
+/// The sync point of the tasks spawned on [`AsyncTasks`], if the resource exists.
+///
+/// Cancels the tasks whose owning entity was despawned, wakes the tasks waiting for the next
+/// frame, then polls the woken tasks and applies the [`World`] accesses they request until none
+/// are left.
+pub fn apply_async_tasks(world: &mut World) {
+    let Some(mut async_tasks) = world.get_resource_mut::<AsyncTasks>() else {
+        return;
+    };
+    let mut tasks = std::mem::take(&mut async_tasks.tasks);
+    tasks.retain(|task| match task.owner {
+        Some(owner) => world.get_entity(owner).is_some(),
+        None => true,
+    });
+    let mut async_tasks = world.resource_mut::<AsyncTasks>();
+    async_tasks.tasks = tasks;
+    let shared = async_tasks.shared.clone();
+
+    shared.frame.fetch_add(1, Ordering::AcqRel);
+    let frame_wakers = std::mem::take(
+        &mut *shared
+            .frame_wakers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner),
+    );
+    for waker in frame_wakers {
+        waker.wake();
+    }
+
+    loop {
+        let Some(mut async_tasks) = world.get_resource_mut::<AsyncTasks>() else {
+            return;
+        };
+        async_tasks.tasks.retain_mut(|task| {
+            if !task.woken.0.swap(false, Ordering::AcqRel) {
+                return true;
+            }
+            let waker = Waker::from(task.woken.clone());
+            let mut cx = Context::from_waker(&waker);
+            task.future.get().as_mut().poll(&mut cx).is_pending()
+        });
+
+        let accesses = std::mem::take(
+            &mut *shared
+                .accesses
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        if accesses.is_empty() {
+            return;
+        }
+        for (id, access) in accesses {
+            // The accesses of cancelled tasks are dropped.
+            if world
+                .get_resource::<AsyncTasks>()
+                .is_some_and(|async_tasks| async_tasks.contains(id))
+            {
+                access(world);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::{apply_async_tasks, AsyncTasks};
+    use crate as bevy_ecs;
+    use crate::{prelude::*, world::World};
+
+    #[derive(Resource, Default)]
+    struct Counter(usize);
+
+    #[test]
+    fn awaits_world_access_and_next_frame() {
+        let mut world = World::new();
+        world.init_resource::<AsyncTasks>();
+        world.init_resource::<Counter>();
+        let id = world
+            .resource_mut::<AsyncTasks>()
+            .spawn(|world| async move {
+                for _ in 0..3 {
+                    let count = world
+                        .with(|world| {
+                            world.resource_mut::<Counter>().0 += 1;
+                            world.resource::<Counter>().0
+                        })
+                        .await;
+                    assert!(count <= 3);
+                    world.next_frame().await;
+                }
+            });
+
+        for frame in 1..=3 {
+            apply_async_tasks(&mut world);
+            assert_eq!(world.resource::<Counter>().0, frame);
+            assert!(world.resource::<AsyncTasks>().contains(id));
+        }
+        apply_async_tasks(&mut world);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+
+    #[test]
+    fn cancels_when_owner_is_despawned() {
+        let mut world = World::new();
+        world.init_resource::<AsyncTasks>();
+        let owner = world.spawn_empty().id();
+        let polls = Arc::new(AtomicUsize::new(0));
+        let task_polls = polls.clone();
+        world
+            .resource_mut::<AsyncTasks>()
+            .spawn_for(owner, move |world| async move {
+                loop {
+                    task_polls.fetch_add(1, Ordering::Relaxed);
+                    world.next_frame().await;
+                }
+            });
+
+        apply_async_tasks(&mut world);
+        apply_async_tasks(&mut world);
+        assert_eq!(polls.load(Ordering::Relaxed), 2);
+
+        world.despawn(owner);
+        apply_async_tasks(&mut world);
+        assert_eq!(polls.load(Ordering::Relaxed), 2);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+
+    #[test]
+    fn cancel_drops_pending_access() {
+        let mut world = World::new();
+        world.init_resource::<AsyncTasks>();
+        world.init_resource::<Counter>();
+        let mut tasks = world.resource_mut::<AsyncTasks>();
+        let id = tasks.spawn(|world| async move {
+            world.next_frame().await;
+            world
+                .with(|world| world.resource_mut::<Counter>().0 += 1)
+                .await;
+        });
+        tasks.spawn(move |world| async move {
+            world
+                .with(move |world| {
+                    world.resource_mut::<AsyncTasks>().cancel(id);
+                })
+                .await;
+        });
+
+        apply_async_tasks(&mut world);
+        apply_async_tasks(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 0);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+use std::ops::Deref;
+
+use crate::{
+    change_detection::MutUntyped,
+    component::{Component, ComponentId},
+    entity::Entity,
+    event::Event,
+    system::{Commands, Resource},
+    world::{Mut, World},
+};
+
+/// A [`World`] reference that disallows structural ECS changes.
+///
+/// This includes spawning and despawning entities, as well as inserting and removing components.
+/// Existing components and resources can still be read and mutated, and structural changes can be
+/// queued through [`DeferredWorld::commands`]. Those commands are applied the next time the world
+/// flushes its commands, see [`World::flush_commands`].
+///
+/// This is the world access given to [`ComponentHooks`](crate::component::ComponentHooks),
+/// which run in the middle of structural changes where the entity being modified must not move.
+pub struct DeferredWorld<'w> {
+    world: &'w mut World,
+}
+
+impl<'w> Deref for DeferredWorld<'w> {
+    type Target = World;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.world
+    }
+}
+
+impl<'w> From<&'w mut World> for DeferredWorld<'w> {
+    #[inline]
+    fn from(world: &'w mut World) -> Self {
+        DeferredWorld { world }
+    }
+}
+
+impl<'w> DeferredWorld<'w> {
+    /// Reborrows this [`DeferredWorld`] with a shorter lifetime.
+    #[inline]
+    pub fn reborrow(&mut self) -> DeferredWorld<'_> {
+        DeferredWorld { world: self.world }
+    }
+
+    /// Gives access to the underlying [`World`].
+    ///
+    /// Callers must not make structural changes, which could invalidate entity locations
+    /// held further up the stack.
+    #[inline]
+    pub(crate) fn as_world_mut(&mut self) -> &mut World {
+        self.world
+    }
+
+    /// Creates a [`Commands`] instance that pushes to the world's command queue.
+    ///
+    /// The commands are applied the next time [`World::flush_commands`] is called,
+    /// which happens automatically after commands or despawns are applied to the world.
+    #[inline]
+    pub fn commands(&mut self) -> Commands<'_, '_> {
+        let world = &mut *self.world;
+        Commands::new_from_entities(&mut world.command_queue, &world.entities)
+    }
+
+    /// Retrieves a mutable reference to the given `entity`'s [`Component`] of the given type.
+    /// Returns `None` if the `entity` does not have a [`Component`] of the given type.
+    #[inline]
+    pub fn get_mut<T: Component>(&mut self, entity: Entity) -> Option<Mut<T>> {
+        self.world.get_mut(entity)
+    }
+
+    /// Retrieves a mutable untyped reference to the given `entity`'s [`Component`] of the given [`ComponentId`].
+    /// Returns `None` if the `entity` does not have a [`Component`] of the given type.
+    ///
+    /// **You should prefer to use the typed API [`DeferredWorld::get_mut`] where possible and only
+    /// use this in cases where the actual types are not known at compile time.**
+    #[inline]
+    pub fn get_mut_by_id(
+        &mut self,
+        entity: Entity,
+        component_id: ComponentId,
+    ) -> Option<MutUntyped<'_>> {
+        self.world.get_mut_by_id(entity, component_id)
+    }
+
+    /// Gets a mutable reference to the resource of the given type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist.
+    /// Use [`get_resource_mut`](DeferredWorld::get_resource_mut) instead if you want to handle this case.
+    #[inline]
+    #[track_caller]
+    pub fn resource_mut<R: Resource>(&mut self) -> Mut<'_, R> {
+        self.world.resource_mut()
+    }
+
+    /// Gets a mutable reference to the resource of the given type if it exists.
+    #[inline]
+    pub fn get_resource_mut<R: Resource>(&mut self) -> Option<Mut<'_, R>> {
+        self.world.get_resource_mut()
+    }
+
+    /// Gets a mutable reference to the non-send resource of the given type, if it exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist, or if this function is not called
+    /// from the main thread.
+    #[inline]
+    #[track_caller]
+    pub fn non_send_resource_mut<R: 'static>(&mut self) -> Mut<'_, R> {
+        self.world.non_send_resource_mut()
+    }
+
+    /// Gets a mutable reference to the non-send resource of the given type, if it exists.
+    /// Otherwise returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if it isn't called from the same thread that the resource was inserted from.
+    #[inline]
+    pub fn get_non_send_resource_mut<R: 'static>(&mut self) -> Option<Mut<'_, R>> {
+        self.world.get_non_send_resource_mut()
+    }
+
+    /// Sends an [`Event`].
+    ///
+    /// This method logs an error and does nothing if the [`Events`](crate::event::Events)
+    /// resource for `E` has not been initialized.
+    #[inline]
+    pub fn send_event<E: Event>(&mut self, event: E) {
+        self.world.send_event(event);
+    }
+
+    /// Triggers all `on_add` hooks for the components in `targets`.
+    #[inline]
+    pub(crate) fn trigger_on_add(
+        &mut self,
+        entity: Entity,
+        targets: impl IntoIterator<Item = ComponentId>,
+    ) {
+        for component_id in targets {
+            let hook = self
+                .world
+                .components
+                .get_info(component_id)
+                .and_then(|info| info.hooks().on_add);
+            if let Some(hook) = hook {
+                hook(self.reborrow(), entity, component_id);
+            }
+        }
+    }
+
+    /// Triggers all `on_insert` hooks for the components in `targets`.
+    #[inline]
+    pub(crate) fn trigger_on_insert(
+        &mut self,
+        entity: Entity,
+        targets: impl IntoIterator<Item = ComponentId>,
+    ) {
+        for component_id in targets {
+            let hook = self
+                .world
+                .components
+                .get_info(component_id)
+                .and_then(|info| info.hooks().on_insert);
+            if let Some(hook) = hook {
+                hook(self.reborrow(), entity, component_id);
+            }
+        }
+    }
+
+    /// Triggers all `on_replace` hooks for the components in `targets`.
+    #[inline]
+    pub(crate) fn trigger_on_replace(
+        &mut self,
+        entity: Entity,
+        targets: impl IntoIterator<Item = ComponentId>,
+    ) {
+        for component_id in targets {
+            let hook = self
+                .world
+                .components
+                .get_info(component_id)
+                .and_then(|info| info.hooks().on_replace);
+            if let Some(hook) = hook {
+                hook(self.reborrow(), entity, component_id);
+            }
+        }
+    }
+
+    /// Triggers all `on_remove` hooks for the components in `targets`.
+    #[inline]
+    pub(crate) fn trigger_on_remove(
+        &mut self,
+        entity: Entity,
+        targets: impl IntoIterator<Item = ComponentId>,
+    ) {
+        for component_id in targets {
+            let hook = self
+                .world
+                .components
+                .get_info(component_id)
+                .and_then(|info| info.hooks().on_remove);
+            if let Some(hook) = hook {
+                hook(self.reborrow(), entity, component_id);
+            }
+        }
+    }
+}
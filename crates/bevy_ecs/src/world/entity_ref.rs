@@ -4,9 +4,11 @@ use crate::{
     change_detection::MutUntyped,
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
+    observer::{OnRemove, OnReplace},
+    query::{Access, DebugCheckedUnwrap},
     removal_detection::RemovedComponentEvents,
     storage::Storages,
-    world::{Mut, World},
+    world::{DeferredWorld, Mut, World},
 };
 use bevy_ptr::{OwningPtr, Ptr};
 use bevy_utils::tracing::debug;
@@ -173,6 +175,7 @@ impl<'w> EntityRef<'w> {
 
 impl<'w> From<EntityMut<'w>> for EntityRef<'w> {
     fn from(entity_mut: EntityMut<'w>) -> EntityRef<'w> {
+        entity_mut.assert_not_despawned();
         // SAFETY: the safety invariants on EntityMut and EntityRef are identical
         // and EntityMut is promised to be valid by construction.
         unsafe { EntityRef::new(entity_mut.world, entity_mut.entity, entity_mut.location) }
@@ -187,7 +190,19 @@ pub struct EntityMut<'w> {
 }
 
 impl<'w> EntityMut<'w> {
+    #[inline(always)]
+    #[track_caller]
+    fn assert_not_despawned(&self) {
+        if self.is_despawned() {
+            panic!(
+                "Entity {:?} was despawned by a command queued by a hook or observer",
+                self.entity
+            );
+        }
+    }
+
     fn as_unsafe_world_cell_readonly(&self) -> UnsafeEntityCell<'_> {
+        self.assert_not_despawned();
         UnsafeEntityCell::new(
             self.world.as_unsafe_world_cell_readonly(),
             self.entity,
@@ -195,6 +210,7 @@ impl<'w> EntityMut<'w> {
         )
     }
     fn as_unsafe_world_cell(&mut self) -> UnsafeEntityCell<'_> {
+        self.assert_not_despawned();
         UnsafeEntityCell::new(
             self.world.as_unsafe_world_cell(),
             self.entity,
@@ -232,14 +248,30 @@ impl<'w> EntityMut<'w> {
     }
 
     /// Gets metadata indicating the location where the current entity is stored.
+    ///
+    /// # Panics
+    ///
+    /// If the entity was despawned by a command queued by a hook or observer.
     #[inline]
     pub fn location(&self) -> EntityLocation {
+        self.assert_not_despawned();
         self.location
     }
 
+    /// Returns `true` if the entity was despawned by a command queued by a hook or observer,
+    /// while it was being spawned or modified through this `EntityMut`.
+    ///
+    /// The other methods of a despawned `EntityMut` panic, except for [`EntityMut::id`],
+    /// [`EntityMut::despawn`], which does nothing, and the methods giving access to the world.
+    #[inline]
+    pub fn is_despawned(&self) -> bool {
+        self.location.archetype_id == ArchetypeId::INVALID
+    }
+
     /// Returns the archetype that the current entity belongs to.
     #[inline]
     pub fn archetype(&self) -> &Archetype {
+        self.assert_not_despawned();
         &self.world.archetypes[self.location.archetype_id]
     }
 
@@ -325,23 +357,38 @@ impl<'w> EntityMut<'w> {
     ///
     /// This will overwrite any previous value(s) of the same component type.
     pub fn insert<T: Bundle>(&mut self, bundle: T) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
-        let bundle_info = self
+        let old_archetype_id = self.location.archetype_id;
+        let bundle_id = self
             .world
             .bundles
-            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        // Hooks cannot make structural changes, so `self.location` stays valid.
+        self.world
+            .trigger_replace_hooks(self.entity, bundle_id, old_archetype_id);
+        // SAFETY: `bundle_id` was just initialized above
+        let bundle_info = unsafe { self.world.bundles.get(bundle_id).debug_checked_unwrap() };
         let mut bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
             &mut self.world.components,
             &mut self.world.storages,
-            self.location.archetype_id,
+            old_archetype_id,
             change_tick,
         );
         // SAFETY: location matches current entity. `T` matches `bundle_info`
         unsafe {
             self.location = bundle_inserter.insert(self.entity, self.location, bundle);
         }
+        self.world.trigger_insert_hooks(
+            self.entity,
+            bundle_id,
+            Some(old_archetype_id),
+            self.location.archetype_id,
+        );
+        self.flush_commands();
 
         self
     }
@@ -361,18 +408,27 @@ impl<'w> EntityMut<'w> {
         component_id: ComponentId,
         component: OwningPtr<'_>,
     ) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
 
         let bundles = &mut self.world.bundles;
         let components = &mut self.world.components;
 
-        let (bundle_info, storage_type) = bundles.init_component_info(components, component_id);
+        let old_archetype_id = self.location.archetype_id;
+        let bundle_id = bundles.init_component_info(components, component_id).0.id();
+        // Hooks cannot make structural changes, so `self.location` stays valid.
+        self.world
+            .trigger_replace_hooks(self.entity, bundle_id, old_archetype_id);
+        let (bundle_info, storage_type) = self
+            .world
+            .bundles
+            .init_component_info(&mut self.world.components, component_id);
         let bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
             &mut self.world.components,
             &mut self.world.storages,
-            self.location.archetype_id,
+            old_archetype_id,
             change_tick,
         );
 
@@ -383,6 +439,13 @@ impl<'w> EntityMut<'w> {
             Some(component).into_iter(),
             Some(storage_type).into_iter(),
         );
+        self.world.trigger_insert_hooks(
+            self.entity,
+            bundle_id,
+            Some(old_archetype_id),
+            self.location.archetype_id,
+        );
+        self.flush_commands();
 
         self
     }
@@ -404,18 +467,27 @@ impl<'w> EntityMut<'w> {
         component_ids: &[ComponentId],
         iter_components: I,
     ) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
 
         let bundles = &mut self.world.bundles;
         let components = &mut self.world.components;
 
-        let (bundle_info, storage_types) = bundles.init_dynamic_info(components, component_ids);
+        let old_archetype_id = self.location.archetype_id;
+        let bundle_id = bundles.init_dynamic_info(components, component_ids).0.id();
+        // Hooks cannot make structural changes, so `self.location` stays valid.
+        self.world
+            .trigger_replace_hooks(self.entity, bundle_id, old_archetype_id);
+        let (bundle_info, storage_types) = self
+            .world
+            .bundles
+            .init_dynamic_info(&mut self.world.components, component_ids);
         let bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
             &mut self.world.components,
             &mut self.world.storages,
-            self.location.archetype_id,
+            old_archetype_id,
             change_tick,
         );
 
@@ -426,6 +498,13 @@ impl<'w> EntityMut<'w> {
             iter_components,
             storage_types.iter().cloned(),
         );
+        self.world.trigger_insert_hooks(
+            self.entity,
+            bundle_id,
+            Some(old_archetype_id),
+            self.location.archetype_id,
+        );
+        self.flush_commands();
 
         self
     }
@@ -437,21 +516,20 @@ impl<'w> EntityMut<'w> {
     // TODO: BundleRemover?
     #[must_use]
     pub fn take<T: Bundle>(&mut self) -> Option<T> {
-        let archetypes = &mut self.world.archetypes;
-        let storages = &mut self.world.storages;
-        let components = &mut self.world.components;
-        let entities = &mut self.world.entities;
-        let removed_components = &mut self.world.removed_components;
-
-        let bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        self.assert_not_despawned();
         let old_location = self.location;
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
+        let bundle_id = bundle_info.id();
         // SAFETY: `archetype_id` exists because it is referenced in the old `EntityLocation` which is valid,
         // components exist in `bundle_info` because `Bundles::init_info` initializes a `BundleInfo` containing all components of the bundle type `T`
         let new_archetype_id = unsafe {
             remove_bundle_from_archetype(
-                archetypes,
-                storages,
-                components,
+                &mut self.world.archetypes,
+                &mut self.world.storages,
+                &mut self.world.components,
                 old_location.archetype_id,
                 bundle_info,
                 false,
@@ -462,6 +540,18 @@ impl<'w> EntityMut<'w> {
             return None;
         }
 
+        // Hooks cannot make structural changes, so `old_location` stays valid.
+        self.world
+            .trigger_remove_hooks(self.entity, bundle_id, old_location.archetype_id);
+
+        let archetypes = &mut self.world.archetypes;
+        let storages = &mut self.world.storages;
+        let components = &mut self.world.components;
+        let entities = &mut self.world.entities;
+        let removed_components = &mut self.world.removed_components;
+        // SAFETY: `bundle_id` was just initialized above
+        let bundle_info = unsafe { self.world.bundles.get(bundle_id).debug_checked_unwrap() };
+
         let mut bundle_components = bundle_info.components().iter().cloned();
         let entity = self.entity;
         // SAFETY: bundle components are iterated in order, which guarantees that the component type
@@ -497,6 +587,7 @@ impl<'w> EntityMut<'w> {
                 new_archetype_id,
             );
         }
+        self.flush_commands();
 
         Some(result)
     }
@@ -585,22 +676,21 @@ impl<'w> EntityMut<'w> {
     /// Removes any components in the [`Bundle`] from the entity.
     // TODO: BundleRemover?
     pub fn remove<T: Bundle>(&mut self) -> &mut Self {
-        let archetypes = &mut self.world.archetypes;
-        let storages = &mut self.world.storages;
-        let components = &mut self.world.components;
-        let entities = &mut self.world.entities;
-        let removed_components = &mut self.world.removed_components;
-
-        let bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        self.assert_not_despawned();
         let old_location = self.location;
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
+        let bundle_id = bundle_info.id();
 
         // SAFETY: `archetype_id` exists because it is referenced in the old `EntityLocation` which is valid,
         // components exist in `bundle_info` because `Bundles::init_info` initializes a `BundleInfo` containing all components of the bundle type `T`
         let new_archetype_id = unsafe {
             remove_bundle_from_archetype(
-                archetypes,
-                storages,
-                components,
+                &mut self.world.archetypes,
+                &mut self.world.storages,
+                &mut self.world.components,
                 old_location.archetype_id,
                 bundle_info,
                 true,
@@ -612,6 +702,17 @@ impl<'w> EntityMut<'w> {
             return self;
         }
 
+        // Hooks cannot make structural changes, so `old_location` stays valid.
+        self.world
+            .trigger_remove_hooks(self.entity, bundle_id, old_location.archetype_id);
+
+        let archetypes = &mut self.world.archetypes;
+        let storages = &mut self.world.storages;
+        let entities = &mut self.world.entities;
+        let removed_components = &mut self.world.removed_components;
+        // SAFETY: `bundle_id` was just initialized above
+        let bundle_info = unsafe { self.world.bundles.get(bundle_id).debug_checked_unwrap() };
+
         let old_archetype = &mut archetypes[old_location.archetype_id];
         let entity = self.entity;
         for component_id in bundle_info.components().iter().cloned() {
@@ -643,14 +744,34 @@ impl<'w> EntityMut<'w> {
                 new_archetype_id,
             );
         }
+        self.flush_commands();
 
         self
     }
 
     /// Despawns the current entity.
     pub fn despawn(self) {
+        if self.is_despawned() {
+            return;
+        }
         debug!("Despawning entity {:?}", self.entity);
         let world = self.world;
+        let archetype = &world.archetypes[self.location.archetype_id];
+        let has_on_replace = archetype.has_on_replace() || world.observers.has_on_replace();
+        let has_on_remove = archetype.has_on_remove() || world.observers.has_on_remove();
+        if has_on_replace || has_on_remove {
+            let components: Vec<ComponentId> = archetype.components().collect();
+            // Hooks and observers cannot make structural changes, so the entity is still valid afterwards.
+            let mut deferred_world = DeferredWorld::from(&mut *world);
+            if has_on_replace {
+                deferred_world.trigger_on_replace(self.entity, components.iter().copied());
+                deferred_world.trigger_lifecycle_observers::<OnReplace>(self.entity, &components);
+            }
+            if has_on_remove {
+                deferred_world.trigger_on_remove(self.entity, components.iter().copied());
+                deferred_world.trigger_lifecycle_observers::<OnRemove>(self.entity, &components);
+            }
+        }
         world.flush();
         let location = world
             .entities
@@ -781,13 +902,25 @@ impl<'w> EntityMut<'w> {
         f(guard.entity_mut.world)
     }
 
+    /// Applies the commands queued by hooks and observers during a structural change to this
+    /// entity, then updates the cached location in case those commands moved or despawned it.
+    pub(crate) fn flush_commands(&mut self) {
+        self.world.flush_commands();
+        self.update_location();
+    }
+
     /// Updates the internal entity location to match the current location in the internal
     /// [`World`].
     ///
     /// This is *only* required when using the unsafe function [`EntityMut::world_mut`],
-    /// which enables the location to change.
+    /// which enables the location to change. If the entity was despawned, this `EntityMut`
+    /// is marked as [despawned](EntityMut::is_despawned).
     pub fn update_location(&mut self) {
-        self.location = self.world.entities().get(self.entity).unwrap();
+        self.location = self
+            .world
+            .entities()
+            .get(self.entity)
+            .unwrap_or(EntityLocation::INVALID);
     }
 }
 
@@ -825,6 +958,369 @@ impl<'w> EntityMut<'w> {
     }
 }
 
+/// Provides read-only access to a single entity and some of its components defined by the contained [`Access`].
+///
+/// This is the item of [`Query`](crate::system::Query)s whose components are only known at runtime,
+/// built with a [`QueryBuilder`](crate::query::QueryBuilder).
+#[derive(Clone)]
+pub struct FilteredEntityRef<'w> {
+    entity: UnsafeEntityCell<'w>,
+    access: Access<ComponentId>,
+}
+
+impl<'w> FilteredEntityRef<'w> {
+    /// # Safety
+    /// - No `&mut World` can exist from the underlying `UnsafeWorldCell`
+    /// - If `access` takes read access to a component no mutable reference to that
+    /// component can exist at the same time as the returned [`FilteredEntityRef`]
+    /// - If `access` takes any access for a component `entity` must have that component.
+    #[inline]
+    pub(crate) unsafe fn new(entity: UnsafeEntityCell<'w>, access: Access<ComponentId>) -> Self {
+        Self { entity, access }
+    }
+
+    /// Returns the [ID](Entity) of the current entity.
+    #[inline]
+    #[must_use = "Omit the .id() call if you do not need to store the `Entity` identifier."]
+    pub fn id(&self) -> Entity {
+        self.entity.id()
+    }
+
+    /// Gets metadata indicating the location where the current entity is stored.
+    #[inline]
+    pub fn location(&self) -> EntityLocation {
+        self.entity.location()
+    }
+
+    /// Returns the archetype that the current entity belongs to.
+    #[inline]
+    pub fn archetype(&self) -> &Archetype {
+        self.entity.archetype()
+    }
+
+    /// Returns a reference to the underlying [`Access`].
+    #[inline]
+    pub fn access(&self) -> &Access<ComponentId> {
+        &self.access
+    }
+
+    /// Returns `true` if the current entity has a component of type `T`.
+    /// Otherwise, this returns `false`.
+    ///
+    /// ## Notes
+    ///
+    /// If you do not know the concrete type of a component, consider using
+    /// [`Self::contains_id`] or [`Self::contains_type_id`].
+    #[inline]
+    pub fn contains<T: Component>(&self) -> bool {
+        self.contains_type_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the current entity has a component identified by `component_id`.
+    /// Otherwise, this returns false.
+    ///
+    /// ## Notes
+    ///
+    /// - If you know the concrete type of the component, you should prefer [`Self::contains`].
+    /// - If you know the component's [`TypeId`] but not its [`ComponentId`], consider using
+    /// [`Self::contains_type_id`].
+    #[inline]
+    pub fn contains_id(&self, component_id: ComponentId) -> bool {
+        self.entity.contains_id(component_id)
+    }
+
+    /// Returns `true` if the current entity has a component with the type identified by `type_id`.
+    /// Otherwise, this returns false.
+    ///
+    /// ## Notes
+    ///
+    /// - If you know the concrete type of the component, you should prefer [`Self::contains`].
+    /// - If you have a [`ComponentId`] instead of a [`TypeId`], consider using [`Self::contains_id`].
+    #[inline]
+    pub fn contains_type_id(&self, type_id: TypeId) -> bool {
+        self.entity.contains_type_id(type_id)
+    }
+
+    /// Gets access to the component of type `T` for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include it.
+    #[inline]
+    pub fn get<T: Component>(&self) -> Option<&'w T> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_read(id)
+            // SAFETY: We have read access so we must have the component
+            .then(|| unsafe { self.entity.get() })
+            .flatten()
+    }
+
+    /// Gets access to the component of type `T` for the current entity,
+    /// including change detection information as a [`Ref`].
+    ///
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include it.
+    #[inline]
+    pub fn get_ref<T: Component>(&self) -> Option<Ref<'w, T>> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_read(id)
+            // SAFETY: We have read access so we must have the component
+            .then(|| unsafe { self.entity.get_ref() })
+            .flatten()
+    }
+
+    /// Retrieves the change ticks for the given component. This can be useful for implementing change
+    /// detection in custom runtimes.
+    #[inline]
+    pub fn get_change_ticks<T: Component>(&self) -> Option<ComponentTicks> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_read(id)
+            // SAFETY: We have read access so we must have the component
+            .then(|| unsafe { self.entity.get_change_ticks::<T>() })
+            .flatten()
+    }
+
+    /// Retrieves the change ticks for the given [`ComponentId`]. This can be useful for implementing change
+    /// detection in custom runtimes.
+    ///
+    /// **You should prefer to use the typed API [`Self::get_change_ticks`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    #[inline]
+    pub fn get_change_ticks_by_id(&self, component_id: ComponentId) -> Option<ComponentTicks> {
+        self.access
+            .has_read(component_id)
+            // SAFETY: We have read access so we must have the component
+            .then(|| unsafe { self.entity.get_change_ticks_by_id(component_id) })
+            .flatten()
+    }
+
+    /// Gets the component of the given [`ComponentId`] from the entity.
+    ///
+    /// **You should prefer to use the typed API [`Self::get`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// Unlike [`FilteredEntityRef::get`], this returns a raw pointer to the component,
+    /// which is only valid while the `'w` borrow of the lifetime is active.
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<Ptr<'w>> {
+        self.access
+            .has_read(component_id)
+            // SAFETY: We have read access so we must have the component
+            .then(|| unsafe { self.entity.get_by_id(component_id) })
+            .flatten()
+    }
+}
+
+impl<'w> From<FilteredEntityMut<'w>> for FilteredEntityRef<'w> {
+    fn from(entity_mut: FilteredEntityMut<'w>) -> Self {
+        // SAFETY:
+        // - `FilteredEntityMut` guarantees exclusive access to all components in the new `FilteredEntityRef`.
+        unsafe { FilteredEntityRef::new(entity_mut.entity, entity_mut.access) }
+    }
+}
+
+impl<'a> From<&'a FilteredEntityMut<'_>> for FilteredEntityRef<'a> {
+    fn from(entity_mut: &'a FilteredEntityMut<'_>) -> Self {
+        // SAFETY:
+        // - `FilteredEntityMut` guarantees exclusive access to all components in the new `FilteredEntityRef`.
+        unsafe { FilteredEntityRef::new(entity_mut.entity, entity_mut.access.clone()) }
+    }
+}
+
+/// Provides mutable access to a single entity and some of its components defined by the contained [`Access`].
+///
+/// Components can only be accessed mutably if the [`Access`] has write access to them.
+/// This is the mutable item of [`Query`](crate::system::Query)s whose components are only known at runtime,
+/// built with a [`QueryBuilder`](crate::query::QueryBuilder).
+pub struct FilteredEntityMut<'w> {
+    entity: UnsafeEntityCell<'w>,
+    access: Access<ComponentId>,
+}
+
+impl<'w> FilteredEntityMut<'w> {
+    /// # Safety
+    /// - No `&mut World` can exist from the underlying `UnsafeWorldCell`
+    /// - If `access` takes read access to a component no mutable reference to that
+    /// component can exist at the same time as the returned [`FilteredEntityMut`]
+    /// - If `access` takes write access to a component, no reference to that component
+    /// may exist at the same time as the returned [`FilteredEntityMut`]
+    /// - If `access` takes any access for a component `entity` must have that component.
+    #[inline]
+    pub(crate) unsafe fn new(entity: UnsafeEntityCell<'w>, access: Access<ComponentId>) -> Self {
+        Self { entity, access }
+    }
+
+    /// Returns a new instance with a shorter lifetime.
+    /// This is useful if you have `&mut FilteredEntityMut`, but you need `FilteredEntityMut`.
+    pub fn reborrow(&mut self) -> FilteredEntityMut<'_> {
+        // SAFETY: We have exclusive access to the entire entity and its components.
+        unsafe { Self::new(self.entity, self.access.clone()) }
+    }
+
+    /// Gets read-only access to all of the entity's components.
+    pub fn as_readonly(&self) -> FilteredEntityRef<'_> {
+        FilteredEntityRef::from(self)
+    }
+
+    /// Returns the [ID](Entity) of the current entity.
+    #[inline]
+    #[must_use = "Omit the .id() call if you do not need to store the `Entity` identifier."]
+    pub fn id(&self) -> Entity {
+        self.entity.id()
+    }
+
+    /// Gets metadata indicating the location where the current entity is stored.
+    #[inline]
+    pub fn location(&self) -> EntityLocation {
+        self.entity.location()
+    }
+
+    /// Returns the archetype that the current entity belongs to.
+    #[inline]
+    pub fn archetype(&self) -> &Archetype {
+        self.entity.archetype()
+    }
+
+    /// Returns a reference to the underlying [`Access`].
+    #[inline]
+    pub fn access(&self) -> &Access<ComponentId> {
+        &self.access
+    }
+
+    /// Returns `true` if the current entity has a component of type `T`.
+    /// Otherwise, this returns `false`.
+    ///
+    /// ## Notes
+    ///
+    /// If you do not know the concrete type of a component, consider using
+    /// [`Self::contains_id`] or [`Self::contains_type_id`].
+    #[inline]
+    pub fn contains<T: Component>(&self) -> bool {
+        self.contains_type_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the current entity has a component identified by `component_id`.
+    /// Otherwise, this returns false.
+    ///
+    /// ## Notes
+    ///
+    /// - If you know the concrete type of the component, you should prefer [`Self::contains`].
+    /// - If you know the component's [`TypeId`] but not its [`ComponentId`], consider using
+    /// [`Self::contains_type_id`].
+    #[inline]
+    pub fn contains_id(&self, component_id: ComponentId) -> bool {
+        self.entity.contains_id(component_id)
+    }
+
+    /// Returns `true` if the current entity has a component with the type identified by `type_id`.
+    /// Otherwise, this returns false.
+    ///
+    /// ## Notes
+    ///
+    /// - If you know the concrete type of the component, you should prefer [`Self::contains`].
+    /// - If you have a [`ComponentId`] instead of a [`TypeId`], consider using [`Self::contains_id`].
+    #[inline]
+    pub fn contains_type_id(&self, type_id: TypeId) -> bool {
+        self.entity.contains_type_id(type_id)
+    }
+
+    /// Gets access to the component of type `T` for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include it.
+    #[inline]
+    pub fn get<T: Component>(&self) -> Option<&'_ T> {
+        self.as_readonly().get()
+    }
+
+    /// Gets access to the component of type `T` for the current entity,
+    /// including change detection information as a [`Ref`].
+    ///
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include it.
+    #[inline]
+    pub fn get_ref<T: Component>(&self) -> Option<Ref<'_, T>> {
+        self.as_readonly().get_ref()
+    }
+
+    /// Gets mutable access to the component of type `T` for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include write access to it.
+    #[inline]
+    pub fn get_mut<T: Component>(&mut self) -> Option<Mut<'_, T>> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_write(id)
+            // SAFETY: We have write access so we must have the component
+            .then(|| unsafe { self.entity.get_mut() })
+            .flatten()
+    }
+
+    /// Consumes self and gets mutable access to the component of type `T`
+    /// with the world `'w` lifetime for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include write access to it.
+    #[inline]
+    pub fn into_mut<T: Component>(self) -> Option<Mut<'w, T>> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_write(id)
+            // SAFETY: We have write access so we must have the component
+            .then(|| unsafe { self.entity.get_mut() })
+            .flatten()
+    }
+
+    /// Retrieves the change ticks for the given component. This can be useful for implementing change
+    /// detection in custom runtimes.
+    #[inline]
+    pub fn get_change_ticks<T: Component>(&self) -> Option<ComponentTicks> {
+        self.as_readonly().get_change_ticks::<T>()
+    }
+
+    /// Retrieves the change ticks for the given [`ComponentId`]. This can be useful for implementing change
+    /// detection in custom runtimes.
+    ///
+    /// **You should prefer to use the typed API [`Self::get_change_ticks`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    #[inline]
+    pub fn get_change_ticks_by_id(&self, component_id: ComponentId) -> Option<ComponentTicks> {
+        self.as_readonly().get_change_ticks_by_id(component_id)
+    }
+
+    /// Gets the component of the given [`ComponentId`] from the entity.
+    ///
+    /// **You should prefer to use the typed API [`Self::get`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// Unlike [`FilteredEntityMut::get`], this returns a raw pointer to the component,
+    /// which is only valid while the [`FilteredEntityMut`] is alive.
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<Ptr<'_>> {
+        self.as_readonly().get_by_id(component_id)
+    }
+
+    /// Gets a [`MutUntyped`] of the component of the given [`ComponentId`] from the entity.
+    ///
+    /// **You should prefer to use the typed API [`Self::get_mut`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// Unlike [`FilteredEntityMut::get_mut`], this returns a raw pointer to the component,
+    /// which is only valid while the [`FilteredEntityMut`] is alive.
+    #[inline]
+    pub fn get_mut_by_id(&mut self, component_id: ComponentId) -> Option<MutUntyped<'_>> {
+        self.access
+            .has_write(component_id)
+            // SAFETY: We have write access so we must have the component
+            .then(|| unsafe { self.entity.get_mut_by_id(component_id) })
+            .flatten()
+    }
+}
+
 /// Inserts a dynamic [`Bundle`] into the entity.
 ///
 /// # Safety
@@ -943,6 +1439,7 @@ unsafe fn remove_bundle_from_archetype(
         }
 
         let new_archetype_id = archetypes.get_id_or_insert(
+            components,
             next_table_id,
             next_table_components,
             next_sparse_set_components,
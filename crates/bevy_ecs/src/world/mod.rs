@@ -1,28 +1,45 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+mod async_world;
+mod deferred_world;
 mod entity_ref;
 pub mod error;
+mod snapshot;
 mod spawn_batch;
+mod storage_report;
 pub mod unsafe_world_cell;
 mod world_cell;
 
 pub use crate::change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD};
-pub use entity_ref::{EntityMut, EntityRef};
+pub use async_world::{apply_async_tasks, AsyncTaskId, AsyncTasks, AsyncWorld};
+pub use deferred_world::DeferredWorld;
+pub use entity_ref::{EntityMut, EntityRef, FilteredEntityMut, FilteredEntityRef};
+pub use snapshot::{SnapshotConfig, WorldSnapshot};
 pub use spawn_batch::*;
+pub use storage_report::{
+    ArchetypeReport, ColumnReport, ResourceReport, SparseSetReport, StorageReport, TableReport,
+};
 pub use world_cell::*;
 
 use crate::{
-    archetype::{ArchetypeComponentId, ArchetypeId, ArchetypeRow, Archetypes},
-    bundle::{Bundle, BundleInserter, BundleSpawner, Bundles},
-    change_detection::{MutUntyped, TicksMut},
-    component::{Component, ComponentDescriptor, ComponentId, ComponentInfo, Components, Tick},
-    entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
+    archetype::{ArchetypeComponentId, ArchetypeId, ArchetypeRow, Archetypes, ComponentStatus},
+    bundle::{Bundle, BundleId, BundleInserter, BundleSpawner, Bundles},
+    change_detection::{DetectChangesMut, MutUntyped, TicksMut},
+    component::{
+        Component, ComponentCloneHandler, ComponentDescriptor, ComponentHooks, ComponentId,
+        ComponentInfo, Components, Tick, TrackPrevious,
+    },
+    entity::{
+        AllocAtWithoutReplacement, Entities, Entity, EntityAllocationMode, EntityCloneBuilder,
+        EntityLocation,
+    },
     event::{Event, Events},
+    observer::{Observers, OnAdd, OnInsert, OnRemove, OnReplace},
     query::{DebugCheckedUnwrap, QueryState, ReadOnlyWorldQuery, WorldQuery},
     removal_detection::RemovedComponentEvents,
     schedule::{Schedule, ScheduleLabel, Schedules},
-    storage::{ResourceData, Storages},
-    system::Resource,
+    storage::{PreviousValues, ResourceData, Storages},
+    system::{CommandQueue, Resource},
     world::error::TryRunScheduleError,
 };
 use bevy_ptr::{OwningPtr, Ptr};
@@ -30,6 +47,7 @@ use bevy_utils::tracing::warn;
 use std::{
     any::TypeId,
     fmt,
+    ops::Range,
     sync::atomic::{AtomicU32, Ordering},
 };
 mod identifier;
@@ -70,6 +88,9 @@ pub struct World {
     pub(crate) change_tick: AtomicU32,
     pub(crate) last_change_tick: Tick,
     pub(crate) last_check_tick: Tick,
+    /// Commands queued through a [`DeferredWorld`], applied by [`World::flush_commands`].
+    pub(crate) command_queue: CommandQueue,
+    pub(crate) observers: Observers,
 }
 
 impl Default for World {
@@ -88,6 +109,8 @@ impl Default for World {
             change_tick: AtomicU32::new(1),
             last_change_tick: Tick::new(0),
             last_check_tick: Tick::new(0),
+            command_queue: CommandQueue::default(),
+            observers: Observers::default(),
         }
     }
 }
@@ -138,6 +161,44 @@ impl World {
         &mut self.entities
     }
 
+    /// Sets how this world reuses the indices of despawned entities.
+    ///
+    /// Use [`EntityAllocationMode::Deterministic`] when several worlds must agree on entity ids,
+    /// for example when running a lockstep simulation.
+    pub fn set_entity_allocation_mode(&mut self, mode: EntityAllocationMode) {
+        self.flush();
+        self.entities.set_allocation_mode(mode);
+    }
+
+    /// Reserves a contiguous block of `count` entity indices that this world will never allocate
+    /// on its own. See [`Entities::reserve_range`].
+    pub fn reserve_entity_range(&mut self, count: u32) -> Range<u32> {
+        self.flush();
+        self.entities.reserve_range(count)
+    }
+
+    /// Reports how this world's archetypes, tables, sparse sets and resources use memory.
+    ///
+    /// See [`StorageReport`] for details.
+    pub fn storage_report(&self) -> StorageReport {
+        StorageReport::new(self)
+    }
+
+    /// Releases unused memory held by this world's tables, sparse sets, archetypes and removed
+    /// resources.
+    ///
+    /// Storages grow as entities are spawned but never shrink on their own, so a world that once
+    /// held many entities keeps that memory around. This reallocates every storage, so it is best
+    /// called rarely, for example after unloading a level.
+    pub fn shrink_to_fit(&mut self) {
+        self.flush();
+        self.archetypes.shrink_to_fit();
+        self.storages.tables.shrink_to_fit();
+        self.storages.sparse_sets.shrink_to_fit();
+        self.storages.resources.shrink_to_fit();
+        self.storages.non_send_resources.shrink_to_fit();
+    }
+
     /// Retrieves this world's [Archetypes] collection
     #[inline]
     pub fn archetypes(&self) -> &Archetypes {
@@ -197,6 +258,69 @@ impl World {
             .init_component_with_descriptor(&mut self.storages, descriptor)
     }
 
+    /// Starts tracking the previous values of the [`Component`] type `T`, initializing it if
+    /// needed, and returns its [`ComponentId`].
+    ///
+    /// This is equivalent to deriving the component with `#[component(track_previous)]`, for
+    /// component types defined in other crates. See [`TrackPrevious`] for details.
+    pub fn track_previous<T: Component + Clone>(&mut self) -> ComponentId {
+        let id = self.init_component::<T>();
+        self.storages
+            .previous_values
+            .get_or_insert_with(id, || PreviousValues::new(TrackPrevious::new::<T>()));
+        id
+    }
+
+    /// Returns a mutable reference to the [`ComponentHooks`] for a [`Component`] type.
+    ///
+    /// Will panic if `T` exists in any archetypes.
+    pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
+        let index = self.init_component::<T>();
+        assert!(
+            !self.archetypes.iter().any(|a| a.contains(index)),
+            "Components hooks cannot be modified if the component already exists in an archetype, use init_component if {} may already be in use",
+            std::any::type_name::<T>()
+        );
+        // SAFETY: We just created this component
+        unsafe { self.components.get_hooks_mut(index).debug_checked_unwrap() }
+    }
+
+    /// Returns a mutable reference to the [`ComponentHooks`] for a [`Component`] with the given id if it exists.
+    ///
+    /// Will panic if `id` exists in any archetypes.
+    pub fn register_component_hooks_by_id(
+        &mut self,
+        id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        assert!(
+            !self.archetypes.iter().any(|a| a.contains(id)),
+            "Components hooks cannot be modified if the component already exists in an archetype, use init_component if the component with id {:?} may already be in use",
+            id
+        );
+        self.components.get_hooks_mut(id)
+    }
+
+    /// Sets how the [`Component`] type `T` is copied when an entity is cloned with [`World::clone_entity`],
+    /// overriding the handler returned by [`Component::clone_handler`].
+    ///
+    /// [`ComponentCloneHandler::from_clone`] clones `T` without going through reflection.
+    pub fn set_component_clone_handler<T: Component>(&mut self, handler: ComponentCloneHandler) {
+        let id = self.init_component::<T>();
+        self.components.set_clone_handler(id, handler);
+    }
+
+    /// Spawns a copy of the `source` entity with all of its components that can be cloned,
+    /// and returns the new [`Entity`].
+    ///
+    /// See [`EntityCloneBuilder`] to clone related entities recursively or skip some components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` does not exist.
+    pub fn clone_entity(&mut self, source: Entity) -> Entity {
+        EntityCloneBuilder::default().clone_entity(self, source)
+    }
+
     /// Returns the [`ComponentId`] of the given [`Component`] type `T`.
     ///
     /// The returned `ComponentId` is specific to the `World` instance
@@ -512,7 +636,7 @@ impl World {
         self.flush();
         let change_tick = self.change_tick();
         let entity = self.entities.alloc();
-        let entity_location = {
+        let (bundle_id, entity_location) = {
             let bundle_info = self
                 .bundles
                 .init_info::<B>(&mut self.components, &mut self.storages);
@@ -525,11 +649,16 @@ impl World {
             );
 
             // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent
-            unsafe { spawner.spawn_non_existent(entity, bundle) }
+            let location = unsafe { spawner.spawn_non_existent(entity, bundle) };
+            (bundle_info.id(), location)
         };
-
+        // Hooks cannot make structural changes, so `entity_location` stays valid.
+        self.trigger_insert_hooks(entity, bundle_id, None, entity_location.archetype_id);
         // SAFETY: entity and location are valid, as they were just created above
-        unsafe { EntityMut::new(self, entity, entity_location) }
+        let mut entity = unsafe { EntityMut::new(self, entity, entity_location) };
+        // Commands queued by hooks and observers may move or despawn the new entity.
+        entity.flush_commands();
+        entity
     }
 
     /// # Safety
@@ -644,6 +773,7 @@ impl World {
     pub fn despawn(&mut self, entity: Entity) -> bool {
         if let Some(entity) = self.get_entity_mut(entity) {
             entity.despawn();
+            self.flush_commands();
             true
         } else {
             warn!("error[B0003]: Could not despawn entity {:?} because it doesn't exist in this World.", entity);
@@ -660,6 +790,9 @@ impl World {
     /// The state is also used for change detection when accessing components and resources outside
     /// of a system, for example via [`World::get_mut()`] or [`World::get_resource_mut()`].
     ///
+    /// This also discards the previous values captured for components with
+    /// [`TrackPrevious`](crate::component::TrackPrevious) enabled.
+    ///
     /// By clearing this internal state, the world "forgets" about those changes, allowing a new round
     /// of detection to be recorded.
     ///
@@ -692,9 +825,60 @@ impl World {
     /// [`RemovedComponents`]: crate::removal_detection::RemovedComponents
     pub fn clear_trackers(&mut self) {
         self.removed_components.update();
+        for previous in self.storages.previous_values.values_mut() {
+            previous.clear();
+        }
         self.last_change_tick = self.increment_change_tick();
     }
 
+    /// Resets the change ticks of the `T` components that were mutated in the current frame but
+    /// are equal to their previous value, so they are no longer detected as changed.
+    ///
+    /// Only has an effect if `T` tracks its previous values, see [`TrackPrevious`].
+    /// This is usually called after the systems writing `T` and before the systems reacting to
+    /// its changes.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Clone, PartialEq)]
+    /// #[component(track_previous)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn(Health(10)).id();
+    /// world.clear_trackers();
+    ///
+    /// // written, but with the same value
+    /// world.get_mut::<Health>(entity).unwrap().0 = 10;
+    /// world.suppress_unchanged_writes::<Health>();
+    ///
+    /// let health = world.get_mut::<Health>(entity).unwrap();
+    /// assert!(!health.is_changed());
+    /// ```
+    pub fn suppress_unchanged_writes<T: Component + PartialEq>(&mut self) {
+        let Some(component_id) = self.components.component_id::<T>() else {
+            return;
+        };
+        let Some(values) = self.storages.previous_values.get_mut(component_id) else {
+            return;
+        };
+        // Take the values out, so the components can be accessed while comparing them.
+        let mut values = values.take();
+        values.for_each(|entity, previous, changed| {
+            let Some(previous) = previous.downcast_ref::<T>() else {
+                return;
+            };
+            if let Some(mut current) = self.get_mut::<T>(entity) {
+                if current.bypass_change_detection() == previous {
+                    current.set_last_changed(changed);
+                }
+            }
+        });
+        if let Some(slot) = self.storages.previous_values.get_mut(component_id) {
+            *slot = values;
+        }
+    }
+
     /// Returns [`QueryState`] for the given [`WorldQuery`], which is used to efficiently
     /// run queries on the [`World`] by storing and reusing the [`QueryState`].
     /// ```
@@ -1208,6 +1392,32 @@ impl World {
 
         let change_tick = self.change_tick();
 
+        // Hooks and observers need access to the whole world around each insertion, so fall back
+        // to inserting the bundles one entity at a time.
+        let has_hooks = self
+            .bundles
+            .init_info::<B>(&mut self.components, &mut self.storages)
+            .has_hooks(&self.components)
+            || self.observers.has_on_add()
+            || self.observers.has_on_insert()
+            || self.observers.has_on_replace();
+        if has_hooks {
+            let mut invalid_entities = Vec::new();
+            for (entity, bundle) in iter {
+                match self.get_or_spawn(entity) {
+                    Some(mut entity_mut) => {
+                        entity_mut.insert(bundle);
+                    }
+                    None => invalid_entities.push(entity),
+                }
+            }
+            return if invalid_entities.is_empty() {
+                Ok(())
+            } else {
+                Err(invalid_entities)
+            };
+        }
+
         let bundle_info = self
             .bundles
             .init_info::<B>(&mut self.components, &mut self.storages);
@@ -1339,6 +1549,7 @@ impl World {
                 changed: &mut ticks.changed,
                 last_run: last_change_tick,
                 this_run: change_tick,
+                previous: None,
             },
         };
         let result = f(self, value_mut);
@@ -1481,6 +1692,136 @@ impl World {
         component_id
     }
 
+    /// Applies any commands queued by [`ComponentHooks`] and observers through a [`DeferredWorld`].
+    ///
+    /// This is called automatically whenever a [`CommandQueue`] is applied to the world and after
+    /// entities are spawned, despawned, or have components inserted or removed through [`EntityMut`],
+    /// so it rarely needs to be called manually.
+    pub fn flush_commands(&mut self) {
+        while !self.command_queue.is_empty() {
+            let mut commands = std::mem::take(&mut self.command_queue);
+            commands.apply(self);
+        }
+    }
+
+    /// Runs the `on_add` and `on_insert` hooks, then the [`OnAdd`] and [`OnInsert`] observers,
+    /// for the components of the bundle with the given [`BundleId`] that was just inserted on `entity`.
+    ///
+    /// `old_archetype_id` is the archetype the entity was in before the insertion, or `None` if the
+    /// entity was just spawned.
+    pub(crate) fn trigger_insert_hooks(
+        &mut self,
+        entity: Entity,
+        bundle_id: BundleId,
+        old_archetype_id: Option<ArchetypeId>,
+        new_archetype_id: ArchetypeId,
+    ) {
+        let new_archetype = &self.archetypes[new_archetype_id];
+        let (has_on_add, has_on_insert) = (
+            new_archetype.has_on_add() || self.observers.has_on_add(),
+            new_archetype.has_on_insert() || self.observers.has_on_insert(),
+        );
+        if !has_on_add && !has_on_insert {
+            return;
+        }
+        // SAFETY: `bundle_id` was obtained from this world's bundles
+        let bundle_components = unsafe { self.bundles.get(bundle_id).debug_checked_unwrap() }
+            .components()
+            .to_vec();
+        let added = match old_archetype_id {
+            None => bundle_components.clone(),
+            Some(old_archetype_id) => {
+                // SAFETY: the add bundle edge is created when the bundle is inserted
+                let add_bundle = unsafe {
+                    self.archetypes[old_archetype_id]
+                        .edges()
+                        .get_add_bundle_internal(bundle_id)
+                        .debug_checked_unwrap()
+                };
+                bundle_components
+                    .iter()
+                    .zip(&add_bundle.bundle_status)
+                    .filter(|(_, status)| matches!(status, ComponentStatus::Added))
+                    .map(|(id, _)| *id)
+                    .collect()
+            }
+        };
+        let mut world = DeferredWorld::from(self);
+        if has_on_add {
+            world.trigger_on_add(entity, added.iter().copied());
+            world.trigger_lifecycle_observers::<OnAdd>(entity, &added);
+        }
+        if has_on_insert {
+            world.trigger_on_insert(entity, bundle_components.iter().copied());
+            world.trigger_lifecycle_observers::<OnInsert>(entity, &bundle_components);
+        }
+    }
+
+    /// Runs the `on_replace` hooks, then the [`OnReplace`] observers, for every component of the
+    /// bundle with the given [`BundleId`] that `entity`, located in `archetype_id`, already has.
+    ///
+    /// Must be called before the bundle is inserted, while the old values can still be read.
+    pub(crate) fn trigger_replace_hooks(
+        &mut self,
+        entity: Entity,
+        bundle_id: BundleId,
+        archetype_id: ArchetypeId,
+    ) {
+        if !self.archetypes[archetype_id].has_on_replace() && !self.observers.has_on_replace() {
+            return;
+        }
+        let targets = self.bundle_components_in_archetype(bundle_id, archetype_id);
+        let mut world = DeferredWorld::from(self);
+        world.trigger_on_replace(entity, targets.iter().copied());
+        world.trigger_lifecycle_observers::<OnReplace>(entity, &targets);
+    }
+
+    /// Runs the `on_replace` and `on_remove` hooks, then the [`OnReplace`] and [`OnRemove`]
+    /// observers, for every component of the bundle with the given [`BundleId`] that `entity`,
+    /// located in `archetype_id`, currently has.
+    ///
+    /// Must be called before the components are removed.
+    pub(crate) fn trigger_remove_hooks(
+        &mut self,
+        entity: Entity,
+        bundle_id: BundleId,
+        archetype_id: ArchetypeId,
+    ) {
+        let archetype = &self.archetypes[archetype_id];
+        let has_on_replace = archetype.has_on_replace() || self.observers.has_on_replace();
+        let has_on_remove = archetype.has_on_remove() || self.observers.has_on_remove();
+        if !has_on_replace && !has_on_remove {
+            return;
+        }
+        let targets = self.bundle_components_in_archetype(bundle_id, archetype_id);
+        let mut world = DeferredWorld::from(self);
+        if has_on_replace {
+            world.trigger_on_replace(entity, targets.iter().copied());
+            world.trigger_lifecycle_observers::<OnReplace>(entity, &targets);
+        }
+        if has_on_remove {
+            world.trigger_on_remove(entity, targets.iter().copied());
+            world.trigger_lifecycle_observers::<OnRemove>(entity, &targets);
+        }
+    }
+
+    /// Returns the components of the bundle with the given [`BundleId`] that are part of the
+    /// archetype with the given [`ArchetypeId`].
+    fn bundle_components_in_archetype(
+        &self,
+        bundle_id: BundleId,
+        archetype_id: ArchetypeId,
+    ) -> Vec<ComponentId> {
+        let archetype = &self.archetypes[archetype_id];
+        // SAFETY: `bundle_id` was obtained from this world's bundles
+        unsafe { self.bundles.get(bundle_id).debug_checked_unwrap() }
+            .components()
+            .iter()
+            .copied()
+            .filter(|&id| archetype.contains(id))
+            .collect()
+    }
+
     /// Empties queued entities and adds them to the empty [Archetype](crate::archetype::Archetype).
     /// This should be called before doing operations that might operate on queued entities,
     /// such as inserting a [Component].
@@ -1553,6 +1894,7 @@ impl World {
             ref mut sparse_sets,
             ref mut resources,
             ref mut non_send_resources,
+            ref mut previous_values,
         } = self.storages;
 
         #[cfg(feature = "trace")]
@@ -1561,6 +1903,9 @@ impl World {
         sparse_sets.check_change_ticks(change_tick);
         resources.check_change_ticks(change_tick);
         non_send_resources.check_change_ticks(change_tick);
+        for previous in previous_values.values_mut() {
+            previous.check_change_ticks(change_tick);
+        }
 
         if let Some(mut schedules) = self.get_resource_mut::<crate::schedule::Schedules>() {
             schedules.check_change_ticks(change_tick);
@@ -1904,6 +2249,7 @@ mod tests {
     use crate::{
         change_detection::DetectChangesMut,
         component::{ComponentDescriptor, ComponentInfo, StorageType},
+        entity::{Entity, EntityAllocationMode},
         ptr::OwningPtr,
         system::Resource,
     };
@@ -2291,4 +2637,24 @@ mod tests {
         let mut world = World::new();
         world.spawn(());
     }
+
+    #[test]
+    fn spawn_in_reserved_entity_range() {
+        let mut world = World::new();
+        world.set_entity_allocation_mode(EntityAllocationMode::Deterministic);
+        let first = world.spawn_empty().id();
+        let range = world.reserve_entity_range(2);
+
+        let reserved = Entity::from_raw(range.start);
+        assert!(world.get_entity(reserved).is_none());
+        assert_eq!(world.spawn_empty().id().index(), range.end);
+
+        assert!(world.get_or_spawn(reserved).is_some());
+        assert!(world.get_entity(reserved).is_some());
+        assert_eq!(world.entities().len(), 4);
+
+        world.despawn(first);
+        world.despawn(reserved);
+        assert_eq!(world.spawn_empty().id().index(), first.index());
+    }
 }
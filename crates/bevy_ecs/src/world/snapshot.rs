@@ -0,0 +1,538 @@
+use std::{any::Any, marker::PhantomData, sync::Arc};
+
+use bevy_ptr::UnsafeCellDeref;
+use bevy_utils::HashSet;
+
+use crate::{
+    component::{Component, ComponentStorage, StorageType},
+    entity::{EntitiesAllocatorState, Entity},
+    query::With,
+    system::Resource,
+    world::World,
+};
+
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::{ReflectComponent, ReflectResource};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{Reflect, TypeRegistration};
+#[cfg(feature = "bevy_reflect")]
+use std::any::TypeId;
+
+type SnapshotData = Box<dyn Any + Send + Sync>;
+
+/// Captures and restores one kind of data stored in a [`World`].
+trait SnapshotEntry: Send + Sync + 'static {
+    fn capture(&self, world: &World) -> SnapshotData;
+    fn restore(&self, data: &SnapshotData, world: &mut World);
+}
+
+/// Selects the components and resources that a [`WorldSnapshot`] captures.
+///
+/// Entities and the state of the entity allocator are always captured, so that restoring a
+/// snapshot brings back the exact same [`Entity`] ids and later allocations are reproducible.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::{SnapshotConfig, WorldSnapshot};
+/// #[derive(Component, Clone)]
+/// struct Position(f32);
+///
+/// let mut config = SnapshotConfig::new();
+/// config.component::<Position>();
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Position(0.0)).id();
+/// let snapshot = WorldSnapshot::capture(&mut world, &config);
+///
+/// world.get_mut::<Position>(entity).unwrap().0 = 1.0;
+/// world.despawn(entity);
+///
+/// snapshot.restore(&mut world);
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 0.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct SnapshotConfig {
+    entries: Vec<Arc<dyn SnapshotEntry>>,
+}
+
+impl SnapshotConfig {
+    /// Creates a config that only captures entities.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the component `C` by cloning it straight out of its table columns or sparse set.
+    pub fn component<C: Component + Clone>(&mut self) -> &mut Self {
+        self.entries
+            .push(Arc::new(CloneComponent::<C>(PhantomData)));
+        self
+    }
+
+    /// Captures the resource `R` by cloning it.
+    pub fn resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        self.entries.push(Arc::new(CloneResource::<R>(PhantomData)));
+        self
+    }
+
+    /// Captures the component described by `registration` through reflection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type was not registered with [`ReflectComponent`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn reflect_component(&mut self, registration: &TypeRegistration) -> &mut Self {
+        let reflect_component = registration
+            .data::<ReflectComponent>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "{} is not registered with ReflectComponent",
+                    registration.type_name()
+                )
+            })
+            .clone();
+        self.entries.push(Arc::new(ReflectedComponent {
+            type_id: registration.type_id(),
+            reflect_component,
+        }));
+        self
+    }
+
+    /// Captures the resource described by `registration` through reflection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type was not registered with [`ReflectResource`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn reflect_resource(&mut self, registration: &TypeRegistration) -> &mut Self {
+        let reflect_resource = registration
+            .data::<ReflectResource>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "{} is not registered with ReflectResource",
+                    registration.type_name()
+                )
+            })
+            .clone();
+        self.entries
+            .push(Arc::new(ReflectedResource { reflect_resource }));
+        self
+    }
+}
+
+/// A copy of the entities, entity allocator and selected components and resources of a [`World`],
+/// which can be restored in place to roll the world back.
+///
+/// Unlike a `DynamicScene`, a snapshot keeps entity ids intact: restoring despawns entities
+/// spawned since the capture, respawns despawned ones under their original ids and resets the
+/// allocator, so entities spawned after a restore get the same ids they got after the capture.
+///
+/// Only the data selected by the [`SnapshotConfig`] is restored. Components and resources that
+/// were not captured are left untouched on entities that survive, and are missing from entities
+/// that had to be respawned.
+///
+/// # Change detection
+///
+/// Restoring never moves change ticks backwards. Every restored component or resource value is
+/// written at the world's current change tick, so it reports as changed to every system, and
+/// values that had to be inserted also report as added. Values removed by the restore show up in
+/// [`RemovedComponents`](crate::removal_detection::RemovedComponents).
+///
+/// Component hooks and observers run as usual while restoring. Hooks that spawn entities make
+/// later allocations diverge from the captured world.
+pub struct WorldSnapshot {
+    entities: Vec<Entity>,
+    allocator: EntitiesAllocatorState,
+    data: Vec<(Arc<dyn SnapshotEntry>, SnapshotData)>,
+}
+
+impl WorldSnapshot {
+    /// Captures the current state of `world`, as selected by `config`.
+    pub fn capture(world: &mut World, config: &SnapshotConfig) -> Self {
+        world.flush();
+        let entities = alive_entities(world);
+        let allocator = world.entities.allocator_state();
+        let data = config
+            .entries
+            .iter()
+            .map(|entry| (entry.clone(), entry.capture(world)))
+            .collect();
+        Self {
+            entities,
+            allocator,
+            data,
+        }
+    }
+
+    /// The entities that were alive when this snapshot was captured.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Rolls `world` back to the state captured by this snapshot.
+    ///
+    /// The snapshot is left intact, so it can be restored any number of times.
+    pub fn restore(&self, world: &mut World) {
+        world.flush();
+        let captured: HashSet<Entity> = self.entities.iter().copied().collect();
+        for entity in alive_entities(world) {
+            if !captured.contains(&entity) {
+                world.despawn(entity);
+            }
+        }
+        for &entity in &self.entities {
+            if !world.entities().contains(entity) {
+                world
+                    .get_or_spawn(entity)
+                    .expect("entities missing from the snapshot were despawned above");
+            }
+        }
+        world.flush();
+        world.entities.restore_allocator_state(&self.allocator);
+
+        for (entry, data) in &self.data {
+            entry.restore(data, world);
+        }
+    }
+}
+
+fn alive_entities(world: &World) -> Vec<Entity> {
+    world
+        .archetypes()
+        .iter()
+        .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.entity()))
+        .collect()
+}
+
+/// Removes `C` from every entity that is not in `keep`.
+fn remove_components_except<C: Component>(world: &mut World, keep: &HashSet<Entity>) {
+    let stale: Vec<Entity> = world
+        .query_filtered::<Entity, With<C>>()
+        .iter(world)
+        .filter(|entity| !keep.contains(entity))
+        .collect();
+    for entity in stale {
+        world.entity_mut(entity).remove::<C>();
+    }
+}
+
+struct CloneComponent<C>(PhantomData<fn() -> C>);
+
+impl<C: Component + Clone> SnapshotEntry for CloneComponent<C> {
+    fn capture(&self, world: &World) -> SnapshotData {
+        let mut values: Vec<(Entity, C)> = Vec::new();
+        let Some(id) = world.component_id::<C>() else {
+            return Box::new(values);
+        };
+        match C::Storage::STORAGE_TYPE {
+            StorageType::Table => {
+                for table in world.storages().tables.iter() {
+                    let Some(column) = table.get_column(id) else {
+                        continue;
+                    };
+                    // SAFETY: the column stores values of `C`, and no mutable access to them can
+                    // exist while `world` is borrowed immutably.
+                    let data = unsafe { column.get_data_slice::<C>() };
+                    for (&entity, value) in table.entities().iter().zip(data) {
+                        // SAFETY: see above.
+                        values.push((entity, unsafe { value.deref() }.clone()));
+                    }
+                }
+            }
+            StorageType::SparseSet => {
+                let Some(sparse_set) = world.storages().sparse_sets.get(id) else {
+                    return Box::new(values);
+                };
+                for archetype in world.archetypes().iter() {
+                    if !archetype.contains(id) {
+                        continue;
+                    }
+                    for entity in archetype.entities() {
+                        let value = sparse_set.get(entity.entity()).unwrap();
+                        // SAFETY: the sparse set stores values of `C`.
+                        values.push((entity.entity(), unsafe { value.deref::<C>() }.clone()));
+                    }
+                }
+            }
+        }
+        Box::new(values)
+    }
+
+    fn restore(&self, data: &SnapshotData, world: &mut World) {
+        let values = data.downcast_ref::<Vec<(Entity, C)>>().unwrap();
+        let keep = values.iter().map(|(entity, _)| *entity).collect();
+        remove_components_except::<C>(world, &keep);
+        for (entity, value) in values {
+            let mut entity = world.entity_mut(*entity);
+            if let Some(mut component) = entity.get_mut::<C>() {
+                *component = value.clone();
+            } else {
+                entity.insert(value.clone());
+            }
+        }
+    }
+}
+
+struct CloneResource<R>(PhantomData<fn() -> R>);
+
+impl<R: Resource + Clone> SnapshotEntry for CloneResource<R> {
+    fn capture(&self, world: &World) -> SnapshotData {
+        Box::new(world.get_resource::<R>().cloned())
+    }
+
+    fn restore(&self, data: &SnapshotData, world: &mut World) {
+        match data.downcast_ref::<Option<R>>().unwrap() {
+            Some(value) => match world.get_resource_mut::<R>() {
+                Some(mut resource) => *resource = value.clone(),
+                None => world.insert_resource(value.clone()),
+            },
+            None => {
+                world.remove_resource::<R>();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectedComponent {
+    type_id: TypeId,
+    reflect_component: ReflectComponent,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl SnapshotEntry for ReflectedComponent {
+    fn capture(&self, world: &World) -> SnapshotData {
+        let mut values: Vec<(Entity, Box<dyn Reflect>)> = Vec::new();
+        if let Some(id) = world.components().get_id(self.type_id) {
+            for archetype in world.archetypes().iter() {
+                if !archetype.contains(id) {
+                    continue;
+                }
+                for entity in archetype.entities() {
+                    let value = self
+                        .reflect_component
+                        .reflect(world.entity(entity.entity()))
+                        .unwrap();
+                    values.push((entity.entity(), value.clone_value()));
+                }
+            }
+        }
+        Box::new(values)
+    }
+
+    fn restore(&self, data: &SnapshotData, world: &mut World) {
+        let values = data
+            .downcast_ref::<Vec<(Entity, Box<dyn Reflect>)>>()
+            .unwrap();
+        let keep: HashSet<Entity> = values.iter().map(|(entity, _)| *entity).collect();
+        if let Some(id) = world.components().get_id(self.type_id) {
+            let stale: Vec<Entity> = world
+                .archetypes()
+                .iter()
+                .filter(|archetype| archetype.contains(id))
+                .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.entity()))
+                .filter(|entity| !keep.contains(entity))
+                .collect();
+            for entity in stale {
+                self.reflect_component.remove(&mut world.entity_mut(entity));
+            }
+        }
+        for (entity, value) in values {
+            self.reflect_component
+                .apply_or_insert(&mut world.entity_mut(*entity), &**value);
+        }
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectedResource {
+    reflect_resource: ReflectResource,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl SnapshotEntry for ReflectedResource {
+    fn capture(&self, world: &World) -> SnapshotData {
+        Box::new(
+            self.reflect_resource
+                .reflect(world)
+                .map(|value| value.clone_value()),
+        )
+    }
+
+    fn restore(&self, data: &SnapshotData, world: &mut World) {
+        match data.downcast_ref::<Option<Box<dyn Reflect>>>().unwrap() {
+            Some(value) => self.reflect_resource.apply_or_insert(world, &**value),
+            None => self.reflect_resource.remove(world),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{
+        component::Component,
+        entity::Entity,
+        query::{Added, Changed},
+        system::Resource,
+        world::World,
+    };
+
+    use super::{SnapshotConfig, WorldSnapshot};
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct Marker(&'static str);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct NotCaptured;
+
+    #[derive(Resource, Clone, Debug, PartialEq)]
+    struct Frame(u32);
+
+    fn config() -> SnapshotConfig {
+        let mut config = SnapshotConfig::new();
+        config
+            .component::<Position>()
+            .component::<Marker>()
+            .resource::<Frame>();
+        config
+    }
+
+    #[test]
+    fn restores_component_values() {
+        let mut world = World::new();
+        let a = world.spawn((Position(1), Marker("a"))).id();
+        let b = world.spawn(Position(2)).id();
+        let snapshot = WorldSnapshot::capture(&mut world, &config());
+
+        world.get_mut::<Position>(a).unwrap().0 = 10;
+        world.entity_mut(a).remove::<Marker>();
+        world.entity_mut(b).insert(Marker("b"));
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+        assert_eq!(world.get::<Marker>(a), Some(&Marker("a")));
+        assert_eq!(world.get::<Position>(b), Some(&Position(2)));
+        assert_eq!(world.get::<Marker>(b), None);
+    }
+
+    #[test]
+    fn restores_entity_ids() {
+        let mut world = World::new();
+        let kept = world.spawn(Position(1)).id();
+        let despawned = world.spawn((Position(2), NotCaptured)).id();
+        let snapshot = WorldSnapshot::capture(&mut world, &config());
+
+        world.despawn(despawned);
+        let spawned = world.spawn(Position(3)).id();
+        snapshot.restore(&mut world);
+
+        assert!(world.get_entity(spawned).is_none());
+        assert_eq!(world.get::<Position>(kept), Some(&Position(1)));
+        assert_eq!(world.get::<Position>(despawned), Some(&Position(2)));
+        // Components that were not captured are not brought back.
+        assert!(world.get::<NotCaptured>(despawned).is_none());
+        assert_eq!(world.entities().len(), 2);
+    }
+
+    #[test]
+    fn allocation_is_deterministic_after_restore() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        world.spawn_empty();
+        world.despawn(a);
+        let snapshot = WorldSnapshot::capture(&mut world, &SnapshotConfig::new());
+
+        let first: Vec<Entity> = (0..3).map(|_| world.spawn_empty().id()).collect();
+        world.despawn(first[1]);
+        snapshot.restore(&mut world);
+        let second: Vec<Entity> = (0..3).map(|_| world.spawn_empty().id()).collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn restores_resources() {
+        let mut world = World::new();
+        world.insert_resource(Frame(1));
+        let snapshot = WorldSnapshot::capture(&mut world, &config());
+
+        world.resource_mut::<Frame>().0 = 2;
+        snapshot.restore(&mut world);
+        assert_eq!(world.resource::<Frame>(), &Frame(1));
+
+        world.remove_resource::<Frame>();
+        snapshot.restore(&mut world);
+        assert_eq!(world.resource::<Frame>(), &Frame(1));
+
+        let mut world = World::new();
+        let snapshot = WorldSnapshot::capture(&mut world, &config());
+        world.insert_resource(Frame(3));
+        snapshot.restore(&mut world);
+        assert!(!world.contains_resource::<Frame>());
+    }
+
+    #[test]
+    fn restored_values_report_changes() {
+        let mut world = World::new();
+        let kept = world.spawn(Position(1)).id();
+        let despawned = world.spawn(Position(2)).id();
+        let snapshot = WorldSnapshot::capture(&mut world, &config());
+
+        world.despawn(despawned);
+        world.clear_trackers();
+        snapshot.restore(&mut world);
+
+        let mut changed = world.query_filtered::<Entity, Changed<Position>>();
+        let mut changed: Vec<Entity> = changed.iter(&world).collect();
+        changed.sort();
+        assert_eq!(changed, vec![kept, despawned]);
+
+        let mut added = world.query_filtered::<Entity, Added<Position>>();
+        assert_eq!(added.iter(&world).collect::<Vec<_>>(), vec![despawned]);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn restores_through_reflection() {
+        use crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectResource};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Resource)]
+        struct Score(u32);
+
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Health>();
+        registry.write().register::<Score>();
+
+        let mut config = SnapshotConfig::new();
+        {
+            let registry = registry.read();
+            config
+                .reflect_component(registry.get(std::any::TypeId::of::<Health>()).unwrap())
+                .reflect_resource(registry.get(std::any::TypeId::of::<Score>()).unwrap());
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Score(5));
+        let entity = world.spawn(Health(10)).id();
+        let snapshot = WorldSnapshot::capture(&mut world, &config);
+
+        world.get_mut::<Health>(entity).unwrap().0 = 0;
+        world.resource_mut::<Score>().0 = 6;
+        let spawned = world.spawn(Health(1)).id();
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+        assert_eq!(world.resource::<Score>(), &Score(5));
+        assert!(world.get_entity(spawned).is_none());
+    }
+}
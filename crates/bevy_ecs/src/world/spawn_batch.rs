@@ -15,7 +15,16 @@ where
     I::Item: Bundle,
 {
     inner: I,
-    spawner: BundleSpawner<'w, 'w>,
+    spawner: BatchSpawner<'w>,
+}
+
+enum BatchSpawner<'w> {
+    /// Spawns each bundle as the iterator is advanced.
+    Lazy(BundleSpawner<'w, 'w>),
+    /// Bundles with [`ComponentHooks`](crate::component::ComponentHooks), or spawned while there are
+    /// [`OnAdd`](crate::observer::OnAdd) or [`OnInsert`](crate::observer::OnInsert) observers, need
+    /// access to the whole [`World`] after each spawn, so they are spawned up front and their ids are yielded here.
+    Spawned(std::vec::IntoIter<Entity>),
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -24,7 +33,7 @@ where
     I::Item: Bundle,
 {
     #[inline]
-    pub(crate) fn new(world: &'w mut World, iter: I) -> Self {
+    pub(crate) fn new(world: &'w mut World, mut iter: I) -> Self {
         // Ensure all entity allocations are accounted for so `self.entities` can realloc if
         // necessary
         world.flush();
@@ -34,6 +43,20 @@ where
         let (lower, upper) = iter.size_hint();
         let length = upper.unwrap_or(lower);
 
+        let has_hooks = world
+            .bundles
+            .init_info::<I::Item>(&mut world.components, &mut world.storages)
+            .has_hooks(&world.components)
+            || world.observers.has_on_add()
+            || world.observers.has_on_insert();
+        if has_hooks {
+            let mut entities = Vec::with_capacity(length);
+            entities.extend(iter.by_ref().map(|bundle| world.spawn(bundle).id()));
+            return Self {
+                inner: iter,
+                spawner: BatchSpawner::Spawned(entities.into_iter()),
+            };
+        }
         let bundle_info = world
             .bundles
             .init_info::<I::Item>(&mut world.components, &mut world.storages);
@@ -49,7 +72,7 @@ where
 
         Self {
             inner: iter,
-            spawner,
+            spawner: BatchSpawner::Lazy(spawner),
         }
     }
 }
@@ -72,13 +95,21 @@ where
     type Item = Entity;
 
     fn next(&mut self) -> Option<Entity> {
-        let bundle = self.inner.next()?;
-        // SAFETY: bundle matches spawner type
-        unsafe { Some(self.spawner.spawn(bundle)) }
+        match &mut self.spawner {
+            BatchSpawner::Lazy(spawner) => {
+                let bundle = self.inner.next()?;
+                // SAFETY: bundle matches spawner type
+                unsafe { Some(spawner.spawn(bundle)) }
+            }
+            BatchSpawner::Spawned(entities) => entities.next(),
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.inner.size_hint()
+        match &self.spawner {
+            BatchSpawner::Lazy(_) => self.inner.size_hint(),
+            BatchSpawner::Spawned(entities) => entities.size_hint(),
+        }
     }
 }
 
@@ -88,7 +119,10 @@ where
     T: Bundle,
 {
     fn len(&self) -> usize {
-        self.inner.len()
+        match &self.spawner {
+            BatchSpawner::Lazy(_) => self.inner.len(),
+            BatchSpawner::Spawned(entities) => entities.len(),
+        }
     }
 }
 
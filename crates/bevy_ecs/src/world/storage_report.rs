@@ -0,0 +1,335 @@
+use crate::{
+    archetype::ArchetypeId,
+    component::ComponentId,
+    storage::{ResourceData, TableId},
+    world::World,
+};
+
+/// A read-only summary of how a [`World`] stores its entities, components and resources.
+///
+/// Created with [`World::storage_report`]. Byte counts only include the memory owned directly by
+/// the storages, not heap memory owned by the component values themselves.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Position(f32, f32);
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Position(0.0, 0.0)).id();
+/// world.despawn(entity);
+///
+/// let report = world.storage_report();
+/// for archetype in report.empty_archetypes() {
+///     println!("archetype {:?} has no entities", archetype.id);
+/// }
+/// println!("{} bytes allocated but unused", report.unused_bytes());
+///
+/// world.shrink_to_fit();
+/// assert_eq!(world.storage_report().unused_bytes(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StorageReport {
+    /// Every archetype in the world.
+    pub archetypes: Vec<ArchetypeReport>,
+    /// Every table in the world.
+    pub tables: Vec<TableReport>,
+    /// Every sparse set in the world.
+    pub sparse_sets: Vec<SparseSetReport>,
+    /// Every resource in the world, including `!Send` resources.
+    pub resources: Vec<ResourceReport>,
+}
+
+/// Describes an [`Archetype`](crate::archetype::Archetype) in a [`StorageReport`].
+#[derive(Debug, Clone)]
+pub struct ArchetypeReport {
+    /// The id of the archetype.
+    pub id: ArchetypeId,
+    /// The table that stores the table components of the archetype.
+    pub table_id: TableId,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The components of the archetype.
+    pub components: Vec<ComponentId>,
+}
+
+/// Describes a [`Table`](crate::storage::Table) in a [`StorageReport`].
+#[derive(Debug, Clone)]
+pub struct TableReport {
+    /// The id of the table.
+    pub id: TableId,
+    /// The number of entities stored in the table.
+    pub entity_count: usize,
+    /// The number of entities the table can store without reallocating.
+    pub entity_capacity: usize,
+    /// The columns of the table.
+    pub columns: Vec<ColumnReport>,
+}
+
+/// Describes a [`Column`](crate::storage::Column) of a table in a [`StorageReport`].
+#[derive(Debug, Clone)]
+pub struct ColumnReport {
+    /// The component stored in the column.
+    pub component_id: ComponentId,
+    /// The size in bytes of a single component value.
+    pub item_size: usize,
+    /// The number of component values stored in the column.
+    pub len: usize,
+    /// The number of component values the column can store without reallocating.
+    pub capacity: usize,
+    /// The number of bytes used by the stored values and their change ticks.
+    pub used_bytes: usize,
+    /// The number of bytes allocated by the column.
+    pub allocated_bytes: usize,
+}
+
+/// Describes a [`ComponentSparseSet`](crate::storage::ComponentSparseSet) in a [`StorageReport`].
+#[derive(Debug, Clone)]
+pub struct SparseSetReport {
+    /// The component stored in the sparse set.
+    pub component_id: ComponentId,
+    /// The number of component values stored in the sparse set.
+    pub len: usize,
+    /// The number of component values the sparse set can store without reallocating.
+    pub capacity: usize,
+    /// The number of bytes used by the stored values, their change ticks and the entity lookup
+    /// tables.
+    pub used_bytes: usize,
+    /// The number of bytes allocated by the sparse set, including its entity lookup tables.
+    pub allocated_bytes: usize,
+}
+
+/// Describes a resource in a [`StorageReport`].
+#[derive(Debug, Clone)]
+pub struct ResourceReport {
+    /// The id of the resource.
+    pub component_id: ComponentId,
+    /// The type name of the resource.
+    pub name: String,
+    /// Whether the resource currently has a value.
+    pub is_present: bool,
+    /// Whether the resource is `Send`.
+    pub is_send: bool,
+    /// The number of bytes allocated for the resource.
+    pub allocated_bytes: usize,
+}
+
+impl TableReport {
+    /// The number of bytes allocated by the columns of the table.
+    pub fn allocated_bytes(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|column| column.allocated_bytes)
+            .sum()
+    }
+
+    /// The number of bytes used by the values stored in the table.
+    pub fn used_bytes(&self) -> usize {
+        self.columns.iter().map(|column| column.used_bytes).sum()
+    }
+}
+
+impl StorageReport {
+    pub(crate) fn new(world: &World) -> Self {
+        let archetypes = world
+            .archetypes()
+            .iter()
+            .map(|archetype| ArchetypeReport {
+                id: archetype.id(),
+                table_id: archetype.table_id(),
+                entity_count: archetype.len(),
+                components: archetype.components().collect(),
+            })
+            .collect();
+
+        let tables = world
+            .storages()
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(index, table)| TableReport {
+                id: TableId::new(index),
+                entity_count: table.entity_count(),
+                entity_capacity: table.entity_capacity(),
+                columns: table
+                    .iter_with_ids()
+                    .map(|(component_id, column)| ColumnReport {
+                        component_id,
+                        item_size: column.item_layout().size(),
+                        len: column.len(),
+                        capacity: column.capacity(),
+                        used_bytes: column.used_bytes(),
+                        allocated_bytes: column.allocated_bytes(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let sparse_sets = world
+            .storages()
+            .sparse_sets
+            .iter()
+            .map(|(component_id, sparse_set)| SparseSetReport {
+                component_id,
+                len: sparse_set.len(),
+                capacity: sparse_set.capacity(),
+                used_bytes: sparse_set.used_bytes(),
+                allocated_bytes: sparse_set.allocated_bytes(),
+            })
+            .collect();
+
+        fn resource_report<const SEND: bool>(
+            (component_id, data): (ComponentId, &ResourceData<SEND>),
+        ) -> ResourceReport {
+            ResourceReport {
+                component_id,
+                name: data.type_name().to_string(),
+                is_present: data.is_present(),
+                is_send: SEND,
+                allocated_bytes: data.allocated_bytes(),
+            }
+        }
+        let resources = world
+            .storages()
+            .resources
+            .iter()
+            .map(resource_report)
+            .chain(
+                world
+                    .storages()
+                    .non_send_resources
+                    .iter()
+                    .map(resource_report),
+            )
+            .collect();
+
+        Self {
+            archetypes,
+            tables,
+            sparse_sets,
+            resources,
+        }
+    }
+
+    /// Iterates over the archetypes that currently have no entities.
+    pub fn empty_archetypes(&self) -> impl Iterator<Item = &ArchetypeReport> {
+        self.archetypes
+            .iter()
+            .filter(|archetype| archetype.entity_count == 0)
+    }
+
+    /// The number of bytes allocated by tables, sparse sets and resources.
+    pub fn allocated_bytes(&self) -> usize {
+        self.tables
+            .iter()
+            .map(TableReport::allocated_bytes)
+            .sum::<usize>()
+            + self
+                .sparse_sets
+                .iter()
+                .map(|sparse_set| sparse_set.allocated_bytes)
+                .sum::<usize>()
+            + self
+                .resources
+                .iter()
+                .map(|resource| resource.allocated_bytes)
+                .sum::<usize>()
+    }
+
+    /// The number of bytes allocated by table columns and sparse sets that do not hold a
+    /// component value, which can be released with [`World::shrink_to_fit`].
+    pub fn unused_bytes(&self) -> usize {
+        let tables: usize = self
+            .tables
+            .iter()
+            .map(|table| table.allocated_bytes() - table.used_bytes())
+            .sum();
+        let sparse_sets: usize = self
+            .sparse_sets
+            .iter()
+            .map(|sparse_set| sparse_set.allocated_bytes - sparse_set.used_bytes)
+            .sum();
+        tables + sparse_sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{component::Component, system::Resource, world::World};
+
+    #[derive(Component)]
+    struct A(u64);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct B(u32);
+
+    #[derive(Resource)]
+    struct R([u8; 16]);
+
+    #[test]
+    fn reports_storages() {
+        let mut world = World::new();
+        world.insert_resource(R([0; 16]));
+        for i in 0..10 {
+            world.spawn((A(i), B(i as u32)));
+        }
+        let a = world.component_id::<A>().unwrap();
+        let b = world.component_id::<B>().unwrap();
+
+        let report = world.storage_report();
+        let archetype = report
+            .archetypes
+            .iter()
+            .find(|archetype| archetype.components.contains(&a))
+            .unwrap();
+        assert_eq!(archetype.entity_count, 10);
+        assert!(archetype.components.contains(&b));
+
+        let table = &report.tables[archetype.table_id.index()];
+        assert_eq!(table.entity_count, 10);
+        assert_eq!(table.columns.len(), 1);
+        assert_eq!(table.columns[0].component_id, a);
+        assert_eq!(table.columns[0].item_size, 8);
+        assert!(table.allocated_bytes() >= table.used_bytes());
+
+        let sparse_set = &report.sparse_sets[0];
+        assert_eq!(sparse_set.component_id, b);
+        assert_eq!(sparse_set.len, 10);
+
+        let resource = &report.resources[0];
+        assert!(resource.is_present && resource.is_send);
+        assert!(resource.allocated_bytes >= world.resource::<R>().0.len());
+
+        let mut query = world.query::<(&A, &B)>();
+        assert!(query.iter(&world).all(|(a, b)| a.0 == b.0 as u64));
+    }
+
+    #[test]
+    fn shrink_to_fit_releases_unused_memory() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..100).map(|i| world.spawn((A(i), B(0))).id()).collect();
+        for entity in &entities[1..] {
+            world.despawn(*entity);
+        }
+        let before = world.storage_report();
+        assert!(before.unused_bytes() > 0);
+        let empty_archetypes = before.empty_archetypes().count();
+
+        world.shrink_to_fit();
+        let after = world.storage_report();
+        assert!(after.allocated_bytes() < before.allocated_bytes());
+        assert!(after.unused_bytes() < before.unused_bytes());
+        assert!(world.get::<A>(entities[0]).is_some());
+
+        world.despawn(entities[0]);
+        assert_eq!(
+            world.storage_report().empty_archetypes().count(),
+            empty_archetypes + 1
+        );
+        world.shrink_to_fit();
+        assert_eq!(world.storage_report().allocated_bytes(), 0);
+        world.spawn((A(1), B(1)));
+    }
+}
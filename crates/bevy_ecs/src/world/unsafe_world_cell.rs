@@ -6,7 +6,7 @@ use super::{Mut, Ref, World, WorldId};
 use crate::{
     archetype::{Archetype, ArchetypeComponentId, Archetypes},
     bundle::Bundles,
-    change_detection::{MutUntyped, Ticks, TicksMut},
+    change_detection::{MutUntyped, PreviousSlot, Ticks, TicksMut},
     component::{
         ComponentId, ComponentStorage, ComponentTicks, Components, StorageType, Tick, TickCells,
     },
@@ -698,6 +698,7 @@ impl<'w> UnsafeEntityCell<'w> {
                 // SAFETY: returned component is of type T
                 value: value.deref::<T>(),
                 ticks: Ticks::from_tick_cells(cells, last_change_tick, change_tick),
+                previous: self.previous_slot(component_id),
             })
         }
     }
@@ -793,16 +794,35 @@ impl<'w> UnsafeEntityCell<'w> {
                 self.entity,
                 self.location,
             )
-            .map(|(value, cells)| Mut {
-                // SAFETY: returned component is of type T
-                value: value.assert_unique().deref_mut::<T>(),
-                ticks: TicksMut::from_tick_cells(cells, last_change_tick, change_tick),
+            .map(|(value, cells)| {
+                let mut ticks = TicksMut::from_tick_cells(cells, last_change_tick, change_tick);
+                ticks.previous = self.previous_slot(component_id);
+                Mut {
+                    // SAFETY: returned component is of type T
+                    value: value.assert_unique().deref_mut::<T>(),
+                    ticks,
+                }
             })
         }
     }
 }
 
 impl<'w> UnsafeEntityCell<'w> {
+    /// Returns where the previous value of the component is stored, if it tracks it.
+    ///
+    /// # Safety
+    /// The returned slot must only be used while the [`UnsafeEntityCell`] has permission to access
+    /// the component.
+    #[inline]
+    unsafe fn previous_slot(self, component_id: ComponentId) -> Option<PreviousSlot<'w>> {
+        // SAFETY: only the previous value of the component of this entity is accessed, as
+        // ensured by the caller
+        let values = unsafe { self.world.storages() }
+            .previous_values
+            .get(component_id);
+        PreviousSlot::new(values, self.entity)
+    }
+
     /// Gets the component of the given [`ComponentId`] from the entity.
     ///
     /// **You should prefer to use the typed API where possible and only
@@ -853,14 +873,18 @@ impl<'w> UnsafeEntityCell<'w> {
                 self.entity,
                 self.location,
             )
-            .map(|(value, cells)| MutUntyped {
-                // SAFETY: world access validated by caller and ties world lifetime to `MutUntyped` lifetime
-                value: value.assert_unique(),
-                ticks: TicksMut::from_tick_cells(
+            .map(|(value, cells)| {
+                let mut ticks = TicksMut::from_tick_cells(
                     cells,
                     self.world.last_change_tick(),
                     self.world.change_tick(),
-                ),
+                );
+                ticks.previous = self.previous_slot(component_id);
+                MutUntyped {
+                    // SAFETY: world access validated by caller and ties world lifetime to `MutUntyped` lifetime
+                    value: value.assert_unique(),
+                    ticks,
+                }
             })
         }
     }
@@ -1,7 +1,7 @@
 use crate::{vertex_attributes::*, Gltf, GltfExtras, GltfNode};
 use anyhow::Result;
 use bevy_asset::{
-    AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, HandleId, LoadContext, LoadedAsset,
+    AssetIoError, AssetLoader, BoxedFuture, Handle, HandleId, LoadContext, LoadedAsset,
 };
 use bevy_core::Name;
 use bevy_core_pipeline::prelude::Camera3dBundle;
@@ -37,7 +37,7 @@ use gltf::{
     texture::{MagFilter, MinFilter, WrappingMode},
     Material, Node, Primitive,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{collections::VecDeque, path::Path};
 use thiserror::Error;
 
@@ -74,13 +74,35 @@ pub struct GltfLoader {
     pub custom_vertex_attributes: HashMap<String, MeshVertexAttribute>,
 }
 
+/// Settings of the [`GltfLoader`], usually read from the `.meta` file of a glTF file.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct GltfLoaderSettings {
+    /// Whether the cameras of the glTF file are added to its scenes.
+    pub load_cameras: bool,
+    /// Whether the lights of the glTF file are added to its scenes.
+    pub load_lights: bool,
+}
+
+impl Default for GltfLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_cameras: true,
+            load_lights: true,
+        }
+    }
+}
+
 impl AssetLoader for GltfLoader {
+    type Settings = GltfLoaderSettings;
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
+        settings: &'a GltfLoaderSettings,
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
-        Box::pin(async move { Ok(load_gltf(bytes, load_context, self).await?) })
+        Box::pin(async move { Ok(load_gltf(bytes, load_context, self, settings).await?) })
     }
 
     fn extensions(&self) -> &[&str] {
@@ -93,6 +115,7 @@ async fn load_gltf<'a, 'b>(
     bytes: &'a [u8],
     load_context: &'a mut LoadContext<'b>,
     loader: &GltfLoader,
+    settings: &GltfLoaderSettings,
 ) -> Result<(), GltfError> {
     let gltf = gltf::Gltf::from_slice(bytes)?;
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
@@ -465,6 +488,7 @@ async fn load_gltf<'a, 'b>(
                         &mut node_index_to_entity_map,
                         &mut entity_to_skin_index_map,
                         &mut active_camera_found,
+                        settings,
                     );
                     if result.is_err() {
                         err = Some(result);
@@ -625,7 +649,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
     let base_color_texture = pbr.base_color_texture().map(|info| {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.labeled_asset_path(&label);
         load_context.get_handle(path)
     });
 
@@ -634,14 +658,14 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
             // TODO: handle normal_texture.scale
             // TODO: handle normal_texture.tex_coord() (the *set* index for the right texcoords)
             let label = texture_label(&normal_texture.texture());
-            let path = AssetPath::new_ref(load_context.path(), Some(&label));
+            let path = load_context.labeled_asset_path(&label);
             load_context.get_handle(path)
         });
 
     let metallic_roughness_texture = pbr.metallic_roughness_texture().map(|info| {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.labeled_asset_path(&label);
         load_context.get_handle(path)
     });
 
@@ -649,7 +673,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&occlusion_texture.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.labeled_asset_path(&label);
         load_context.get_handle(path)
     });
 
@@ -658,7 +682,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.labeled_asset_path(&label);
         load_context.get_handle(path)
     });
 
@@ -695,6 +719,7 @@ fn load_node(
     node_index_to_entity_map: &mut HashMap<usize, Entity>,
     entity_to_skin_index_map: &mut HashMap<Entity, usize>,
     active_camera_found: &mut bool,
+    settings: &GltfLoaderSettings,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
     let mut gltf_error = None;
@@ -710,7 +735,7 @@ fn load_node(
     }
 
     // create camera node
-    if let Some(camera) = gltf_node.camera() {
+    if let Some(camera) = gltf_node.camera().filter(|_| settings.load_cameras) {
         let projection = match camera.projection() {
             gltf::camera::Projection::Orthographic(orthographic) => {
                 let xmag = orthographic.xmag();
@@ -759,7 +784,7 @@ fn load_node(
         if let Some(weights) = mesh.weights() {
             let first_mesh = if let Some(primitive) = mesh.primitives().next() {
                 let primitive_label = primitive_label(&mesh, &primitive);
-                let path = AssetPath::new_ref(load_context.path(), Some(&primitive_label));
+                let path = load_context.labeled_asset_path(&primitive_label);
                 Some(Handle::weak(HandleId::from(path)))
             } else {
                 None
@@ -784,10 +809,8 @@ fn load_node(
 
                 let primitive_label = primitive_label(&mesh, &primitive);
                 let bounds = primitive.bounding_box();
-                let mesh_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&primitive_label));
-                let material_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&material_label));
+                let mesh_asset_path = load_context.labeled_asset_path(&primitive_label);
+                let material_asset_path = load_context.labeled_asset_path(&material_label);
 
                 let mut primitive_entity = parent.spawn(PbrBundle {
                     mesh: load_context.get_handle(mesh_asset_path),
@@ -827,7 +850,7 @@ fn load_node(
             }
         }
 
-        if let Some(light) = gltf_node.light() {
+        if let Some(light) = gltf_node.light().filter(|_| settings.load_lights) {
             match light.kind() {
                 gltf::khr_lights_punctual::Kind::Directional => {
                     let mut entity = parent.spawn(DirectionalLightBundle {
@@ -912,6 +935,7 @@ fn load_node(
                 node_index_to_entity_map,
                 entity_to_skin_index_map,
                 active_camera_found,
+                settings,
             ) {
                 gltf_error = Some(err);
                 return;
@@ -16,55 +16,20 @@ fn push_events(world: &mut World, events: impl IntoIterator<Item = HierarchyEven
     }
 }
 
-/// Adds `child` to `parent`'s [`Children`], without checking if it is already present there.
+/// Sets [`Parent`] of the `child` to `new_parent`, inserting it if `child` doesn't have one.
 ///
-/// This might cause unexpected results when removing duplicate children.
-fn push_child_unchecked(world: &mut World, parent: Entity, child: Entity) {
-    let mut parent = world.entity_mut(parent);
-    if let Some(mut children) = parent.get_mut::<Children>() {
-        children.0.push(child);
-    } else {
-        parent.insert(Children(smallvec::smallvec![child]));
-    }
-}
-
-/// Sets [`Parent`] of the `child` to `new_parent`. Inserts [`Parent`] if `child` doesn't have one.
+/// The hooks of [`Parent`] remove `child` from its previous parent's [`Children`] and push it to
+/// the back of `new_parent`'s [`Children`], even if it was already a child of `new_parent`.
 fn update_parent(world: &mut World, child: Entity, new_parent: Entity) -> Option<Entity> {
     let mut child = world.entity_mut(child);
-    if let Some(mut parent) = child.get_mut::<Parent>() {
-        let previous = parent.0;
-        *parent = Parent(new_parent);
-        Some(previous)
-    } else {
-        child.insert(Parent(new_parent));
-        None
-    }
+    let previous = child.get::<Parent>().map(Parent::get);
+    child.insert(Parent(new_parent));
+    previous
 }
 
-/// Remove child from the parent's [`Children`] component.
-///
-/// Removes the [`Children`] component from the parent if it's empty.
-fn remove_from_children(world: &mut World, parent: Entity, child: Entity) {
-    let Some(mut parent) = world.get_entity_mut(parent) else {
-        return;
-    };
-    let Some(mut children) = parent.get_mut::<Children>() else {
-        return;
-    };
-    children.0.retain(|x| *x != child);
-    if children.is_empty() {
-        parent.remove::<Children>();
-    }
-}
-
-/// Update the [`Parent`] component of the `child`.
-/// Removes the `child` from the previous parent's [`Children`].
-///
-/// Does not update the new parents [`Children`] component.
+/// Update the [`Parent`] component of the `child`, moving it to the back of `parent`'s [`Children`].
 ///
-/// Does nothing if `child` was already a child of `parent`.
-///
-/// Sends [`HierarchyEvent`]'s.
+/// Sends [`HierarchyEvent`]'s, unless `child` was already a child of `parent`.
 fn update_old_parent(world: &mut World, child: Entity, parent: Entity) {
     let previous = update_parent(world, child, parent);
     if let Some(previous_parent) = previous {
@@ -72,7 +37,6 @@ fn update_old_parent(world: &mut World, child: Entity, parent: Entity) {
         if previous_parent == parent {
             return;
         }
-        remove_from_children(world, previous_parent, child);
 
         push_events(
             world,
@@ -87,14 +51,10 @@ fn update_old_parent(world: &mut World, child: Entity, parent: Entity) {
     }
 }
 
-/// Update the [`Parent`] components of the `children`.
-/// Removes the `children` from their previous parent's [`Children`].
-///
-/// Does not update the new parents [`Children`] component.
+/// Update the [`Parent`] components of the `children`, moving them to the back of `parent`'s
+/// [`Children`] in order.
 ///
-/// Does nothing for a child if it was already a child of `parent`.
-///
-/// Sends [`HierarchyEvent`]'s.
+/// Sends [`HierarchyEvent`]'s for the children that were not already children of `parent`.
 fn update_old_parents(world: &mut World, parent: Entity, children: &[Entity]) {
     let mut events: SmallVec<[HierarchyEvent; 8]> = SmallVec::with_capacity(children.len());
     for &child in children {
@@ -104,7 +64,6 @@ fn update_old_parents(world: &mut World, parent: Entity, children: &[Entity]) {
                 continue;
             }
 
-            remove_from_children(world, previous, child);
             events.push(HierarchyEvent::ChildMoved {
                 child,
                 previous_parent: previous,
@@ -117,8 +76,8 @@ fn update_old_parents(world: &mut World, parent: Entity, children: &[Entity]) {
     push_events(world, events);
 }
 
-/// Removes entities in `children` from `parent`'s [`Children`], removing the component if it ends up empty.
-/// Also removes [`Parent`] component from `children`.
+/// Removes [`Parent`] from the entities in `children` that are children of `parent`, which removes
+/// them from `parent`'s [`Children`] and removes that component if it ends up empty.
 fn remove_children(parent: Entity, children: &[Entity], world: &mut World) {
     let mut events: SmallVec<[HierarchyEvent; 8]> = SmallVec::new();
     if let Some(parent_children) = world.get::<Children>(parent) {
@@ -136,27 +95,12 @@ fn remove_children(parent: Entity, children: &[Entity], world: &mut World) {
         }
     }
     push_events(world, events);
-
-    let mut parent = world.entity_mut(parent);
-    if let Some(mut parent_children) = parent.get_mut::<Children>() {
-        parent_children
-            .0
-            .retain(|parent_child| !children.contains(parent_child));
-
-        if parent_children.is_empty() {
-            parent.remove::<Children>();
-        }
-    }
 }
 
-/// Removes all children from `parent` by removing its [`Children`] component, as well as removing
-/// [`Parent`] component from its children.
+/// Removes all children from `parent` by removing its [`Children`] component, which also removes
+/// the [`Parent`] component from its children.
 fn clear_children(parent: Entity, world: &mut World) {
-    if let Some(children) = world.entity_mut(parent).take::<Children>() {
-        for &child in &children.0 {
-            world.entity_mut(child).remove::<Parent>();
-        }
-    }
+    world.entity_mut(parent).remove::<Children>();
 }
 
 /// Command that adds a child to an entity.
@@ -462,7 +406,6 @@ impl<'w> WorldChildBuilder<'w> {
     /// Also adds [`Parent`] component to the created entity.
     pub fn spawn(&mut self, bundle: impl Bundle + Send + Sync + 'static) -> EntityMut<'_> {
         let entity = self.world.spawn((bundle, Parent(self.parent))).id();
-        push_child_unchecked(self.world, self.parent, entity);
         push_events(
             self.world,
             [HierarchyEvent::ChildAdded {
@@ -477,7 +420,6 @@ impl<'w> WorldChildBuilder<'w> {
     /// Also adds [`Parent`] component to the created entity.
     pub fn spawn_empty(&mut self) -> EntityMut<'_> {
         let entity = self.world.spawn(Parent(self.parent)).id();
-        push_child_unchecked(self.world, self.parent, entity);
         push_events(
             self.world,
             [HierarchyEvent::ChildAdded {
@@ -571,12 +513,6 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
         self.world_scope(|world| {
             update_old_parent(world, child, parent);
         });
-        if let Some(mut children_component) = self.get_mut::<Children>() {
-            children_component.0.retain(|value| child != *value);
-            children_component.0.push(child);
-        } else {
-            self.insert(Children::from_entities(&[child]));
-        }
         self
     }
 
@@ -588,14 +524,6 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
         self.world_scope(|world| {
             update_old_parents(world, parent, children);
         });
-        if let Some(mut children_component) = self.get_mut::<Children>() {
-            children_component
-                .0
-                .retain(|value| !children.contains(value));
-            children_component.0.extend(children.iter().cloned());
-        } else {
-            self.insert(Children::from_entities(children));
-        }
         self
     }
 
@@ -607,13 +535,12 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
         self.world_scope(|world| {
             update_old_parents(world, parent, children);
         });
+        // The children were pushed to the back, move them to `index`.
         if let Some(mut children_component) = self.get_mut::<Children>() {
             children_component
                 .0
                 .retain(|value| !children.contains(value));
             children_component.0.insert_from_slice(index, children);
-        } else {
-            self.insert(Children::from_entities(children));
         }
         self
     }
@@ -638,7 +565,6 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
         let child = self.id();
         if let Some(parent) = self.take::<Parent>().map(|p| p.get()) {
             self.world_scope(|world| {
-                remove_from_children(world, parent, child);
                 push_events(world, [HierarchyEvent::ChildRemoved { child, parent }]);
             });
         }
@@ -657,7 +583,7 @@ mod tests {
 
     use bevy_ecs::{
         component::Component,
-        entity::Entity,
+        entity::{Entity, EntityCloneBuilder},
         event::Events,
         system::{CommandQueue, Commands},
         world::World,
@@ -1136,4 +1062,44 @@ mod tests {
         let children = query.get(&world, parent).unwrap();
         assert_eq!(**children, [child]);
     }
+
+    #[test]
+    fn children_follow_parent_component() {
+        let world = &mut World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world.entity_mut(b).insert(Parent(a));
+        world.entity_mut(c).insert(Parent(a));
+        assert_children(world, a, Some(&[b, c]));
+
+        world.entity_mut(b).insert(Parent(c));
+        assert_children(world, a, Some(&[c]));
+        assert_children(world, c, Some(&[b]));
+
+        world.despawn(c);
+        assert_children(world, a, None);
+        assert_parent(world, b, None);
+    }
+
+    #[test]
+    fn clone_recursive_follows_children() {
+        let world = &mut World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.entity_mut(a).push_children(&[b, c]);
+
+        let clone = EntityCloneBuilder::new()
+            .recursive(true)
+            .clone_entity(world, a);
+
+        let cloned_children = world.get::<Children>(clone).unwrap().to_vec();
+        assert_eq!(cloned_children.len(), 2);
+        assert!(!cloned_children.contains(&b) && !cloned_children.contains(&c));
+        assert_parent(world, cloned_children[0], Some(clone));
+        assert_parent(world, cloned_children[1], Some(clone));
+        assert_children(world, a, Some(&[b, c]));
+
+        let single = world.clone_entity(b);
+        assert_parent(world, single, Some(a));
+        assert_children(world, a, Some(&[b, c, single]));
+    }
 }
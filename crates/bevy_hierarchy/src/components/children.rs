@@ -10,14 +10,21 @@ use core::slice;
 use smallvec::SmallVec;
 use std::ops::Deref;
 
+use crate::Parent;
+
 /// Contains references to the child entities of this entity.
 ///
+/// This is the [`RelationshipTarget`](bevy_ecs::relationship::RelationshipTarget) side of the
+/// hierarchy: it is kept up to date by the [`Parent`] components of the children, and removing it
+/// (for example by despawning the entity) removes [`Parent`] from every child.
+///
 /// See [`HierarchyQueryExt`] for hierarchy related methods on [`Query`].
 ///
 /// [`HierarchyQueryExt`]: crate::query_extension::HierarchyQueryExt
 /// [`Query`]: bevy_ecs::system::Query
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component, MapEntities)]
+#[relationship_target(relationship = Parent, despawn_behavior = "Remove")]
 pub struct Children(pub(crate) SmallVec<[Entity; 8]>);
 
 impl MapEntities for Children {
@@ -39,11 +46,6 @@ impl FromWorld for Children {
 }
 
 impl Children {
-    /// Constructs a [`Children`] component with the given entities.
-    pub(crate) fn from_entities(entities: &[Entity]) -> Self {
-        Self(SmallVec::from_slice(entities))
-    }
-
     /// Swaps the child at `a_index` with the child at `b_index`.
     pub fn swap(&mut self, a_index: usize, b_index: usize) {
         self.0.swap(a_index, b_index);
@@ -1,21 +1,28 @@
 use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityMapper, MapEntities},
+    observer::Traversal,
     reflect::{ReflectComponent, ReflectMapEntities},
     world::{FromWorld, World},
 };
 use bevy_reflect::Reflect;
 use std::ops::Deref;
 
+use crate::Children;
+
 /// Holds a reference to the parent entity of this entity.
 /// This component should only be present on entities that actually have a parent entity.
 ///
+/// This is the [`Relationship`](bevy_ecs::relationship::Relationship) side of the hierarchy:
+/// inserting, replacing or removing it keeps the parent's [`Children`] in sync.
+///
 /// See [`HierarchyQueryExt`] for hierarchy related methods on [`Query`].
 ///
 /// [`HierarchyQueryExt`]: crate::query_extension::HierarchyQueryExt
 /// [`Query`]: bevy_ecs::system::Query
 #[derive(Component, Debug, Eq, PartialEq, Reflect)]
 #[reflect(Component, MapEntities, PartialEq)]
+#[relationship(relationship_target = Children)]
 pub struct Parent(pub(crate) Entity);
 
 impl Parent {
@@ -41,6 +48,15 @@ impl MapEntities for Parent {
     }
 }
 
+/// Lets [events](bevy_ecs::event::Event) derived with `#[event(traversal = Parent)]`
+/// propagate from children to their parents when triggered with
+/// [`World::trigger_targets`].
+impl Traversal for Parent {
+    fn traverse(world: &World, entity: Entity) -> Option<Entity> {
+        world.get::<Parent>(entity).map(Parent::get)
+    }
+}
+
 impl Deref for Parent {
     type Target = Entity;
 
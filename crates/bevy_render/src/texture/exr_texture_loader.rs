@@ -10,9 +10,12 @@ use wgpu::{Extent3d, TextureDimension, TextureFormat};
 pub struct ExrTextureLoader;
 
 impl AssetLoader for ExrTextureLoader {
+    type Settings = ();
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
+        _settings: &'a (),
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
@@ -2,11 +2,12 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -17,6 +18,37 @@ pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
 
+/// The sampler used for an image loaded by the [`ImageTextureLoader`].
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSamplerSetting {
+    /// Uses [`ImageSampler::Default`].
+    #[default]
+    Default,
+    /// Uses [`ImageSampler::linear`].
+    Linear,
+    /// Uses [`ImageSampler::nearest`].
+    Nearest,
+}
+
+/// Settings of the [`ImageTextureLoader`], usually read from the `.meta` file of an image.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct ImageLoaderSettings {
+    /// Whether the image holds sRGB color data. Should be `false` for data such as normal maps.
+    pub is_srgb: bool,
+    /// The sampler used for the image.
+    pub sampler: ImageSamplerSetting,
+}
+
+impl Default for ImageLoaderSettings {
+    fn default() -> Self {
+        Self {
+            is_srgb: true,
+            sampler: ImageSamplerSetting::Default,
+        }
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "basis-universal")]
     "basis",
@@ -47,25 +79,33 @@ const FILE_EXTENSIONS: &[&str] = &[
 ];
 
 impl AssetLoader for ImageTextureLoader {
+    type Settings = ImageLoaderSettings;
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
+        settings: &'a ImageLoaderSettings,
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            dyn_img.sampler_descriptor = match settings.sampler {
+                ImageSamplerSetting::Default => ImageSampler::Default,
+                ImageSamplerSetting::Linear => ImageSampler::linear(),
+                ImageSamplerSetting::Nearest => ImageSampler::nearest(),
+            };
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
@@ -1,5 +1,3 @@
-use std::any::TypeId;
-
 use crate::{DynamicSceneBuilder, Scene, SceneSpawnError};
 use anyhow::Result;
 use bevy_ecs::{
@@ -8,7 +6,6 @@ use bevy_ecs::{
     world::World,
 };
 use bevy_reflect::{Reflect, TypePath, TypeRegistryArc, TypeUuid};
-use bevy_utils::HashMap;
 
 #[cfg(feature = "serialize")]
 use crate::serde::SceneSerializer;
@@ -89,20 +86,21 @@ impl DynamicScene {
             reflect_resource.apply_or_insert(world, &**resource);
         }
 
-        // For each component types that reference other entities, we keep track
-        // of which entities in the scene use that component.
-        // This is so we can update the scene-internal references to references
-        // of the actual entities in the world.
-        let mut scene_mappings: HashMap<TypeId, Vec<Entity>> = HashMap::default();
-
+        // Reserve a world entity for every scene entity up front, so that references
+        // between scene entities can be mapped before the components are inserted.
         for scene_entity in &self.entities {
             // Fetch the entity with the given entity id from the `entity_map`
             // or spawn a new entity with a transiently unique id if there is
             // no corresponding entry.
-            let entity = *entity_map
+            entity_map
                 .entry(scene_entity.entity)
                 .or_insert_with(|| world.spawn_empty().id());
-            let entity_mut = &mut world.entity_mut(entity);
+        }
+
+        for scene_entity in &self.entities {
+            let entity = entity_map
+                .get(scene_entity.entity)
+                .expect("scene entities were added to the entity map above");
 
             // Apply/ add each component to the given entity.
             for component in &scene_entity.components {
@@ -118,29 +116,21 @@ impl DynamicScene {
                         }
                     })?;
 
-                // If this component references entities in the scene, track it
-                // so we can update it to the entity in the world.
-                if registration.data::<ReflectMapEntities>().is_some() {
-                    scene_mappings
-                        .entry(registration.type_id())
-                        .or_insert(Vec::new())
-                        .push(entity);
+                // If this component references entities in the scene, update it to
+                // reference the entities in the world before inserting it, so that
+                // its hooks only ever observe world entities.
+                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                    let mut mapped = reflect_component.from_world(world);
+                    mapped.apply(&**component);
+                    map_entities_reflect.map_reflect_entities(world, entity_map, &mut *mapped);
+                    reflect_component.insert(&mut world.entity_mut(entity), &*mapped);
+                    continue;
                 }
 
                 // If the entity already has the given component attached,
                 // just apply the (possibly) new value, otherwise add the
                 // component to the entity.
-                reflect_component.apply_or_insert(entity_mut, &**component);
-            }
-        }
-
-        // Updates references to entities in the scene to entities in the world
-        for (type_id, entities) in scene_mappings.into_iter() {
-            let registration = type_registry.get(type_id).expect(
-                "we should be getting TypeId from this TypeRegistration in the first place",
-            );
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect.map_entities(world, entity_map, &entities);
+                reflect_component.apply_or_insert(&mut world.entity_mut(entity), &**component);
             }
         }
 
@@ -87,12 +87,23 @@ impl Scene {
             reflect_resource.copy(&self.world, world);
         }
 
+        // Reserve a world entity for every scene entity up front, so that references
+        // between scene entities can be mapped before the components are inserted.
         for archetype in self.world.archetypes().iter() {
             for scene_entity in archetype.entities() {
-                let entity = *instance_info
+                instance_info
                     .entity_map
                     .entry(scene_entity.entity())
                     .or_insert_with(|| world.spawn_empty().id());
+            }
+        }
+
+        for archetype in self.world.archetypes().iter() {
+            for scene_entity in archetype.entities() {
+                let entity = instance_info
+                    .entity_map
+                    .get(scene_entity.entity())
+                    .expect("scene entities were added to the entity map above");
                 for component_id in archetype.components() {
                     let component_info = self
                         .world
@@ -100,26 +111,36 @@ impl Scene {
                         .get_info(component_id)
                         .expect("component_ids in archetypes should have ComponentInfo");
 
-                    let reflect_component = type_registry
+                    let registration = type_registry
                         .get(component_info.type_id().unwrap())
                         .ok_or_else(|| SceneSpawnError::UnregisteredType {
                             type_name: component_info.name().to_string(),
-                        })
-                        .and_then(|registration| {
-                            registration.data::<ReflectComponent>().ok_or_else(|| {
-                                SceneSpawnError::UnregisteredComponent {
-                                    type_name: component_info.name().to_string(),
-                                }
-                            })
                         })?;
-                    reflect_component.copy(&self.world, world, scene_entity.entity(), entity);
-                }
-            }
-        }
+                    let reflect_component =
+                        registration.data::<ReflectComponent>().ok_or_else(|| {
+                            SceneSpawnError::UnregisteredComponent {
+                                type_name: component_info.name().to_string(),
+                            }
+                        })?;
 
-        for registration in type_registry.iter() {
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect.map_all_entities(world, &mut instance_info.entity_map);
+                    // Components referencing scene entities are mapped to world entities
+                    // before being inserted, so that their hooks only observe world entities.
+                    if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                        let component = reflect_component
+                            .reflect(self.world.entity(scene_entity.entity()))
+                            .expect("components in the archetype exist on its entities");
+                        let mut mapped = reflect_component.from_world(world);
+                        mapped.apply(component);
+                        map_entities_reflect.map_reflect_entities(
+                            world,
+                            &mut instance_info.entity_map,
+                            &mut *mapped,
+                        );
+                        reflect_component.insert(&mut world.entity_mut(entity), &*mapped);
+                    } else {
+                        reflect_component.copy(&self.world, world, scene_entity.entity(), entity);
+                    }
+                }
             }
         }
 
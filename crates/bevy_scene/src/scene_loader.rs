@@ -26,9 +26,12 @@ impl FromWorld for SceneLoader {
 
 #[cfg(feature = "serialize")]
 impl AssetLoader for SceneLoader {
+    type Settings = ();
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
+        _settings: &'a (),
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
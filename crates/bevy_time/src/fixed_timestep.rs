@@ -23,7 +23,7 @@
 
 use crate::Time;
 use bevy_app::FixedUpdate;
-use bevy_ecs::{system::Resource, world::World};
+use bevy_ecs::{event::EventUpdateSignal, system::Resource, world::World};
 use bevy_utils::Duration;
 use thiserror::Error;
 
@@ -100,12 +100,22 @@ pub enum FixedUpdateError {
 }
 
 /// Ticks the [`FixedTime`] resource then runs the [`FixedUpdate`].
+///
+/// Events are only updated after [`FixedUpdate`] ran, see [`EventUpdateSignal`], except in frames
+/// where time does not advance (for example while [`Time`] is paused), as [`FixedUpdate`] cannot
+/// run until it does.
 pub fn run_fixed_update_schedule(world: &mut World) {
     // Tick the time
     let delta_time = world.resource::<Time>().delta();
     let mut fixed_time = world.resource_mut::<FixedTime>();
     fixed_time.tick(delta_time);
 
+    if delta_time.is_zero() {
+        if let Some(mut signal) = world.get_resource_mut::<EventUpdateSignal>() {
+            signal.signal();
+        }
+    }
+
     // Run the schedule until we run out of accumulated time
     let _ = world.try_schedule_scope(FixedUpdate, |world, schedule| {
         while world.resource_mut::<FixedTime>().expend().is_ok() {
@@ -155,4 +165,72 @@ mod test {
         assert!(fixed_time.expend().is_ok());
         assert!(fixed_time.expend().is_err());
     }
+
+    #[test]
+    fn fixed_update_reads_every_event_once() {
+        use crate::{TimePlugin, TimeUpdateStrategy};
+        use bevy_app::{App, Update};
+        use bevy_ecs::prelude::*;
+
+        #[derive(Event)]
+        struct Input(usize);
+
+        #[derive(Resource, Default)]
+        struct Received(Vec<usize>);
+
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(FixedTime::new_from_secs(1.))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                250,
+            )))
+            .add_event::<Input>()
+            .init_resource::<Received>()
+            .add_systems(
+                Update,
+                |mut sent: Local<usize>, mut inputs: EventWriter<Input>| {
+                    inputs.send(Input(*sent));
+                    *sent += 1;
+                },
+            )
+            .add_systems(
+                FixedUpdate,
+                |mut inputs: EventReader<Input>, mut received: ResMut<Received>| {
+                    received.0.extend(inputs.iter().map(|input| input.0));
+                },
+            );
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let received = &app.world.resource::<Received>().0;
+        // Events sent since the last fixed update have not been read yet.
+        assert!(received.len() >= 16);
+        assert!(received.iter().copied().eq(0..received.len()));
+    }
+
+    #[test]
+    fn events_are_updated_while_time_is_paused() {
+        use crate::{TimePlugin, TimeUpdateStrategy};
+        use bevy_app::App;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Event)]
+        struct Input;
+
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                250,
+            )))
+            .add_event::<Input>();
+        app.world.resource_mut::<Time>().pause();
+
+        app.world.send_event(Input);
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app.world.resource::<Events<Input>>().is_empty());
+    }
 }
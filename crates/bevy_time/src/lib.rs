@@ -24,6 +24,9 @@ pub mod prelude {
 }
 
 use bevy_app::{prelude::*, RunFixedUpdateLoop};
+use bevy_ecs::event::{
+    reset_event_update_signal_system, signal_event_update_system, EventUpdateSignal, EventUpdates,
+};
 use bevy_ecs::prelude::*;
 
 use crate::fixed_timestep::run_fixed_update_schedule;
@@ -48,6 +51,12 @@ impl Plugin for TimePlugin {
             .add_systems(First, time_system.in_set(TimeSystem))
             .add_systems(RunFixedUpdateLoop, run_fixed_update_schedule);
 
+        // Only update events after `FixedUpdate` ran, so that fixed timestep systems see every event.
+        // `run_fixed_update_schedule` also allows it in frames where time does not advance.
+        app.init_resource::<EventUpdateSignal>()
+            .add_systems(FixedUpdate, signal_event_update_system)
+            .add_systems(First, reset_event_update_signal_system.after(EventUpdates));
+
         #[cfg(feature = "bevy_ci_testing")]
         if let Some(ci_testing_config) = app
             .world
@@ -18,9 +18,12 @@ pub struct CustomAsset {
 pub struct CustomAssetLoader;
 
 impl AssetLoader for CustomAssetLoader {
+    type Settings = ();
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
+        _settings: &'a (),
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
         Box::pin(async move {